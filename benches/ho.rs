@@ -7,7 +7,7 @@ use xdevs::{
 extern crate alloc;
 
 fn bench_ho(c: &mut Criterion) {
-    use xdevs::simulation::{AbstractSimulator, Simulable};
+    use xdevs::simulation::{reset::Reset, AbstractSimulator, Simulable};
     const W: usize = 399; // WIDTH - 1
     generate_ho_box!(400, 400);
     let generator = JobGenerator::new(5);
@@ -18,9 +18,25 @@ fn bench_ho(c: &mut Criterion) {
     let mut group = c.benchmark_group("ho-group");
     group.bench_function("ho-sim", |b| {
         b.iter(|| {
+            // Every iteration simulates the same scenario from the initial state
+            simulator.reset();
             simulator.simulate_vt(&config);
         })
     });
+
+    // Same model, but the inner levels share the input bags of their parents
+    {
+        use xdevs::devstone::ho_box_shared::TopModel;
+        xdevs::generate_ho_box_shared!(400, 400);
+        let top_model: TopModel<W> = TopModel::build(JobGenerator::new(5), model_ho);
+        let mut simulator = top_model.to_simulator();
+        group.bench_function("ho-sim-shared", |b| {
+            b.iter(|| {
+                simulator.reset();
+                simulator.simulate_vt(&config);
+            })
+        });
+    }
     group.finish();
 }
 
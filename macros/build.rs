@@ -1,7 +1,11 @@
 fn main() {
+    // The portable backend does not depend on any executor, so it can be combined with any other backend.
     let backends: Vec<_> = std::env::vars()
         .filter_map(|(key, _value)| {
-            if key.starts_with("CARGO_FEATURE_") && key.ends_with("_BACKEND") {
+            if key.starts_with("CARGO_FEATURE_")
+                && key.ends_with("_BACKEND")
+                && key != "CARGO_FEATURE_PORTABLE_BACKEND"
+            {
                 Some(key[14..].to_ascii_lowercase()) // Strip 'CARGO_FEATURE_'
             } else {
                 None
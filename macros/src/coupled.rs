@@ -1,8 +1,8 @@
-use crate::combine_err;
+use crate::{combine_err, to_component::ComponentArgs};
 use proc_macro2::TokenStream as TokenStream2;
-use syn::{Error, FieldsNamed, Ident, ItemStruct, Result};
+use syn::{ext::IdentExt, Error, FieldsNamed, Ident, ItemStruct, Result};
 
-pub fn expand(mut item: ItemStruct) -> Result<TokenStream2> {
+pub fn expand(mut item: ItemStruct, args: ComponentArgs) -> Result<TokenStream2> {
     let mut acc: Option<Error> = None;
 
     let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
@@ -11,6 +11,7 @@ pub fn expand(mut item: ItemStruct) -> Result<TokenStream2> {
 
     let mut item_fields = Vec::new();
     let mut item_tys = Vec::new();
+    let mut mixed = false;
 
     match &item.fields {
         syn::Fields::Named(fields) => {
@@ -22,6 +23,7 @@ pub fn expand(mut item: ItemStruct) -> Result<TokenStream2> {
 
                 item_fields.push(field_ident.clone());
                 item_tys.push(field.ty.clone());
+                mixed |= field.attrs.iter().any(|a| a.path().is_ident("shared"));
             }
         }
         _ => {
@@ -35,6 +37,25 @@ pub fn expand(mut item: ItemStruct) -> Result<TokenStream2> {
         }
     }
 
+    if args.optimistic && args.shared {
+        combine_err(
+            &mut acc,
+            Error::new_spanned(item_ident, "optimistic models cannot be shared"),
+        );
+    }
+    if args.optimistic && mixed {
+        combine_err(
+            &mut acc,
+            Error::new_spanned(item_ident, "optimistic models cannot have shared fields"),
+        );
+    }
+    if args.optimistic && !item.generics.params.is_empty() {
+        combine_err(
+            &mut acc,
+            Error::new_spanned(&item.generics, "optimistic models cannot be generic"),
+        );
+    }
+
     if let Some(err) = acc {
         return Err(err);
     }
@@ -47,11 +68,45 @@ pub fn expand(mut item: ItemStruct) -> Result<TokenStream2> {
         item
     };
 
+    let component_args = [
+        args.classic.then(|| quote::quote!(classic)),
+        args.shared.then(|| quote::quote!(shared)),
+        args.reset.then(|| quote::quote!(reset)),
+    ];
+    let component_args = component_args.iter().flatten();
     let component_tokens = quote::quote! {
-        #[::xdevs::to_component]
+        #[::xdevs::to_component(#(#component_args),*)]
         #raw_components
     };
 
+    // Shared components use the bags of the coupled model, so coordinators do not need their own
+    let buffers = if args.shared {
+        quote::quote!(::xdevs::simulation::coordinator::SharedBuffers)
+    } else {
+        let buffers = match mixed {
+            true => quote::quote!(MixedBuffers),
+            false => quote::quote!(OwnedBuffers),
+        };
+        quote::quote! {
+            ::xdevs::simulation::coordinator::#buffers<
+                <#components_ident #ty_generics as ::xdevs::Component>::Input,
+                <#components_ident #ty_generics as ::xdevs::Component>::Output,
+            >
+        }
+    };
+
+    // Couplings of shared models are implicit, so user-written couplings would be silently ignored.
+    // The macro implements `Coupled` itself, so that any other implementation is rejected.
+    let coupled_impl = args.shared.then(|| {
+        quote::quote! {
+            impl #impl_generics ::xdevs::Coupled for #item_ident #ty_generics #where_clause {}
+        }
+    });
+
+    let processes = args
+        .optimistic
+        .then(|| processes_impl(&item, &item_fields, &item_tys));
+
     // Construct the initialization fields.
     let mut init_fields = Vec::new();
     for (ident, ty) in item_fields.iter().zip(item_tys.iter()) {
@@ -88,6 +143,7 @@ pub fn expand(mut item: ItemStruct) -> Result<TokenStream2> {
 
         impl #impl_generics ::xdevs::component::coupled::PartialCoupled for #item_ident #ty_generics #where_clause {
             type Components = #components_ident #ty_generics;
+            type Buffers = #buffers;
 
             fn get_components(&self) -> &::xdevs::component::coupled::Components<Self> {
                 &self.components
@@ -97,6 +153,88 @@ pub fn expand(mut item: ItemStruct) -> Result<TokenStream2> {
                 &mut self.components
             }
         }
+
+        #coupled_impl
+
+        #processes
     };
     Ok(expanded.into())
 }
+
+/// Generates a builder that splits the coupled model into one logical process per component,
+/// and a struct that holds the resulting processes.
+///
+/// There is an output link for every pair of components (including self-loops). Each link runs
+/// the internal couplings of the model with the event of its source, and sends the resulting
+/// events of its destination. The model must not have external couplings, so the method requires
+/// its input and output to be `()`.
+fn processes_impl(item: &ItemStruct, fields: &[Ident], tys: &[syn::Type]) -> TokenStream2 {
+    let vis = &item.vis;
+    let item_ident = &item.ident;
+    let processes_ident = Ident::new(&format!("{}Processes", item_ident), item_ident.span());
+    let optimistic = quote::quote!(::xdevs::simulation::optimistic);
+    let inboxes: Vec<_> = fields
+        .iter()
+        .map(|field| quote::format_ident!("__{}_inbox", field.unraw()))
+        .collect();
+
+    let links = fields.iter().map(|from| {
+        let links = fields.iter().zip(&inboxes).map(|(to, inbox)| {
+            quote::quote! {
+                let #from = {
+                    let (mut from, mut to) = __bags.next().unwrap();
+                    #from.with_coupling(#inbox.clone(), move |event, send| {
+                        if ::xdevs::port::BagMux::inject_event(&mut from.#from, event.clone()).is_err() {
+                            panic!("output port of logical process is full");
+                        }
+                        <#item_ident as ::xdevs::Coupled>::ic(&from, &mut to);
+                        ::xdevs::port::BagMux::eject_events(&to.#to, |event| send(event));
+                        ::xdevs::port::Bag::clear(&mut from);
+                        ::xdevs::port::Bag::clear(&mut to);
+                    })
+                };
+            }
+        });
+        quote::quote!(#(#links)*)
+    });
+    let n_links = fields.len() * fields.len();
+
+    quote::quote! {
+        /// Logical processes of the components of the coupled model, for optimistic simulation.
+        #vis struct #processes_ident {
+            #(pub #fields: #optimistic::LogicalProcess<'static, <#tys as ::xdevs::simulation::SimpleSimulable>::Simulator>,)*
+        }
+
+        impl #processes_ident {
+            /// Executes an optimistic simulation with a logical process per component.
+            /// See [`simulate`](::xdevs::simulation::optimistic::simulate).
+            pub fn simulate(&mut self, config: &::xdevs::Config, gvt_interval: usize) {
+                #optimistic::simulate(config, gvt_interval, &mut [#(&mut self.#fields),*]);
+            }
+        }
+
+        impl #item_ident {
+            /// Splits the model into a logical process per component.
+            /// Internal couplings become output links between the processes.
+            ///
+            /// Couplings are run once per output event, so couplings that depend on several
+            /// events at once (e.g., that count or merge them) behave differently than in a
+            /// sequential simulation.
+            pub fn into_processes(self) -> #processes_ident
+            where
+                Self: ::xdevs::Component<Input = (), Output = ()>,
+            {
+                let __components = self.components;
+                // Bags for the couplings are built before moving the components out
+                let mut __bags = ::core::array::from_fn::<_, #n_links, _>(|_| (
+                    ::xdevs::simulation::AbstractSimulator::build_output(&__components),
+                    ::xdevs::simulation::AbstractSimulator::build_input(&__components),
+                )).into_iter();
+                #(let #fields = #optimistic::LogicalProcess::new(__components.#fields);)*
+                #(let #inboxes = #fields.inbox();)*
+                #(#links)*
+                #processes_ident { #(#fields),* }
+            }
+        }
+    }
+}
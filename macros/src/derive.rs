@@ -352,3 +352,142 @@ fn expand_output_for(info: &Field, variant: &Ident) -> Result<TokenStream2> {
         }
     })
 }
+
+pub fn derive_wiremux(input: DeriveInput) -> Result<TokenStream2> {
+    let ident = input.ident;
+    let snake_case_ident = Ident::new(&ident.to_string().to_snake_case(), ident.span());
+    let private_mod_ident = Ident::new(
+        &format!("_xdevs_no_std_{}_bagmux", snake_case_ident),
+        ident.span(),
+    );
+    let generics = input.generics;
+
+    let fields = match input.data {
+        Data::Struct(data) => match data.fields {
+            Fields::Named(fields) => fields,
+            _ => {
+                return Err(Error::new_spanned(
+                    ident,
+                    "WireMux can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                ident,
+                "WireMux can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut acc: Option<Error> = None;
+    let mut names = Vec::new();
+    let mut variants = Vec::new();
+    let mut tys = Vec::new();
+    let mut tags: Vec<u16> = Vec::new();
+
+    for field in &fields.named {
+        let field_ident = field.ident.as_ref().expect("named field must have ident");
+        let name = field_ident.to_string();
+        let tag = match wire_tag_attr(field) {
+            Ok(Some(tag)) => tag,
+            Ok(None) => port_tag(&name),
+            Err(err) => {
+                combine_err(&mut acc, err);
+                continue;
+            }
+        };
+        if let Some(pos) = tags.iter().position(|t| *t == tag) {
+            let msg = format!(
+                "port tag {tag} of `{name}` collides with `{}`; use #[wire(tag = N)] to pin a different tag",
+                names[pos]
+            );
+            combine_err(&mut acc, Error::new_spanned(field_ident, msg));
+            continue;
+        }
+        names.push(name);
+        variants.push(to_pascal_case_ident(field_ident));
+        tys.push(field.ty.clone());
+        tags.push(tag);
+    }
+
+    if let Some(err) = acc {
+        return Err(err);
+    }
+
+    let mut where_clause = generics.clone().make_where_clause().clone();
+    for ty in &tys {
+        where_clause.predicates.push(syn::parse_quote! {
+            <#ty as ::xdevs::port::AsPort>::Item: ::xdevs::wire::WireCodec
+        });
+    }
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    let mux = quote::quote! { #private_mod_ident::PortMux #ty_generics };
+
+    Ok(quote::quote! {
+        impl #impl_generics ::xdevs::wire::WireCodec for #mux #where_clause {
+            fn encode(&self, buf: &mut [u8]) -> Result<usize, ::xdevs::wire::WireError> {
+                let tag = ::xdevs::wire::WireMux::port_tag(self);
+                let n = ::xdevs::wire::WireCodec::encode(&tag, buf)?;
+                let m = match self {
+                    #(Self::#variants(value) => ::xdevs::wire::WireCodec::encode(value, &mut buf[n..])?),*
+                };
+                Ok(n + m)
+            }
+
+            fn decode(buf: &[u8]) -> Result<(Self, usize), ::xdevs::wire::WireError> {
+                let (tag, n) = <u16 as ::xdevs::wire::WireCodec>::decode(buf)?;
+                match tag {
+                    #(#tags => {
+                        let (value, m) = ::xdevs::wire::WireCodec::decode(&buf[n..])?;
+                        Ok((Self::#variants(value), n + m))
+                    })*
+                    _ => Err(::xdevs::wire::WireError::UnknownTag(tag)),
+                }
+            }
+        }
+
+        unsafe impl #impl_generics ::xdevs::wire::WireMux for #mux #where_clause {
+            const PORT_TAGS: &'static [(&'static str, u16)] = &[#((#names, #tags)),*];
+
+            #[inline]
+            fn port_tag(&self) -> u16 {
+                match self {
+                    #(Self::#variants(_) => #tags),*
+                }
+            }
+        }
+    })
+}
+
+/// Parses the optional `#[wire(tag = N)]` attribute of a port.
+fn wire_tag_attr(field: &Field) -> Result<Option<u16>> {
+    let mut tag = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("wire"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                let lit: syn::LitInt = meta.value()?.parse()?;
+                tag = Some(lit.base10_parse::<u16>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unknown wire attribute; expected `tag = N`"))
+            }
+        })?;
+    }
+    Ok(tag)
+}
+
+/// Stable port tag: 16-bit folded FNV-1a hash of the port name.
+/// It must match `xdevs::wire::port_tag`.
+fn port_tag(name: &str) -> u16 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in name.bytes() {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    ((hash >> 16) ^ (hash & 0xffff)) as u16
+}
@@ -9,6 +9,7 @@ use syn::{
 pub struct GenerateArgs {
     width: syn::LitInt,
     depth: syn::LitInt,
+    delays: Option<(syn::Expr, syn::Expr)>,
 }
 
 impl Parse for GenerateArgs {
@@ -17,11 +18,43 @@ impl Parse for GenerateArgs {
         input.parse::<Token![,]>()?;
         let depth: syn::LitInt = input.parse()?;
 
+        let delays = if input.is_empty() {
+            None
+        } else {
+            input.parse::<Token![,]>()?;
+            let int_delay: syn::Expr = input.parse()?;
+            input.parse::<Token![,]>()?;
+            let ext_delay: syn::Expr = input.parse()?;
+            Some((int_delay, ext_delay))
+        };
+
         if !input.is_empty() {
-            return Err(input.error("expected only two arguments: width, depth"));
+            return Err(
+                input.error("expected width, depth, and optionally int_delay and ext_delay")
+            );
         }
 
-        Ok(Self { width, depth })
+        Ok(Self {
+            width,
+            depth,
+            delays,
+        })
+    }
+}
+
+impl GenerateArgs {
+    /// Returns the identifier of the synthetic CPU load of the atomic models and the statement
+    /// that defines it. Without explicit delays, atomic models perform no work.
+    fn delays(&self) -> (syn::Ident, proc_macro2::TokenStream) {
+        let ident = syn::Ident::new("delays", proc_macro2::Span::mixed_site());
+        let value = match &self.delays {
+            Some((int_delay, ext_delay)) => {
+                quote! { ::xdevs::devstone::common::Delays::new(#int_delay, #ext_delay) }
+            }
+            None => quote! { ::xdevs::devstone::common::Delays::ZERO },
+        };
+        let stmt = quote! { let #ident: ::xdevs::devstone::common::Delays = #value; };
+        (ident, stmt)
     }
 }
 
@@ -44,17 +77,18 @@ pub(crate) fn expand_li_box(args: GenerateArgs) -> Result<proc_macro2::TokenStre
 
     let width_minus_one = syn::LitInt::new(&(width_val - 1).to_string(), args.width.span());
 
-    let mut token = proc_macro2::TokenStream::new();
+    let (delays, delays_stmt) = args.delays();
+    let mut token = delays_stmt;
 
     for val in 1..(depth_val + 1) {
         if val == 1 {
             if val != depth_val {
                 token.extend(quote! {
-                    let model_1 = ::alloc::boxed::Box::new(::xdevs::devstone::li_box::LIEnum::Leaf(::xdevs::devstone::common::LeafModel::new().to_simulator()));
+                    let model_1 = ::alloc::boxed::Box::new(::xdevs::devstone::li_box::LIEnum::Leaf(::xdevs::devstone::common::LeafModel::with_delays(#delays).to_simulator()));
                 })
             } else {
                 token.extend(quote! {
-                    let model_1 = ::xdevs::devstone::li_box::LIEnum::Leaf(::xdevs::devstone::common::LeafModel::new().to_simulator());
+                    let model_1 = ::xdevs::devstone::li_box::LIEnum::Leaf(::xdevs::devstone::common::LeafModel::with_delays(#delays).to_simulator());
                 });
             }
         } else {
@@ -63,11 +97,11 @@ pub(crate) fn expand_li_box(args: GenerateArgs) -> Result<proc_macro2::TokenStre
             let prev_model = format_ident!("model_{}", val_minus_one);
             if val != depth_val {
                 token.extend(quote! {
-                    let #model_name = ::alloc::boxed::Box::new(::xdevs::devstone::li_box::LIEnum::Branch(::xdevs::devstone::li_box::LIModel::<#width_minus_one>::new(#prev_model).to_simulator()));
+                    let #model_name = ::alloc::boxed::Box::new(::xdevs::devstone::li_box::LIEnum::Branch(::xdevs::devstone::li_box::LIModel::<#width_minus_one>::with_delays(#prev_model, #delays).to_simulator()));
                 });
             } else {
                 token.extend(quote! {
-                    let #model_name = ::xdevs::devstone::li_box::LIEnum::Branch(::xdevs::devstone::li_box::LIModel::<#width_minus_one>::new(#prev_model).to_simulator());
+                    let #model_name = ::xdevs::devstone::li_box::LIEnum::Branch(::xdevs::devstone::li_box::LIModel::<#width_minus_one>::with_delays(#prev_model, #delays).to_simulator());
                 });
             }
         }
@@ -97,17 +131,18 @@ pub(crate) fn expand_hi_box(args: GenerateArgs) -> Result<proc_macro2::TokenStre
 
     let width_minus_one = syn::LitInt::new(&(width_val - 1).to_string(), args.width.span());
 
-    let mut token = proc_macro2::TokenStream::new();
+    let (delays, delays_stmt) = args.delays();
+    let mut token = delays_stmt;
 
     for val in 1..(depth_val + 1) {
         if val == 1 {
             if val != depth_val {
                 token.extend(quote! {
-                    let model_1 = ::alloc::boxed::Box::new(::xdevs::devstone::hi_box::HIEnum::Leaf(::xdevs::devstone::common::LeafModel::new().to_simulator()));
+                    let model_1 = ::alloc::boxed::Box::new(::xdevs::devstone::hi_box::HIEnum::Leaf(::xdevs::devstone::common::LeafModel::with_delays(#delays).to_simulator()));
                 })
             } else {
                 token.extend(quote! {
-                    let model_1 = ::xdevs::devstone::hi_box::HIEnum::Leaf(::xdevs::devstone::common::LeafModel::new().to_simulator());
+                    let model_1 = ::xdevs::devstone::hi_box::HIEnum::Leaf(::xdevs::devstone::common::LeafModel::with_delays(#delays).to_simulator());
                 });
             }
         } else {
@@ -116,11 +151,11 @@ pub(crate) fn expand_hi_box(args: GenerateArgs) -> Result<proc_macro2::TokenStre
             let prev_model = format_ident!("model_{}", val_minus_one);
             if val != depth_val {
                 token.extend(quote! {
-                    let #model_name = ::alloc::boxed::Box::new(::xdevs::devstone::hi_box::HIEnum::Branch(::xdevs::devstone::hi_box::HIModel::<#width_minus_one>::new(#prev_model).to_simulator()));
+                    let #model_name = ::alloc::boxed::Box::new(::xdevs::devstone::hi_box::HIEnum::Branch(::xdevs::devstone::hi_box::HIModel::<#width_minus_one>::with_delays(#prev_model, #delays).to_simulator()));
                 });
             } else {
                 token.extend(quote! {
-                    let #model_name = ::xdevs::devstone::hi_box::HIEnum::Branch(::xdevs::devstone::hi_box::HIModel::<#width_minus_one>::new(#prev_model).to_simulator());
+                    let #model_name = ::xdevs::devstone::hi_box::HIEnum::Branch(::xdevs::devstone::hi_box::HIModel::<#width_minus_one>::with_delays(#prev_model, #delays).to_simulator());
                 });
             }
         }
@@ -131,7 +166,8 @@ pub(crate) fn expand_hi_box(args: GenerateArgs) -> Result<proc_macro2::TokenStre
     Ok(token)
 }
 
-pub(crate) fn expand_ho_box(args: GenerateArgs) -> Result<proc_macro2::TokenStream> {
+pub(crate) fn expand_ho_box(args: GenerateArgs, module: &str) -> Result<proc_macro2::TokenStream> {
+    let module = syn::Ident::new(module, proc_macro2::Span::call_site());
     let width_val: usize = args.width.base10_parse()?;
     let depth_val: usize = args.depth.base10_parse()?;
 
@@ -150,17 +186,18 @@ pub(crate) fn expand_ho_box(args: GenerateArgs) -> Result<proc_macro2::TokenStre
 
     let width_minus_one = syn::LitInt::new(&(width_val - 1).to_string(), args.width.span());
 
-    let mut token = proc_macro2::TokenStream::new();
+    let (delays, delays_stmt) = args.delays();
+    let mut token = delays_stmt;
 
     for val in 1..(depth_val + 1) {
         if val == 1 {
             if val != depth_val {
                 token.extend(quote! {
-                    let model_1 = ::alloc::boxed::Box::new(::xdevs::devstone::ho_box::HOEnum::Leaf(::xdevs::devstone::ho_box::LeafModel::<#width_minus_one>::new().to_simulator()));
+                    let model_1 = ::alloc::boxed::Box::new(::xdevs::devstone::#module::HOEnum::Leaf(::xdevs::devstone::#module::LeafModel::<#width_minus_one>::with_delays(#delays).to_simulator()));
                 })
             } else {
                 token.extend(quote! {
-                    let model_1 = ::xdevs::devstone::ho_box::HOEnum::Leaf(::xdevs::devstone::ho_box::LeafModel::<#width_minus_one>::new().to_simulator());
+                    let model_1 = ::xdevs::devstone::#module::HOEnum::Leaf(::xdevs::devstone::#module::LeafModel::<#width_minus_one>::with_delays(#delays).to_simulator());
                 });
             }
         } else {
@@ -169,11 +206,11 @@ pub(crate) fn expand_ho_box(args: GenerateArgs) -> Result<proc_macro2::TokenStre
             let prev_model = format_ident!("model_{}", val_minus_one);
             if val != depth_val {
                 token.extend(quote! {
-                    let #model_name = ::alloc::boxed::Box::new(::xdevs::devstone::ho_box::HOEnum::Branch(::xdevs::devstone::ho_box::HOModel::<#width_minus_one>::new(#prev_model).to_simulator()));
+                    let #model_name = ::alloc::boxed::Box::new(::xdevs::devstone::#module::HOEnum::Branch(::xdevs::devstone::#module::HOModel::<#width_minus_one>::with_delays(#prev_model, #delays).to_simulator()));
                 });
             } else {
                 token.extend(quote! {
-                    let #model_name = ::xdevs::devstone::ho_box::HOEnum::Branch(::xdevs::devstone::ho_box::HOModel::<#width_minus_one>::new(#prev_model).to_simulator());
+                    let #model_name = ::xdevs::devstone::#module::HOEnum::Branch(::xdevs::devstone::#module::HOModel::<#width_minus_one>::with_delays(#prev_model, #delays).to_simulator());
                 });
             }
         }
@@ -184,7 +221,13 @@ pub(crate) fn expand_ho_box(args: GenerateArgs) -> Result<proc_macro2::TokenStre
     Ok(token)
 }
 
-pub(crate) fn expand_li(args: GenerateArgs) -> Result<proc_macro2::TokenStream> {
+pub(crate) fn expand_li(
+    args: GenerateArgs,
+    module: &str,
+    leaf: &str,
+) -> Result<proc_macro2::TokenStream> {
+    let module = syn::Ident::new(module, proc_macro2::Span::call_site());
+    let leaf = syn::Ident::new(leaf, proc_macro2::Span::call_site());
     let width_val: usize = args.width.base10_parse()?;
     let depth_val: usize = args.depth.base10_parse()?;
 
@@ -203,19 +246,20 @@ pub(crate) fn expand_li(args: GenerateArgs) -> Result<proc_macro2::TokenStream>
 
     let width_minus_one = syn::LitInt::new(&(width_val - 1).to_string(), args.width.span());
 
-    let mut token = proc_macro2::TokenStream::new();
+    let (delays, delays_stmt) = args.delays();
+    let mut token = delays_stmt;
 
     for val in 1..(depth_val + 1) {
         if val == 1 {
             token.extend(quote! {
-                let mut model_1 = ::xdevs::devstone::li::LIEnum::Leaf(::xdevs::devstone::common::LeafModel::new().to_simulator());
+                let mut model_1 = ::xdevs::devstone::#module::LIEnum::Leaf(::xdevs::devstone::common::#leaf::with_delays(#delays).to_simulator());
             });
         } else {
             let val_minus_one = val - 1;
             let model_name = format_ident!("model_{}", val);
             let prev_model = format_ident!("model_{}", val_minus_one);
             token.extend(quote! {
-                let mut #model_name = ::xdevs::devstone::li::LIEnum::Branch(::xdevs::devstone::li::LIModel::<#width_minus_one>::new(&mut #prev_model).to_simulator());
+                let mut #model_name = ::xdevs::devstone::#module::LIEnum::Branch(::xdevs::devstone::#module::LIModel::<#width_minus_one>::with_delays(&mut #prev_model, #delays).to_simulator());
             });
         }
     }
@@ -225,7 +269,13 @@ pub(crate) fn expand_li(args: GenerateArgs) -> Result<proc_macro2::TokenStream>
     Ok(token)
 }
 
-pub(crate) fn expand_hi(args: GenerateArgs) -> Result<proc_macro2::TokenStream> {
+pub(crate) fn expand_hi(
+    args: GenerateArgs,
+    module: &str,
+    leaf: &str,
+) -> Result<proc_macro2::TokenStream> {
+    let module = syn::Ident::new(module, proc_macro2::Span::call_site());
+    let leaf = syn::Ident::new(leaf, proc_macro2::Span::call_site());
     let width_val: usize = args.width.base10_parse()?;
     let depth_val: usize = args.depth.base10_parse()?;
 
@@ -244,19 +294,20 @@ pub(crate) fn expand_hi(args: GenerateArgs) -> Result<proc_macro2::TokenStream>
 
     let width_minus_one = syn::LitInt::new(&(width_val - 1).to_string(), args.width.span());
 
-    let mut token = proc_macro2::TokenStream::new();
+    let (delays, delays_stmt) = args.delays();
+    let mut token = delays_stmt;
 
     for val in 1..(depth_val + 1) {
         if val == 1 {
             token.extend(quote! {
-                let mut model_1 = ::xdevs::devstone::hi::HIEnum::Leaf(::xdevs::devstone::common::LeafModel::new().to_simulator());
+                let mut model_1 = ::xdevs::devstone::#module::HIEnum::Leaf(::xdevs::devstone::common::#leaf::with_delays(#delays).to_simulator());
             });
         } else {
             let val_minus_one = val - 1;
             let model_name = format_ident!("model_{}", val);
             let prev_model = format_ident!("model_{}", val_minus_one);
             token.extend(quote! {
-                let mut #model_name = ::xdevs::devstone::hi::HIEnum::Branch(::xdevs::devstone::hi::HIModel::<#width_minus_one>::new(&mut #prev_model).to_simulator());
+                let mut #model_name = ::xdevs::devstone::#module::HIEnum::Branch(::xdevs::devstone::#module::HIModel::<#width_minus_one>::with_delays(&mut #prev_model, #delays).to_simulator());
             });
         }
     }
@@ -266,7 +317,8 @@ pub(crate) fn expand_hi(args: GenerateArgs) -> Result<proc_macro2::TokenStream>
     Ok(token)
 }
 
-pub(crate) fn expand_ho(args: GenerateArgs) -> Result<proc_macro2::TokenStream> {
+pub(crate) fn expand_ho(args: GenerateArgs, module: &str) -> Result<proc_macro2::TokenStream> {
+    let module = syn::Ident::new(module, proc_macro2::Span::call_site());
     let width_val: usize = args.width.base10_parse()?;
     let depth_val: usize = args.depth.base10_parse()?;
 
@@ -285,19 +337,20 @@ pub(crate) fn expand_ho(args: GenerateArgs) -> Result<proc_macro2::TokenStream>
 
     let width_minus_one = syn::LitInt::new(&(width_val - 1).to_string(), args.width.span());
 
-    let mut token = proc_macro2::TokenStream::new();
+    let (delays, delays_stmt) = args.delays();
+    let mut token = delays_stmt;
 
     for val in 1..(depth_val + 1) {
         if val == 1 {
             token.extend(quote! {
-                let mut model_1 = ::xdevs::devstone::ho::HOEnum::Leaf(::xdevs::devstone::ho::LeafModel::<#width_minus_one>::new().to_simulator());
+                let mut model_1 = ::xdevs::devstone::#module::HOEnum::Leaf(::xdevs::devstone::#module::LeafModel::<#width_minus_one>::with_delays(#delays).to_simulator());
             });
         } else {
             let val_minus_one = val - 1;
             let model_name = format_ident!("model_{}", val);
             let prev_model = format_ident!("model_{}", val_minus_one);
             token.extend(quote! {
-                let mut #model_name = ::xdevs::devstone::ho::HOEnum::Branch(::xdevs::devstone::ho::HOModel::<#width_minus_one>::new(&mut #prev_model).to_simulator());
+                let mut #model_name = ::xdevs::devstone::#module::HOEnum::Branch(::xdevs::devstone::#module::HOModel::<#width_minus_one>::with_delays(&mut #prev_model, #delays).to_simulator());
             });
         }
     }
@@ -306,3 +359,109 @@ pub(crate) fn expand_ho(args: GenerateArgs) -> Result<proc_macro2::TokenStream>
     token.extend(quote! {let mut model_ho = #model_name;});
     Ok(token)
 }
+
+/// Input capacity of the atomic models of a HOmod model with the given width.
+/// Atomics in the first row receive one event from the second input and one from each atomic of
+/// the first row of the triangle, while the leaf atomic receives events from both inputs.
+fn homod_capacity(width: &syn::LitInt, width_val: usize) -> syn::LitInt {
+    let capacity = (2 * (width_val - 1)).max(2);
+    syn::LitInt::new(&capacity.to_string(), width.span())
+}
+
+pub(crate) fn expand_homod(args: GenerateArgs) -> Result<proc_macro2::TokenStream> {
+    let width_val: usize = args.width.base10_parse()?;
+    let depth_val: usize = args.depth.base10_parse()?;
+
+    if width_val < 1 {
+        return Err(syn::Error::new(
+            args.width.span(),
+            "width must be at least 1",
+        ));
+    }
+    if depth_val < 1 {
+        return Err(syn::Error::new(
+            args.depth.span(),
+            "depth must be at least 1",
+        ));
+    }
+
+    let width_minus_one = syn::LitInt::new(&(width_val - 1).to_string(), args.width.span());
+    let capacity = homod_capacity(&args.width, width_val);
+
+    let (delays, delays_stmt) = args.delays();
+    let mut token = delays_stmt;
+
+    for val in 1..(depth_val + 1) {
+        if val == 1 {
+            token.extend(quote! {
+                let mut model_1 = ::xdevs::devstone::homod::HOmodEnum::Leaf(::xdevs::devstone::homod::LeafModel::<#width_minus_one, #capacity>::with_delays(#delays).to_simulator());
+            });
+        } else {
+            let val_minus_one = val - 1;
+            let model_name = format_ident!("model_{}", val);
+            let prev_model = format_ident!("model_{}", val_minus_one);
+            token.extend(quote! {
+                let mut #model_name = ::xdevs::devstone::homod::HOmodEnum::Branch(::xdevs::devstone::homod::HOmodModel::<#width_minus_one, #capacity>::with_delays(&mut #prev_model, #delays).to_simulator());
+            });
+        }
+    }
+
+    let model_name = format_ident!("model_{}", depth_val);
+    token.extend(quote! {let mut model_homod = #model_name;});
+    Ok(token)
+}
+
+pub(crate) fn expand_homod_box(args: GenerateArgs) -> Result<proc_macro2::TokenStream> {
+    let width_val: usize = args.width.base10_parse()?;
+    let depth_val: usize = args.depth.base10_parse()?;
+
+    if width_val < 1 {
+        return Err(syn::Error::new(
+            args.width.span(),
+            "width must be at least 1",
+        ));
+    }
+    if depth_val < 1 {
+        return Err(syn::Error::new(
+            args.depth.span(),
+            "depth must be at least 1",
+        ));
+    }
+
+    let width_minus_one = syn::LitInt::new(&(width_val - 1).to_string(), args.width.span());
+    let capacity = homod_capacity(&args.width, width_val);
+
+    let (delays, delays_stmt) = args.delays();
+    let mut token = delays_stmt;
+
+    for val in 1..(depth_val + 1) {
+        if val == 1 {
+            if val != depth_val {
+                token.extend(quote! {
+                    let model_1 = ::alloc::boxed::Box::new(::xdevs::devstone::homod_box::HOmodEnum::Leaf(::xdevs::devstone::homod_box::LeafModel::<#width_minus_one, #capacity>::with_delays(#delays).to_simulator()));
+                })
+            } else {
+                token.extend(quote! {
+                    let model_1 = ::xdevs::devstone::homod_box::HOmodEnum::Leaf(::xdevs::devstone::homod_box::LeafModel::<#width_minus_one, #capacity>::with_delays(#delays).to_simulator());
+                });
+            }
+        } else {
+            let val_minus_one = val - 1;
+            let model_name = format_ident!("model_{}", val);
+            let prev_model = format_ident!("model_{}", val_minus_one);
+            if val != depth_val {
+                token.extend(quote! {
+                    let #model_name = ::alloc::boxed::Box::new(::xdevs::devstone::homod_box::HOmodEnum::Branch(::xdevs::devstone::homod_box::HOmodModel::<#width_minus_one, #capacity>::with_delays(#prev_model, #delays).to_simulator()));
+                });
+            } else {
+                token.extend(quote! {
+                    let #model_name = ::xdevs::devstone::homod_box::HOmodEnum::Branch(::xdevs::devstone::homod_box::HOmodModel::<#width_minus_one, #capacity>::with_delays(#prev_model, #delays).to_simulator());
+                });
+            }
+        }
+    }
+
+    let model_name = format_ident!("model_{}", depth_val);
+    token.extend(quote! {let model_homod = #model_name ;});
+    Ok(token)
+}
@@ -5,39 +5,64 @@ mod coupled;
 mod derive;
 mod devstone;
 mod rt_engine;
+mod state_machine;
 mod to_component;
 
 /// Macro to generate coupled DEVS components.
+///
+/// Components are visited in decreasing `#[priority = n]` order (0 by default, ties keep the
+/// declaration order). With `#[coupled(classic)]`, only the first imminent component is executed
+/// in each simulation step (Classic DEVS `select` function). Other imminent components that
+/// receive its output only execute their external transition, and their next internal transition
+/// is scheduled from the time of the event, so their due internal transition is discarded.
+/// Selection works per field: a field with several components (e.g., an array or a `Vec`) is
+/// executed as a whole, so all its imminent components are executed in the same step.
+///
+/// With `#[coupled(shared)]`, the struct must have exactly one field, whose component has the same
+/// input and output as the coupled model. The component uses the bags of the coupled model,
+/// and coordinators do not keep bags for it. Couplings are implicit: the macro implements `Coupled`
+/// itself, so models cannot define coupling functions (or error strategies).
+///
+/// In coupled models with several components, components that receive the whole input of the
+/// model (and nothing else) can be marked with `#[shared(input)]`, and components whose whole
+/// output is the output of the model (and is not coupled to other components) can be marked with
+/// `#[shared(output)]`. `#[shared]` marks both. Shared components read the input of the coupled
+/// model in place and write directly into its output, so coordinators do not keep bags for them.
+/// These couplings are implicit: the coupling functions only deal with the other components.
+/// Only one component can share the input.
+///
+/// With `#[coupled(reset)]`, the coupled model implements `Reset` by resetting all its components,
+/// which must implement `Reset` too.
+///
+/// With `#[coupled(optimistic)]` (requires `std`), the macro also generates a `<Name>Processes`
+/// struct and an `into_processes` method that splits the model into one optimistic logical process
+/// per component. Internal couplings become output links between the processes. The model cannot
+/// be generic nor have shared components, and its input and output must be `()` (i.e., no external
+/// couplings).
+/// Couplings are run once per output event, so they must not depend on several events at once.
 #[proc_macro_attribute]
 pub fn coupled(args: TokenStream, item: TokenStream) -> TokenStream {
-    let args = proc_macro2::TokenStream::from(args);
-    if !args.is_empty() {
-        return syn::Error::new_spanned(args, "#[coupled] does not accept arguments")
-            .to_compile_error()
-            .into();
-    }
+    let args = parse_macro_input!(args as to_component::ComponentArgs);
     let item = parse_macro_input!(item as syn::ItemStruct);
 
-    match coupled::expand(item) {
+    match coupled::expand(item, args) {
         Ok(component) => component.into(),
         Err(err) => err.to_compile_error().into(),
     }
 }
 
 /// Macro to generate DEVS components.
+///
+/// Accepts the same `#[priority = n]` and `#[shared]` field attributes and `classic`, `shared` and `reset` arguments
+/// as [`macro@coupled`]. Enums only accept `reset`, and `optimistic` is only accepted by [`macro@coupled`].
 #[proc_macro_attribute]
 pub fn to_component(args: TokenStream, item: TokenStream) -> TokenStream {
-    let args = proc_macro2::TokenStream::from(args);
-    if !args.is_empty() {
-        return syn::Error::new_spanned(args, "#[to_component] does not accept arguments")
-            .to_compile_error()
-            .into();
-    }
+    let args = parse_macro_input!(args as to_component::ComponentArgs);
     let item2 = item.clone();
 
     // Try parsing as a struct first (coupled model)
     if let Ok(item_struct) = syn::parse::<syn::ItemStruct>(item2) {
-        return match to_component::expand_struct(item_struct) {
+        return match to_component::expand_struct(item_struct, args) {
             Ok(component) => component.into(),
             Err(err) => err.to_compile_error().into(),
         };
@@ -46,7 +71,7 @@ pub fn to_component(args: TokenStream, item: TokenStream) -> TokenStream {
     // Then try parsing as an enum (enum-based model)
     let item2 = item.clone();
     if let Ok(item_enum) = syn::parse::<syn::ItemEnum>(item2) {
-        return match to_component::expand_enum(item_enum) {
+        return match to_component::expand_enum(item_enum, args) {
             Ok(component) => component.into(),
             Err(err) => err.to_compile_error().into(),
         };
@@ -59,7 +84,71 @@ pub fn to_component(args: TokenStream, item: TokenStream) -> TokenStream {
     err.to_compile_error().into()
 }
 
-/// Macro to generate RT engine components
+/// Macro to generate phase/sigma atomic models.
+///
+/// It generates the state struct (with a `phase` and a `sigma`), a `<Name>Phase` enum, a `new`
+/// constructor (taking the state variables without initial value), and the `Component` and
+/// `Atomic` implementations.
+///
+/// - `phases` declare the time advance of each phase. Sigma is reset to it when entering the phase.
+/// - `internal` rules declare, for the current phase, the `output` block (with `output` in scope),
+///   the `update` block, and the next phase. Phases without a rule are re-entered.
+/// - `external` rules declare, per input port, the body to run when the port has events in the
+///   given phases. The values of the port can be bound with `port(values)`, and `input` refers to
+///   the whole input when it is a single port. The body returns `Some(Phase)` to enter a phase, or
+///   `None` to keep the current phase with the remaining sigma. For each port, only the first rule
+///   matching the phase at the beginning of the transition runs.
+///
+/// Confluent transitions run the internal transition first, and then the external rules with
+/// `elapsed = 0`.
+///
+/// # Example
+///
+/// ```ignore
+/// xdevs::state_machine! {
+///     pub struct Processor {
+///         time: f64,
+///         job: Option<usize> = None,
+///     }
+///
+///     input: xdevs::Port<usize, 1>;
+///     output: xdevs::Port<usize, 1>;
+///     initial: Idle;
+///
+///     phases {
+///         Idle => f64::INFINITY,
+///         Busy => self.time,
+///     }
+///
+///     internal {
+///         Busy -> Idle {
+///             output { output.add_value(self.job.unwrap()).unwrap(); }
+///             update { self.job = None; }
+///         }
+///     }
+///
+///     external {
+///         Idle on input(jobs) => {
+///             self.job = jobs.last().copied();
+///             Some(Busy)
+///         }
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn state_machine(input: TokenStream) -> TokenStream {
+    let machine = parse_macro_input!(input as state_machine::StateMachine);
+
+    match state_machine::expand(machine) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Macro to generate RT engine components.
+///
+/// The backend can be selected with the `backend = std | embassy | portable` argument.
+/// By default, the `std` or `embassy` backend is used if enabled. Otherwise, the `portable` backend is used.
 #[proc_macro_attribute]
 pub fn rt_engine(args: TokenStream, item: TokenStream) -> TokenStream {
     let args = match parse::<rt_engine::RtEngineArgs>(args) {
@@ -100,6 +189,15 @@ pub fn derive_bagmux(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(WireMux, attributes(wire))]
+pub fn derive_wiremux(input: TokenStream) -> TokenStream {
+    let input: syn::DeriveInput = syn::parse_macro_input!(input);
+    match derive::derive_wiremux(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 // Function to combine errors when parsing
 pub(crate) fn combine_err(acc: &mut Option<Error>, err: Error) {
     match acc {
@@ -109,11 +207,14 @@ pub(crate) fn combine_err(acc: &mut Option<Error>, err: Error) {
 }
 
 /// DEVStone macros — ref version (default, no alloc needed)
+///
+/// All the DEVStone macros take the width and depth of the model, and optionally the internal and
+/// external delays of the atomic models (see `xdevs::devstone::common::Delays`).
 #[proc_macro]
 pub fn generate_li(input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(input as devstone::GenerateArgs);
 
-    match devstone::expand_li(args) {
+    match devstone::expand_li(args, "li", "LeafModel") {
         Ok(tokens) => tokens.into(),
         Err(err) => err.to_compile_error().into(),
     }
@@ -123,7 +224,7 @@ pub fn generate_li(input: TokenStream) -> TokenStream {
 pub fn generate_hi(input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(input as devstone::GenerateArgs);
 
-    match devstone::expand_hi(args) {
+    match devstone::expand_hi(args, "hi", "LeafModel") {
         Ok(tokens) => tokens.into(),
         Err(err) => err.to_compile_error().into(),
     }
@@ -133,7 +234,51 @@ pub fn generate_hi(input: TokenStream) -> TokenStream {
 pub fn generate_ho(input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(input as devstone::GenerateArgs);
 
-    match devstone::expand_ho(args) {
+    match devstone::expand_ho(args, "ho") {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn generate_homod(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as devstone::GenerateArgs);
+
+    match devstone::expand_homod(args) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// DEVStone macros — shared version (default, no alloc needed)
+///
+/// They generate the same models as the ref version, but the inner levels share the bags of
+/// their parents (see `#[shared]`).
+#[proc_macro]
+pub fn generate_li_shared(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as devstone::GenerateArgs);
+
+    match devstone::expand_li(args, "li_shared", "SharedLeafModel") {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn generate_hi_shared(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as devstone::GenerateArgs);
+
+    match devstone::expand_hi(args, "hi_shared", "SharedLeafModel") {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn generate_ho_shared(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as devstone::GenerateArgs);
+
+    match devstone::expand_ho(args, "ho_shared") {
         Ok(tokens) => tokens.into(),
         Err(err) => err.to_compile_error().into(),
     }
@@ -164,7 +309,27 @@ pub fn generate_hi_box(input: TokenStream) -> TokenStream {
 pub fn generate_ho_box(input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(input as devstone::GenerateArgs);
 
-    match devstone::expand_ho_box(args) {
+    match devstone::expand_ho_box(args, "ho_box") {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn generate_ho_box_shared(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as devstone::GenerateArgs);
+
+    match devstone::expand_ho_box(args, "ho_box_shared") {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+#[proc_macro]
+pub fn generate_homod_box(input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(input as devstone::GenerateArgs);
+
+    match devstone::expand_homod_box(args) {
         Ok(tokens) => tokens.into(),
         Err(err) => err.to_compile_error().into(),
     }
@@ -1,6 +1,6 @@
 mod backend;
 
-use backend::{Backend, RtEngineBackend};
+use backend::BackendKind;
 use heck::ToSnakeCase;
 use proc_macro2::TokenStream as TokenStream2;
 use syn::{
@@ -32,6 +32,7 @@ pub struct RtEngineArgs {
     pub out_channel_size: usize,
     #[allow(dead_code)]
     pub max_out_subs: usize,
+    pub backend: BackendKind,
 }
 
 impl Parse for RtEngineArgs {
@@ -44,6 +45,26 @@ impl Parse for RtEngineArgs {
         // Parse built-in Meta items
         let parsed_args = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
 
+        // The backend must be known before checking the rest of arguments
+        let mut backend = None;
+        for meta in &parsed_args {
+            let syn::Meta::NameValue(nv) = meta else {
+                continue;
+            };
+            if !nv.path.is_ident("backend") {
+                continue;
+            }
+            match (backend, BackendKind::parse(&nv.value)) {
+                (None, Ok(kind)) => backend = Some(kind),
+                (Some(_), _) => {
+                    let err = Error::new_spanned(&nv.path, "duplicate argument: backend");
+                    combine_err(&mut acc, err);
+                }
+                (None, Err(err)) => combine_err(&mut acc, err),
+            }
+        }
+        let backend = backend.unwrap_or_default();
+
         for meta in parsed_args {
             // We only care about `path = value` (MetaNameValue)
             let nv = match meta {
@@ -55,7 +76,11 @@ impl Parse for RtEngineArgs {
                 }
             };
 
-            if let Err(err) = RtEngineBackend::check_arg_compatibility(&nv) {
+            if nv.path.is_ident("backend") {
+                continue;
+            }
+
+            if let Err(err) = backend.check_arg_compatibility(&nv) {
                 combine_err(&mut acc, err);
                 continue;
             }
@@ -123,15 +148,23 @@ impl Parse for RtEngineArgs {
             }
         }
 
-        #[cfg(not(any(feature = "embassy-backend", feature = "std-backend")))]
+        #[cfg(not(any(
+            feature = "embassy-backend",
+            feature = "std-backend",
+            feature = "portable-backend"
+        )))]
         {
             return Err(syn::Error::new(
                 input.span(),
-                "No backend feature enabled. Please enable either the `embassy` or `std` feature.",
+                "No backend feature enabled. Please enable the `embassy`, `std` or `portable` feature.",
             ));
         }
 
-        #[cfg(any(feature = "embassy-backend", feature = "std-backend"))]
+        #[cfg(any(
+            feature = "embassy-backend",
+            feature = "std-backend",
+            feature = "portable-backend"
+        ))]
         {
             if let Some(err) = acc {
                 return Err(err);
@@ -141,6 +174,7 @@ impl Parse for RtEngineArgs {
                 in_channel_size: in_channel_size.unwrap_or(1),
                 out_channel_size: out_channel_size.unwrap_or(1),
                 max_out_subs: max_out_subs.unwrap_or(1),
+                backend,
             })
         }
     }
@@ -149,7 +183,7 @@ impl Parse for RtEngineArgs {
 pub fn expand(args: RtEngineArgs, item: ItemImpl) -> Result<TokenStream2> {
     let mut acc: Option<Error> = None;
     let mut generated = TokenStream2::new();
-    match RtEngineBackend::check_item_compatibility(&item) {
+    match args.backend.check_item_compatibility(&item) {
         Ok(()) => {}
         Err(e) => {
             combine_err(&mut acc, e);
@@ -213,7 +247,7 @@ pub fn expand(args: RtEngineArgs, item: ItemImpl) -> Result<TokenStream2> {
         Input as ::xdevs::port::BagMux>::Mux;
         });
 
-        let input_channel_tokens = RtEngineBackend::input_channel(&args, &model_ident);
+        let input_channel_tokens = args.backend.input_channel(&args, &model_ident);
         (
             input_channel_type,
             input_channel_call,
@@ -258,7 +292,7 @@ pub fn expand(args: RtEngineArgs, item: ItemImpl) -> Result<TokenStream2> {
             Output as ::xdevs::port::BagMux>::Mux;
         });
 
-        let output_channel_tokens = RtEngineBackend::output_channel(&args, &model_ident);
+        let output_channel_tokens = args.backend.output_channel(&args, &model_ident);
         output_channel_type = output_channel_tokens.channel_type;
         output_channel_call = output_channel_tokens.channel_call;
         private_output_channel = output_channel_tokens.private_channel;
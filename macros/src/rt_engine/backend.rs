@@ -16,8 +16,14 @@ mod no_backend;
 #[cfg(not(any(feature = "embassy-backend", feature = "std-backend")))]
 pub use no_backend::RtEngineBackend;
 
+#[cfg(feature = "portable-backend")]
+mod portable;
+
+#[cfg(feature = "portable-backend")]
+pub use portable::PortableBackend;
+
 use super::{ChannelTokens, RtEngineArgs};
-use syn::{Ident, ItemImpl, MetaNameValue, Result};
+use syn::{Error, Expr, Ident, ItemImpl, MetaNameValue, Result};
 
 pub trait Backend {
     fn check_arg_compatibility(arg: &MetaNameValue) -> Result<()>;
@@ -25,3 +31,87 @@ pub trait Backend {
     fn input_channel(args: &RtEngineArgs, model_ident: &Ident) -> ChannelTokens;
     fn output_channel(args: &RtEngineArgs, model_ident: &Ident) -> ChannelTokens;
 }
+
+/// Backend selected for a given `rt_engine` invocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendKind {
+    /// Executor-specific backend enabled by the `std` or `embassy` features.
+    Executor,
+    /// Executor-agnostic backend enabled by the `portable` feature.
+    #[cfg(feature = "portable-backend")]
+    Portable,
+}
+
+impl Default for BackendKind {
+    /// The executor-specific backend is preferred over the portable one.
+    fn default() -> Self {
+        #[cfg(all(
+            feature = "portable-backend",
+            not(any(feature = "embassy-backend", feature = "std-backend"))
+        ))]
+        return Self::Portable;
+        #[cfg(not(all(
+            feature = "portable-backend",
+            not(any(feature = "embassy-backend", feature = "std-backend"))
+        )))]
+        return Self::Executor;
+    }
+}
+
+impl BackendKind {
+    /// Parses the value of the `backend` argument.
+    pub fn parse(value: &Expr) -> Result<Self> {
+        let name = match value {
+            Expr::Path(path) => path.path.require_ident()?.to_string(),
+            _ => return Err(Error::new_spanned(value, "expected a backend name")),
+        };
+        match name.as_str() {
+            "std" if cfg!(feature = "std-backend") => Ok(Self::Executor),
+            "embassy" if cfg!(feature = "embassy-backend") => Ok(Self::Executor),
+            #[cfg(feature = "portable-backend")]
+            "portable" => Ok(Self::Portable),
+            _ if ["std", "embassy", "portable"].contains(&name.as_str()) => {
+                Err(Error::new_spanned(
+                    value,
+                    format!("backend `{name}` is not enabled. Please enable the `{name}` feature."),
+                ))
+            }
+            _ => Err(Error::new_spanned(
+                value,
+                format!("unknown backend: {name}. Expected `std`, `embassy` or `portable`."),
+            )),
+        }
+    }
+
+    pub fn check_arg_compatibility(self, arg: &MetaNameValue) -> Result<()> {
+        match self {
+            Self::Executor => RtEngineBackend::check_arg_compatibility(arg),
+            #[cfg(feature = "portable-backend")]
+            Self::Portable => PortableBackend::check_arg_compatibility(arg),
+        }
+    }
+
+    pub fn check_item_compatibility(self, item: &ItemImpl) -> Result<()> {
+        match self {
+            Self::Executor => RtEngineBackend::check_item_compatibility(item),
+            #[cfg(feature = "portable-backend")]
+            Self::Portable => PortableBackend::check_item_compatibility(item),
+        }
+    }
+
+    pub fn input_channel(self, args: &RtEngineArgs, model_ident: &Ident) -> ChannelTokens {
+        match self {
+            Self::Executor => RtEngineBackend::input_channel(args, model_ident),
+            #[cfg(feature = "portable-backend")]
+            Self::Portable => PortableBackend::input_channel(args, model_ident),
+        }
+    }
+
+    pub fn output_channel(self, args: &RtEngineArgs, model_ident: &Ident) -> ChannelTokens {
+        match self {
+            Self::Executor => RtEngineBackend::output_channel(args, model_ident),
+            #[cfg(feature = "portable-backend")]
+            Self::Portable => PortableBackend::output_channel(args, model_ident),
+        }
+    }
+}
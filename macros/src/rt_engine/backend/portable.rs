@@ -0,0 +1,105 @@
+use super::{Backend, ChannelTokens, RtEngineArgs};
+use heck::ToShoutySnakeCase;
+use syn::{Ident, ItemImpl, MetaNameValue, Result};
+
+/// Executor-agnostic backend based on static lock-free channels.
+#[derive(Debug, Clone)]
+pub struct PortableBackend;
+
+impl Backend for PortableBackend {
+    fn check_arg_compatibility(arg: &MetaNameValue) -> Result<()> {
+        let name = arg
+            .path
+            .require_ident()
+            .map(|i| i.to_string())
+            .unwrap_or_default();
+        if name != "in_channel_size" && name != "out_channel_size" {
+            return Ok(());
+        }
+        // Non-integer values are reported in the main macro code
+        if let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) = &arg.value
+        {
+            if let Ok(size) = lit.base10_parse::<usize>() {
+                if !size.is_power_of_two() {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        format!("{name} must be a power of two in the portable backend"),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_item_compatibility(item: &ItemImpl) -> Result<()> {
+        if !item.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &item.generics,
+                "The `#[rt_engine]` macro portable backend does not support generic parameters.",
+            ));
+        }
+        Ok(())
+    }
+
+    fn input_channel(args: &RtEngineArgs, model_ident: &Ident) -> ChannelTokens {
+        let input_ident = quote::format_ident!("{}Input", model_ident);
+        let in_channel_size = args.in_channel_size;
+
+        let channel_type = quote::quote! { ::xdevs::export::portable::InputChannel<'static,
+            <Self as ::xdevs::port::BagMux>::Mux,
+            #in_channel_size
+        > };
+        let upper_name = model_ident.to_string().to_shouty_snake_case();
+        let channel_ident = quote::format_ident!("{}_IN_CHANNEL", upper_name);
+        let channel_call =
+            quote::quote! {::xdevs::export::portable::InputChannel::new(&#channel_ident) };
+
+        let private_channel = quote::quote! {
+            /// Auto-generated static input channel.
+            pub static #channel_ident: ::xdevs::export::portable::Channel<
+                <#input_ident as ::xdevs::port::BagMux>::Mux,
+                #in_channel_size
+            > = ::xdevs::export::portable::Channel::new();
+        };
+
+        ChannelTokens {
+            channel_type,
+            channel_call,
+            private_channel,
+        }
+    }
+
+    fn output_channel(args: &RtEngineArgs, model_ident: &Ident) -> ChannelTokens {
+        let output_ident = quote::format_ident!("{}Output", model_ident);
+        let out_channel_size = args.out_channel_size;
+        let max_out_subs = args.max_out_subs;
+
+        let channel_type = quote::quote! { ::xdevs::export::portable::OutputChannel<'static,
+            <Self as ::xdevs::port::BagMux>::Mux,
+            #out_channel_size,
+            #max_out_subs
+        > };
+        let upper_name = model_ident.to_string().to_shouty_snake_case();
+        let channel_ident = quote::format_ident!("{}_OUT_CHANNEL", upper_name);
+        let channel_call =
+            quote::quote! {::xdevs::export::portable::OutputChannel::new(&#channel_ident) };
+
+        let private_channel = quote::quote! {
+            /// Auto-generated static output PubSub channel.
+            pub static #channel_ident: ::xdevs::export::portable::PubSubChannel<
+                <#output_ident as ::xdevs::port::BagMux>::Mux,
+                #out_channel_size,
+                #max_out_subs,
+            > = ::xdevs::export::portable::PubSubChannel::new();
+        };
+
+        ChannelTokens {
+            channel_type,
+            channel_call,
+            private_channel,
+        }
+    }
+}
@@ -0,0 +1,486 @@
+use crate::combine_err;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    braced,
+    ext::IdentExt,
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Block, Error, Expr, Ident, Result, Token, Type, Visibility,
+};
+
+mod kw {
+    syn::custom_keyword!(input);
+    syn::custom_keyword!(output);
+    syn::custom_keyword!(initial);
+    syn::custom_keyword!(phases);
+    syn::custom_keyword!(internal);
+    syn::custom_keyword!(external);
+    syn::custom_keyword!(update);
+    syn::custom_keyword!(on);
+}
+
+/// State variable of the model, with an optional initial value.
+struct StateField {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    ty: Type,
+    init: Option<Expr>,
+}
+
+impl Parse for StateField {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        let init = match input.parse::<Option<Token![=]>>()? {
+            Some(_) => Some(input.parse()?),
+            None => None,
+        };
+        Ok(Self {
+            attrs,
+            vis,
+            ident,
+            ty,
+            init,
+        })
+    }
+}
+
+/// `Phase => ta_expr`
+struct PhaseDef {
+    ident: Ident,
+    ta: Expr,
+}
+
+/// `From -> To { output { .. } update { .. } }`
+struct InternalRule {
+    from: Ident,
+    to: Ident,
+    output: Option<Block>,
+    update: Option<Block>,
+}
+
+impl Parse for InternalRule {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let from = input.parse()?;
+        input.parse::<Token![->]>()?;
+        let to = input.parse()?;
+        let content;
+        braced!(content in input);
+        let (mut output, mut update) = (None, None);
+        while !content.is_empty() {
+            let lookahead = content.lookahead1();
+            if lookahead.peek(kw::output) {
+                let kw = content.parse::<kw::output>()?;
+                if output.replace(content.parse()?).is_some() {
+                    return Err(Error::new_spanned(kw, "duplicate `output` block"));
+                }
+            } else if lookahead.peek(kw::update) {
+                let kw = content.parse::<kw::update>()?;
+                if update.replace(content.parse()?).is_some() {
+                    return Err(Error::new_spanned(kw, "duplicate `update` block"));
+                }
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+        Ok(Self {
+            from,
+            to,
+            output,
+            update,
+        })
+    }
+}
+
+/// `Phase | Phase on port(values) => expr`
+struct ExternalRule {
+    /// Phases in which the rule applies (`None` for `_`).
+    phases: Option<Vec<Ident>>,
+    port: Ident,
+    values: Option<Ident>,
+    body: Expr,
+}
+
+impl Parse for ExternalRule {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let phases = match input.parse::<Option<Token![_]>>()? {
+            Some(_) => None,
+            None => Some(
+                Punctuated::<Ident, Token![|]>::parse_separated_nonempty(input)?
+                    .into_iter()
+                    .collect(),
+            ),
+        };
+        input.parse::<kw::on>()?;
+        let port = input.call(Ident::parse_any)?;
+        let values = match input.peek(syn::token::Paren) {
+            true => {
+                let content;
+                parenthesized!(content in input);
+                Some(content.parse()?)
+            }
+            false => None,
+        };
+        input.parse::<Token![=>]>()?;
+        let body = input.parse()?;
+        Ok(Self {
+            phases,
+            port,
+            values,
+            body,
+        })
+    }
+}
+
+pub struct StateMachine {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    fields: Vec<StateField>,
+    input: Option<Type>,
+    output: Option<Type>,
+    initial: Option<(Ident, Option<Expr>)>,
+    phases: Vec<PhaseDef>,
+    internal: Vec<InternalRule>,
+    external: Vec<ExternalRule>,
+}
+
+impl Parse for StateMachine {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![struct]>()?;
+        let ident = input.parse()?;
+        let content;
+        braced!(content in input);
+        let fields = Punctuated::<StateField, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+
+        let mut machine = Self {
+            attrs,
+            vis,
+            ident,
+            fields,
+            input: None,
+            output: None,
+            initial: None,
+            phases: Vec::new(),
+            internal: Vec::new(),
+            external: Vec::new(),
+        };
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::input) {
+                let kw = input.parse::<kw::input>()?;
+                input.parse::<Token![:]>()?;
+                if machine.input.replace(input.parse()?).is_some() {
+                    return Err(Error::new_spanned(kw, "duplicate `input` type"));
+                }
+                input.parse::<Token![;]>()?;
+            } else if lookahead.peek(kw::output) {
+                let kw = input.parse::<kw::output>()?;
+                input.parse::<Token![:]>()?;
+                if machine.output.replace(input.parse()?).is_some() {
+                    return Err(Error::new_spanned(kw, "duplicate `output` type"));
+                }
+                input.parse::<Token![;]>()?;
+            } else if lookahead.peek(kw::initial) {
+                let kw = input.parse::<kw::initial>()?;
+                input.parse::<Token![:]>()?;
+                let phase = input.parse()?;
+                let sigma = match input.peek(syn::token::Paren) {
+                    true => {
+                        let content;
+                        parenthesized!(content in input);
+                        Some(content.parse()?)
+                    }
+                    false => None,
+                };
+                if machine.initial.replace((phase, sigma)).is_some() {
+                    return Err(Error::new_spanned(kw, "duplicate `initial` phase"));
+                }
+                input.parse::<Token![;]>()?;
+            } else if lookahead.peek(kw::phases) {
+                input.parse::<kw::phases>()?;
+                let content;
+                braced!(content in input);
+                while !content.is_empty() {
+                    let ident = content.parse()?;
+                    content.parse::<Token![=>]>()?;
+                    let ta = content.parse()?;
+                    machine.phases.push(PhaseDef { ident, ta });
+                    if !content.is_empty() {
+                        content.parse::<Token![,]>()?;
+                    }
+                }
+            } else if lookahead.peek(kw::internal) {
+                input.parse::<kw::internal>()?;
+                let content;
+                braced!(content in input);
+                while !content.is_empty() {
+                    machine.internal.push(content.parse()?);
+                    content.parse::<Option<Token![,]>>()?;
+                }
+            } else if lookahead.peek(kw::external) {
+                input.parse::<kw::external>()?;
+                let content;
+                braced!(content in input);
+                while !content.is_empty() {
+                    machine.external.push(content.parse()?);
+                    content.parse::<Option<Token![,]>>()?;
+                }
+            } else {
+                return Err(lookahead.error());
+            }
+        }
+        Ok(machine)
+    }
+}
+
+impl StateMachine {
+    /// Checks that all the phases referenced by the rules are declared.
+    fn validate(&self) -> Result<()> {
+        if self.phases.is_empty() {
+            return Err(Error::new_spanned(
+                &self.ident,
+                "state machine must declare at least one phase",
+            ));
+        }
+        let mut acc: Option<Error> = None;
+        for (i, phase) in self.phases.iter().enumerate() {
+            if self.phases[..i].iter().any(|p| p.ident == phase.ident) {
+                combine_err(
+                    &mut acc,
+                    Error::new_spanned(&phase.ident, "duplicate phase"),
+                );
+            }
+        }
+        for (i, rule) in self.internal.iter().enumerate() {
+            if self.internal[..i].iter().any(|r| r.from == rule.from) {
+                let msg = "duplicate internal rule for this phase";
+                combine_err(&mut acc, Error::new_spanned(&rule.from, msg));
+            }
+        }
+
+        let mut referenced: Vec<&Ident> = Vec::new();
+        match &self.initial {
+            Some((phase, _)) => referenced.push(phase),
+            None => {
+                let msg = "missing `initial: Phase;`";
+                combine_err(&mut acc, Error::new_spanned(&self.ident, msg));
+            }
+        }
+        referenced.extend(self.internal.iter().flat_map(|r| [&r.from, &r.to]));
+        referenced.extend(self.external.iter().flat_map(|r| r.phases.iter().flatten()));
+        for ident in referenced {
+            if !self.phases.iter().any(|p| p.ident == *ident) {
+                combine_err(&mut acc, Error::new_spanned(ident, "unknown phase"));
+            }
+        }
+
+        match acc {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+pub fn expand(machine: StateMachine) -> Result<TokenStream2> {
+    machine.validate()?;
+
+    let StateMachine {
+        attrs,
+        vis,
+        ident,
+        fields,
+        input,
+        output,
+        initial,
+        phases,
+        internal,
+        external,
+    } = machine;
+    let phase_ident = format_ident!("{}Phase", ident);
+    let input = input.map_or_else(|| quote!(()), |ty| quote!(#ty));
+    let output = output.map_or_else(|| quote!(()), |ty| quote!(#ty));
+
+    // State struct and constructor
+    let field_defs = fields.iter().map(|f| {
+        let (attrs, vis, ident, ty) = (&f.attrs, &f.vis, &f.ident, &f.ty);
+        quote!(#(#attrs)* #vis #ident: #ty)
+    });
+    let args = fields.iter().filter(|f| f.init.is_none()).map(|f| {
+        let (ident, ty) = (&f.ident, &f.ty);
+        quote!(#ident: #ty)
+    });
+    let inits = fields.iter().map(|f| {
+        let ident = &f.ident;
+        match &f.init {
+            Some(init) => quote!(#ident: #init),
+            None => quote!(#ident),
+        }
+    });
+    let (initial_phase, initial_sigma) = initial.expect("validated");
+    let initial_sigma = match initial_sigma {
+        Some(sigma) => quote!(#sigma),
+        None => quote!(model.phase_ta(#phase_ident::#initial_phase)),
+    };
+
+    // Phases and time advances
+    let phase_idents: Vec<_> = phases.iter().map(|p| &p.ident).collect();
+    let phase_tas = phases.iter().map(|p| &p.ta);
+
+    // Internal transitions and output
+    let int_from: Vec<_> = internal.iter().map(|r| &r.from).collect();
+    let int_to = internal.iter().map(|r| &r.to);
+    let int_update = internal.iter().map(|r| &r.update);
+    let lambda_arms = internal.iter().filter_map(|r| {
+        let (from, block) = (&r.from, r.output.as_ref()?);
+        Some(quote!(#phase_ident::#from => #block))
+    });
+
+    // External transitions, grouped by input port in declaration order
+    let mut ports: Vec<&Ident> = Vec::new();
+    for rule in &external {
+        if !ports.contains(&&rule.port) {
+            ports.push(&rule.port);
+        }
+    }
+    let port_rules = ports.iter().map(|port| {
+        let port_expr = match *port == "input" {
+            true => quote!(input),
+            false => quote!(&input.#port),
+        };
+        let arms = external.iter().filter(|r| r.port == **port).map(|rule| {
+            let pattern = match &rule.phases {
+                Some(phases) => quote!(#(#phase_ident::#phases)|*),
+                None => quote!(_),
+            };
+            let values = match &rule.values {
+                Some(values) => quote!(let #values = port.get_values();),
+                None => quote!(),
+            };
+            let body = &rule.body;
+            quote!(#pattern => { #values #body })
+        });
+        quote! {
+            let port = #port_expr;
+            if !xdevs::port::Bag::is_empty(port) {
+                #[allow(unreachable_patterns)]
+                let next: Option<#phase_ident> = match phase {
+                    #(#arms,)*
+                    _ => None,
+                };
+                if let Some(next) = next {
+                    self.enter(next);
+                }
+            }
+        }
+    });
+
+    let doc_new = format!("Creates a new [`{ident}`] in its initial phase.");
+    let doc_phase = format!("Phases of [`{ident}`].");
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis struct #ident {
+            #(#field_defs,)*
+            phase: #phase_ident,
+            sigma: f64,
+        }
+
+        #[doc = #doc_phase]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis enum #phase_ident {
+            #(#phase_idents,)*
+        }
+
+        impl #ident {
+            #[doc = #doc_new]
+            #vis fn new(#(#args),*) -> Self {
+                let mut model = Self {
+                    #(#inits,)*
+                    phase: #phase_ident::#initial_phase,
+                    sigma: 0.0,
+                };
+                model.sigma = #initial_sigma;
+                model
+            }
+
+            /// Returns the current phase.
+            #[inline]
+            #vis fn phase(&self) -> #phase_ident {
+                self.phase
+            }
+
+            /// Returns the time remaining until the next internal transition.
+            #[inline]
+            #vis fn sigma(&self) -> f64 {
+                self.sigma
+            }
+
+            #[inline]
+            fn phase_ta(&self, phase: #phase_ident) -> f64 {
+                match phase {
+                    #(#phase_ident::#phase_idents => #phase_tas,)*
+                }
+            }
+
+            /// Moves to `phase`, resetting sigma to its time advance.
+            #[inline]
+            fn enter(&mut self, phase: #phase_ident) {
+                self.phase = phase;
+                self.sigma = self.phase_ta(phase);
+            }
+        }
+
+        impl xdevs::Component for #ident {
+            type Kind = xdevs::AtomicKind;
+            type Input = #input;
+            type Output = #output;
+        }
+
+        impl xdevs::Atomic for #ident {
+            fn delta_int(&mut self) {
+                #[allow(unreachable_patterns)]
+                match self.phase {
+                    #(#phase_ident::#int_from => {
+                        #int_update
+                        self.enter(#phase_ident::#int_to);
+                    })*
+                    phase => self.enter(phase),
+                }
+            }
+
+            #[allow(unused_variables, unused_imports)]
+            fn delta_ext(&mut self, elapsed: f64, input: &Self::Input) {
+                use #phase_ident::*;
+                self.sigma -= elapsed;
+                let phase = self.phase;
+                #(#port_rules)*
+            }
+
+            #[allow(unused_variables)]
+            fn lambda(&self, output: &mut Self::Output) {
+                #[allow(unreachable_patterns)]
+                match self.phase {
+                    #(#lambda_arms)*
+                    _ => {}
+                }
+            }
+
+            #[inline]
+            fn ta(&self) -> f64 {
+                self.sigma
+            }
+        }
+    })
+}
@@ -1,23 +1,163 @@
 use crate::combine_err;
 use proc_macro2::TokenStream as TokenStream2;
-use syn::{Error, ItemEnum, ItemStruct, Result};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    Attribute, Error, Expr, ExprLit, ExprUnary, Ident, ItemEnum, ItemStruct, Lit, Result, UnOp,
+};
+
+/// Arguments of the `#[to_component]` (and `#[coupled]`) attribute.
+#[derive(Default)]
+pub struct ComponentArgs {
+    /// Classic DEVS mode: only the imminent component with the highest priority
+    /// is executed in each simulation step.
+    pub classic: bool,
+    /// Shared mode: the only component uses the input and output bags of its parent.
+    pub shared: bool,
+    /// Implement the `Reset` trait by resetting all the components.
+    pub reset: bool,
+    /// Generate a builder that splits the coupled model into logical processes
+    /// for optimistic simulation (only for `#[coupled]`).
+    pub optimistic: bool,
+}
+
+impl Parse for ComponentArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = ComponentArgs::default();
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            let flag = match ident.to_string().as_str() {
+                "classic" => &mut args.classic,
+                "shared" => &mut args.shared,
+                "reset" => &mut args.reset,
+                "optimistic" => &mut args.optimistic,
+                _ => {
+                    return Err(Error::new_spanned(
+                        ident,
+                        "expected `classic`, `shared`, `reset` or `optimistic`",
+                    ))
+                }
+            };
+            if *flag {
+                return Err(Error::new_spanned(ident, "duplicate argument"));
+            }
+            *flag = true;
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// Parses the value of a `#[priority = n]` attribute.
+fn parse_priority(attr: &Attribute) -> Result<i64> {
+    let syn::Meta::NameValue(meta) = &attr.meta else {
+        return Err(Error::new_spanned(attr, "expected `#[priority = n]`"));
+    };
+    match &meta.value {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => lit.base10_parse(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => match expr.as_ref() {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) => lit.base10_parse::<i64>().map(|p| -p),
+            _ => Err(Error::new_spanned(&meta.value, "expected integer priority")),
+        },
+        _ => Err(Error::new_spanned(&meta.value, "expected integer priority")),
+    }
+}
 
-pub fn expand_struct(item: ItemStruct) -> Result<TokenStream2> {
+/// Parses a `#[shared]`, `#[shared(input)]` or `#[shared(output)]` attribute.
+/// Returns whether the component shares the input and the output of its parent.
+fn parse_shared(attr: &Attribute) -> Result<(bool, bool)> {
+    if let syn::Meta::Path(_) = &attr.meta {
+        return Ok((true, true));
+    }
+    let idents = attr
+        .parse_args_with(syn::punctuated::Punctuated::<Ident, syn::Token![,]>::parse_terminated)?;
+    let (mut input, mut output) = (false, false);
+    for ident in idents {
+        let flag = match ident.to_string().as_str() {
+            "input" => &mut input,
+            "output" => &mut output,
+            _ => return Err(Error::new_spanned(ident, "expected `input` or `output`")),
+        };
+        if *flag {
+            return Err(Error::new_spanned(ident, "duplicate argument"));
+        }
+        *flag = true;
+    }
+    if !input && !output {
+        return Err(Error::new_spanned(attr, "expected `input` or `output`"));
+    }
+    Ok((input, output))
+}
+
+pub fn expand_struct(mut item: ItemStruct, args: ComponentArgs) -> Result<TokenStream2> {
     let mut acc: Option<Error> = None;
 
-    // Extract the field identifiers and types from the struct
+    if args.optimistic {
+        combine_err(
+            &mut acc,
+            Error::new_spanned(
+                &item.ident,
+                "optimistic mode is only supported for coupled models",
+            ),
+        );
+    }
+
+    // Extract the field identifiers, types, and priorities from the struct
     let mut item_fields = Vec::new();
     let mut item_tys = Vec::new();
+    let mut priorities = Vec::new();
+    let mut shared_fields = Vec::new();
 
-    match &item.fields {
+    match &mut item.fields {
         syn::Fields::Named(fields) => {
-            for field in &fields.named {
+            for field in &mut fields.named {
+                let mut priority = 0;
+                let mut found = false;
+                for attr in field.attrs.iter().filter(|a| a.path().is_ident("priority")) {
+                    if found {
+                        combine_err(&mut acc, Error::new_spanned(attr, "duplicate priority"));
+                        continue;
+                    }
+                    found = true;
+                    match parse_priority(attr) {
+                        Ok(p) => priority = p,
+                        Err(err) => combine_err(&mut acc, err),
+                    }
+                }
+                let mut shared = (false, false);
+                for attr in field.attrs.iter().filter(|a| a.path().is_ident("shared")) {
+                    if shared != (false, false) {
+                        combine_err(&mut acc, Error::new_spanned(attr, "duplicate shared"));
+                        continue;
+                    }
+                    match parse_shared(attr) {
+                        Ok(s) => shared = s,
+                        Err(err) => combine_err(&mut acc, err),
+                    }
+                }
+                // Priority and shared attributes are consumed here
+                field
+                    .attrs
+                    .retain(|a| !a.path().is_ident("priority") && !a.path().is_ident("shared"));
+
                 let Some(field_ident) = &field.ident else {
                     combine_err(&mut acc, Error::new_spanned(field, "expected named field"));
                     continue;
                 };
                 item_fields.push(field_ident.clone());
                 item_tys.push(field.ty.clone());
+                priorities.push(priority);
+                shared_fields.push(shared);
             }
         }
         _ => {
@@ -28,12 +168,85 @@ pub fn expand_struct(item: ItemStruct) -> Result<TokenStream2> {
         }
     }
 
+    if args.shared && item_fields.len() != 1 {
+        combine_err(
+            &mut acc,
+            Error::new_spanned(
+                &item.fields,
+                "shared components must have exactly one field",
+            ),
+        );
+    }
+
+    // Components with shared bags use the bags of the parent instead of their own
+    let mixed = shared_fields.iter().any(|&(input, output)| input || output);
+    if args.shared && mixed {
+        combine_err(
+            &mut acc,
+            Error::new_spanned(
+                &item.fields,
+                "shared components cannot have `#[shared]` fields",
+            ),
+        );
+    }
+    if shared_fields.iter().filter(|&&(input, _)| input).count() > 1 {
+        // Components clear their input after their transitions
+        combine_err(
+            &mut acc,
+            Error::new_spanned(
+                &item.fields,
+                "only one component can share the input of its parent",
+            ),
+        );
+    }
+
     if let Some(err) = acc {
         return Err(err);
     }
 
+    // The footprint of the components follows the declaration order
+    let footprint = footprint_impl(&item.generics, &item.ident, &item_fields, &item_tys, false);
+    let reset = args.reset.then(|| {
+        reset_impl(
+            &item.generics,
+            &item.ident,
+            quote::quote! {
+                #(::xdevs::simulation::reset::Reset::reset(&mut self.#item_fields);)*
+            },
+        )
+    });
+
+    // Components are visited by decreasing priority (ties keep the declaration order)
+    let mut order: Vec<usize> = (0..item_fields.len()).collect();
+    order.sort_by_key(|&i| core::cmp::Reverse(priorities[i]));
+    let item_fields: Vec<_> = order.iter().map(|&i| item_fields[i].clone()).collect();
+    let item_tys: Vec<_> = order.iter().map(|&i| item_tys[i].clone()).collect();
+    let shared_fields: Vec<_> = order.iter().map(|&i| shared_fields[i]).collect();
+    let item_names: Vec<_> = item_fields.iter().map(|f| f.unraw().to_string()).collect();
+
     // Generate the input and output wrapper structs, and modify the original struct's fields to be of Simulator types
-    let (input_struct, output_struct, item) = crate::build_component_structs(item);
+    let (mut input_struct, mut output_struct, item) = crate::build_component_structs(item);
+    // Shared components do not have their own bags. Their fields are kept as markers,
+    // so the bags still use all the generics of the components.
+    let shared_ty = |field: &syn::Field, input: bool| {
+        let i = item_fields
+            .iter()
+            .position(|f| Some(f) == field.ident.as_ref())?;
+        let shared = match input {
+            true => shared_fields[i].0,
+            false => shared_fields[i].1,
+        };
+        shared.then(|| &item_tys[i])
+    };
+    for (bag_struct, input) in [(&mut input_struct, true), (&mut output_struct, false)] {
+        if let syn::Fields::Named(fields) = &mut bag_struct.fields {
+            for field in &mut fields.named {
+                if let Some(ty) = shared_ty(field, input) {
+                    field.ty = syn::parse_quote!(::core::marker::PhantomData<#ty>);
+                }
+            }
+        }
+    }
 
     // Generate the implementation of the Component and AbstractSimulator traits for the struct
     let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
@@ -41,21 +254,162 @@ pub fn expand_struct(item: ItemStruct) -> Result<TokenStream2> {
     let item_input_ident = &input_struct.ident;
     let item_output_ident = &output_struct.ident;
 
-    let expanded = quote::quote! {
-        #[derive(xdevs::Bag)]
-        #input_struct
+    // In shared mode, the bags of the only component are the bags of the struct.
+    // With shared fields, their bags are the `shared_input` and `shared_output` of the parent.
+    let (inputs, outputs): (Vec<_>, Vec<_>) = item_fields
+        .iter()
+        .zip(&shared_fields)
+        .map(|(field, &(shared_input, shared_output))| {
+            let input = match (args.shared, shared_input) {
+                (true, _) => quote::quote!(*input),
+                (false, true) => quote::quote!(*shared_input),
+                (false, false) => quote::quote!(input.#field),
+            };
+            let output = match (args.shared, shared_output) {
+                (true, _) => quote::quote!(*output),
+                (false, true) => quote::quote!(*shared_output),
+                (false, false) => quote::quote!(output.#field),
+            };
+            (input, output)
+        })
+        .unzip();
+    let (build_inputs, build_outputs): (Vec<_>, Vec<_>) = item_fields
+        .iter()
+        .zip(&shared_fields)
+        .map(|(field, &(shared_input, shared_output))| {
+            let marker = quote::quote!(::core::marker::PhantomData);
+            let input = match shared_input {
+                true => marker.clone(),
+                false => quote::quote!(::xdevs::simulation::AbstractSimulator::build_input(&self.#field)),
+            };
+            let output = match shared_output {
+                true => marker,
+                false => quote::quote!(::xdevs::simulation::AbstractSimulator::build_output(&self.#field)),
+            };
+            (input, output)
+        })
+        .unzip();
+    let (bag_structs, input_ty, output_ty, build_input, build_output) = if args.shared {
+        let ty = &item_tys[0];
+        let field = &item_fields[0];
+        (
+            None,
+            quote::quote!(<#ty as ::xdevs::Component>::Input),
+            quote::quote!(<#ty as ::xdevs::Component>::Output),
+            quote::quote!(::xdevs::simulation::AbstractSimulator::build_input(&self.#field)),
+            quote::quote!(::xdevs::simulation::AbstractSimulator::build_output(&self.#field)),
+        )
+    } else {
+        (
+            Some(quote::quote! {
+                #[derive(xdevs::Bag)]
+                #input_struct
+
+                #[derive(xdevs::Bag)]
+                #output_struct
+            }),
+            quote::quote!(#item_input_ident #ty_generics),
+            quote::quote!(#item_output_ident #ty_generics),
+            quote::quote! {
+                #item_input_ident {
+                    #(#item_fields: #build_inputs,)*
+                }
+            },
+            quote::quote! {
+                #item_output_ident {
+                    #(#item_fields: #build_outputs,)*
+                }
+            },
+        )
+    };
+
+    let (lambda, delta) = if args.classic {
+        // Only the first imminent component (in priority order) is executed.
+        // The remaining imminent components are executed in subsequent steps.
+        let lambda = quote::quote! {
+            #(if ::xdevs::simulation::AbstractSimulator::t_next(&self.#item_fields) <= t {
+                ::xdevs::simulation::AbstractSimulator::lambda(&mut self.#item_fields, &mut #outputs, t);
+                return;
+            })*
+        };
+        // The other components only execute their external transitions, even if they are
+        // imminent. Imminent components without input keep their internal transitions (and
+        // outputs) for subsequent steps, while those with input are rescheduled as in Classic DEVS.
+        let delta = quote::quote! {
+            let mut selected = false;
+            let mut t_next = f64::INFINITY;
+            #({
+                let imminent = !selected && ::xdevs::simulation::AbstractSimulator::t_next(&self.#item_fields) <= t;
+                selected |= imminent;
+                let t_next_i = if imminent {
+                    ::xdevs::simulation::AbstractSimulator::delta(
+                        &mut self.#item_fields,
+                        &mut #inputs,
+                        &mut #outputs,
+                        t)
+                } else {
+                    ::xdevs::simulation::AbstractSimulator::delta_ext(&mut self.#item_fields, &mut #inputs, t)
+                };
+                t_next = f64::min(t_next, t_next_i);
+            })*
+            t_next
+        };
+        (lambda, delta)
+    } else {
+        let lambda = quote::quote! {
+            #(::xdevs::simulation::AbstractSimulator::lambda(&mut self.#item_fields, &mut #outputs, t);)*
+        };
+        let delta = quote::quote! {
+            let mut t_next = f64::INFINITY;
+            #(t_next = f64::min(t_next, ::xdevs::simulation::AbstractSimulator::delta(
+                    &mut self.#item_fields,
+                    &mut #inputs,
+                    &mut #outputs,
+                    t));)*
+            t_next
+        };
+        (lambda, delta)
+    };
 
-        #[derive(xdevs::Bag)]
-        #output_struct
+    let delta_ext = quote::quote! {
+        let mut t_next = f64::INFINITY;
+        #(t_next = f64::min(t_next, ::xdevs::simulation::AbstractSimulator::delta_ext(
+                &mut self.#item_fields,
+                &mut #inputs,
+                t));)*
+        t_next
+    };
+
+    let (lambda_fn, delta_fn, delta_ext_fn, shared_impl) = if mixed {
+        shared_impl(
+            &item.generics,
+            item_ident,
+            &item_fields,
+            &item_tys,
+            &shared_fields,
+            [lambda, delta, delta_ext],
+        )
+    } else {
+        (lambda, delta, delta_ext, TokenStream2::new())
+    };
+
+    let expanded = quote::quote! {
+        #bag_structs
 
         #item
 
         impl #impl_generics ::xdevs::Component for #item_ident #ty_generics #where_clause {
             type Kind = ::xdevs::ComponentsKind;
-            type Input = #item_input_ident #ty_generics;
-            type Output = #item_output_ident #ty_generics;
+            type Input = #input_ty;
+            type Output = #output_ty;
         }
 
+        #footprint
+
+        #reset
+
+        #shared_impl
+
         unsafe impl #impl_generics ::xdevs::simulation::AbstractSimulator for #item_ident #ty_generics #where_clause {
             type Input = <Self as ::xdevs::Component>::Input;
             type Output = <Self as ::xdevs::Component>::Output;
@@ -74,27 +428,80 @@ pub fn expand_struct(item: ItemStruct) -> Result<TokenStream2> {
 
             #[inline(always)]
             fn lambda(&mut self, output: &mut Self::Output, t: f64) {
-                #(::xdevs::simulation::AbstractSimulator::lambda(&mut self.#item_fields, &mut output.#item_fields, t);)*
+                #lambda_fn
             }
 
             #[inline(always)]
             fn delta(&mut self, input: &mut Self::Input, output: &mut Self::Output, t: f64) -> f64 {
+                #delta_fn
+            }
+
+            #[inline(always)]
+            fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+                #delta_ext_fn
+            }
+
+            #[inline(always)]
+            fn t_next(&self) -> f64 {
                 let mut t_next = f64::INFINITY;
-                #(t_next = f64::min(t_next, ::xdevs::simulation::AbstractSimulator::delta(
-                        &mut self.#item_fields,
-                        &mut input.#item_fields,
-                        &mut output.#item_fields,
-                        t));)*
+                #(t_next = f64::min(t_next, ::xdevs::simulation::AbstractSimulator::t_next(&self.#item_fields));)*
                 t_next
             }
+
+            #[inline(always)]
+            fn build_input(&self) -> Self::Input {
+                #build_input
+            }
+
+            #[inline(always)]
+            fn build_output(&self) -> Self::Output {
+                #build_output
+            }
+
+            #[inline(always)]
+            fn has_error(&self) -> bool {
+                false #(|| ::xdevs::simulation::AbstractSimulator::has_error(&self.#item_fields))*
+            }
+
+            #[inline(always)]
+            fn take_error(&mut self) -> Option<::xdevs::simulation::error::ModelError> {
+                #(if let Some(mut error) = ::xdevs::simulation::AbstractSimulator::take_error(&mut self.#item_fields) {
+                    error.push_parent(::xdevs::simulation::error::PathSegment::Field(#item_names));
+                    return Some(error);
+                })*
+                None
+            }
+
+            #[inline(always)]
+            fn take_isolated_error(&mut self) -> Option<::xdevs::simulation::error::ModelError> {
+                #(if let Some(mut error) = ::xdevs::simulation::AbstractSimulator::take_isolated_error(&mut self.#item_fields) {
+                    error.push_parent(::xdevs::simulation::error::PathSegment::Field(#item_names));
+                    return Some(error);
+                })*
+                None
+            }
         }
     };
 
     Ok(expanded)
 }
-pub fn expand_enum(mut item: ItemEnum) -> Result<TokenStream2> {
+
+pub fn expand_enum(mut item: ItemEnum, args: ComponentArgs) -> Result<TokenStream2> {
     let mut acc: Option<Error> = None;
 
+    if args.classic || args.shared {
+        return Err(Error::new_spanned(
+            &item.ident,
+            "classic and shared modes are only supported for struct components",
+        ));
+    }
+    if args.optimistic {
+        return Err(Error::new_spanned(
+            &item.ident,
+            "optimistic mode is only supported for coupled models",
+        ));
+    }
+
     let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
     let item_ident = &item.ident;
 
@@ -141,6 +548,26 @@ pub fn expand_enum(mut item: ItemEnum) -> Result<TokenStream2> {
         ));
     }
 
+    let footprint = footprint_impl(
+        &item.generics,
+        &item.ident,
+        &variant_idents,
+        &variant_tys,
+        true,
+    );
+
+    let reset = args.reset.then(|| {
+        reset_impl(
+            &item.generics,
+            &item.ident,
+            quote::quote! {
+                match self {
+                    #(#item_ident::#variant_idents(inner) => ::xdevs::simulation::reset::Reset::reset(inner)),*
+                }
+            },
+        )
+    });
+
     let first_variant_ty = &variant_tys[0];
     for variant in item.variants.iter_mut() {
         if let syn::Fields::Unnamed(fields) = &mut variant.fields {
@@ -177,6 +604,48 @@ pub fn expand_enum(mut item: ItemEnum) -> Result<TokenStream2> {
         }
     });
 
+    let delta_ext_arms = variant_idents.iter().map(|ident| {
+        quote::quote! {
+            #item_ident::#ident(inner) => ::xdevs::simulation::AbstractSimulator::delta_ext(inner, input, t)
+        }
+    });
+
+    let t_next_arms = variant_idents.iter().map(|ident| {
+        quote::quote! {
+            #item_ident::#ident(inner) => ::xdevs::simulation::AbstractSimulator::t_next(inner)
+        }
+    });
+
+    let build_input_arms = variant_idents.iter().map(|ident| {
+        quote::quote! {
+            #item_ident::#ident(inner) => ::xdevs::simulation::AbstractSimulator::build_input(inner)
+        }
+    });
+
+    let build_output_arms = variant_idents.iter().map(|ident| {
+        quote::quote! {
+            #item_ident::#ident(inner) => ::xdevs::simulation::AbstractSimulator::build_output(inner)
+        }
+    });
+
+    let has_error_arms = variant_idents.iter().map(|ident| {
+        quote::quote! {
+            #item_ident::#ident(inner) => ::xdevs::simulation::AbstractSimulator::has_error(inner)
+        }
+    });
+
+    let take_error_arms = variant_idents.iter().map(|ident| {
+        quote::quote! {
+            #item_ident::#ident(inner) => ::xdevs::simulation::AbstractSimulator::take_error(inner)
+        }
+    });
+
+    let take_isolated_error_arms = variant_idents.iter().map(|ident| {
+        quote::quote! {
+            #item_ident::#ident(inner) => ::xdevs::simulation::AbstractSimulator::take_isolated_error(inner)
+        }
+    });
+
     let expanded = quote::quote! {
         #item
 
@@ -186,6 +655,10 @@ pub fn expand_enum(mut item: ItemEnum) -> Result<TokenStream2> {
             type Output = <#first_variant_ty as ::xdevs::Component>::Output;
         }
 
+        #footprint
+
+        #reset
+
         unsafe impl #impl_generics ::xdevs::simulation::AbstractSimulator for #item_ident #ty_generics #where_clause {
             type Input = <#first_variant_ty as ::xdevs::Component>::Input;
             type Output = <#first_variant_ty as ::xdevs::Component>::Output;
@@ -217,8 +690,203 @@ pub fn expand_enum(mut item: ItemEnum) -> Result<TokenStream2> {
                     #(#delta_arms),*
                 }
             }
+
+            #[inline(always)]
+            fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+                match self {
+                    #(#delta_ext_arms),*
+                }
+            }
+
+            #[inline(always)]
+            fn t_next(&self) -> f64 {
+                match self {
+                    #(#t_next_arms),*
+                }
+            }
+
+            #[inline(always)]
+            fn build_input(&self) -> Self::Input {
+                match self {
+                    #(#build_input_arms),*
+                }
+            }
+
+            #[inline(always)]
+            fn build_output(&self) -> Self::Output {
+                match self {
+                    #(#build_output_arms),*
+                }
+            }
+
+            #[inline(always)]
+            fn has_error(&self) -> bool {
+                match self {
+                    #(#has_error_arms),*
+                }
+            }
+
+            #[inline(always)]
+            fn take_error(&mut self) -> Option<::xdevs::simulation::error::ModelError> {
+                match self {
+                    #(#take_error_arms),*
+                }
+            }
+
+            #[inline(always)]
+            fn take_isolated_error(&mut self) -> Option<::xdevs::simulation::error::ModelError> {
+                match self {
+                    #(#take_isolated_error_arms),*
+                }
+            }
         }
     };
 
     Ok(expanded)
 }
+
+/// Generates the implementation of `SharedComponents` for components with `#[shared]` fields,
+/// with the given bodies of `lambda`, `delta` and `delta_ext`.
+///
+/// Without the bags of a parent, `AbstractSimulator` runs the shared components with their own
+/// (temporary) bags. Coordinators always use `SharedComponents` instead.
+fn shared_impl(
+    generics: &syn::Generics,
+    item_ident: &Ident,
+    fields: &[Ident],
+    tys: &[syn::Type],
+    shared: &[(bool, bool)],
+    [lambda, delta, delta_ext]: [TokenStream2; 3],
+) -> (TokenStream2, TokenStream2, TokenStream2, TokenStream2) {
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    let mut shared_generics = generics.clone();
+
+    // Bags of the parent are generic unless some component shares them
+    let shared_input = fields.iter().zip(tys).zip(shared).find(|(_, s)| s.0);
+    let (input_ty, input_bag) = match shared_input {
+        Some(((field, ty), _)) => (
+            quote::quote!(<#ty as ::xdevs::Component>::Input),
+            quote::quote!(::xdevs::simulation::AbstractSimulator::build_input(&self.#field)),
+        ),
+        None => {
+            shared_generics.params.push(syn::parse_quote!(__I));
+            (quote::quote!(__I), quote::quote!(()))
+        }
+    };
+    let shared_output = fields.iter().zip(tys).zip(shared).find(|(_, s)| s.1);
+    let (output_ty, output_bag) = match shared_output {
+        Some(((field, ty), _)) => (
+            quote::quote!(<#ty as ::xdevs::Component>::Output),
+            quote::quote!(::xdevs::simulation::AbstractSimulator::build_output(&self.#field)),
+        ),
+        None => {
+            shared_generics.params.push(syn::parse_quote!(__O));
+            (quote::quote!(__O), quote::quote!(()))
+        }
+    };
+    let (impl_generics, _, _) = shared_generics.split_for_impl();
+
+    let shared_components = quote::quote!(::xdevs::simulation::coordinator::SharedComponents);
+    let fallback_input = shared_input.map_or(quote::quote!(()), |_| input_ty.clone());
+    let fallback_output = shared_output.map_or(quote::quote!(()), |_| output_ty.clone());
+    let fallback = quote::quote!(<Self as #shared_components<#fallback_input, #fallback_output>>);
+
+    let lambda_fn = quote::quote! {
+        let mut shared_output = #output_bag;
+        #fallback::lambda_shared(self, output, &mut shared_output, t);
+    };
+    let delta_fn = quote::quote! {
+        let (mut shared_input, mut shared_output) = (#input_bag, #output_bag);
+        #fallback::delta_shared(self, input, output, &mut shared_input, &mut shared_output, t)
+    };
+    let delta_ext_fn = quote::quote! {
+        let mut shared_input = #input_bag;
+        #fallback::delta_ext_shared(self, input, &mut shared_input, t)
+    };
+
+    let simulator = quote::quote!(::xdevs::simulation::AbstractSimulator);
+    let shared_impl = quote::quote! {
+        impl #impl_generics #shared_components<#input_ty, #output_ty> for #item_ident #ty_generics #where_clause {
+            #[inline(always)]
+            #[allow(unused_variables)]
+            fn lambda_shared(
+                &mut self,
+                output: &mut <Self as #simulator>::Output,
+                shared_output: &mut #output_ty,
+                t: f64,
+            ) {
+                #lambda
+            }
+
+            #[inline(always)]
+            #[allow(unused_variables)]
+            fn delta_shared(
+                &mut self,
+                input: &mut <Self as #simulator>::Input,
+                output: &mut <Self as #simulator>::Output,
+                shared_input: &mut #input_ty,
+                shared_output: &mut #output_ty,
+                t: f64,
+            ) -> f64 {
+                #delta
+            }
+
+            #[inline(always)]
+            #[allow(unused_variables)]
+            fn delta_ext_shared(
+                &mut self,
+                input: &mut <Self as #simulator>::Input,
+                shared_input: &mut #input_ty,
+                t: f64,
+            ) -> f64 {
+                #delta_ext
+            }
+        }
+    };
+
+    (lambda_fn, delta_fn, delta_ext_fn, shared_impl)
+}
+
+/// Generates the implementation of the `StaticFootprint` trait for a component group.
+/// The footprint of every field (or variant) is the footprint of its simulator.
+fn footprint_impl(
+    generics: &syn::Generics,
+    item_ident: &Ident,
+    idents: &[Ident],
+    tys: &[syn::Type],
+    exclusive: bool,
+) -> TokenStream2 {
+    let mut generics = generics.clone();
+    let where_clause = generics.make_where_clause();
+    for ty in tys {
+        where_clause.predicates.push(syn::parse_quote! {
+            <#ty as ::xdevs::simulation::SimpleSimulable>::Simulator: ::xdevs::footprint::StaticFootprint
+        });
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let names = idents.iter().map(|ident| ident.unraw().to_string());
+    let exclusive = exclusive.then(|| quote::quote!(.exclusive()));
+
+    quote::quote! {
+        impl #impl_generics ::xdevs::footprint::StaticFootprint for #item_ident #ty_generics #where_clause {
+            const FOOTPRINT: ::xdevs::footprint::Footprint = ::xdevs::footprint::Footprint::new::<Self>(&[
+                #(::xdevs::footprint::Field::new(#names, 1, ::xdevs::footprint::footprint::<#tys>()),)*
+            ])#exclusive;
+        }
+    }
+}
+
+/// Generates the implementation of the `Reset` trait for a component group.
+/// The simulators of all the fields (or variants) must implement `Reset`.
+fn reset_impl(generics: &syn::Generics, item_ident: &Ident, body: TokenStream2) -> TokenStream2 {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote::quote! {
+        impl #impl_generics ::xdevs::simulation::reset::Reset for #item_ident #ty_generics #where_clause {
+            #[inline(always)]
+            fn reset(&mut self) {
+                #body
+            }
+        }
+    }
+}
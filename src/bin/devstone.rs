@@ -0,0 +1,597 @@
+/// DEVStone benchmark runner.
+/// It simulates every DEVStone configuration that matches the given topologies, variants,
+/// widths and depths several times, and writes the results to the standard output as CSV or JSON.
+///
+/// The `ref` and `box` variants are sized at compile time, so only the widths and depths of the
+/// built-in grid (see [`GRID`]) are available. The `dyn` variant is sized at runtime, so it runs
+/// every width and depth of the given ranges (by default, those of the grid).
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    env,
+    ops::RangeInclusive,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+use xdevs::{
+    devstone::{
+        common::{Delays, Devstone, JobGenerator},
+        expected::{Counts, Topology},
+    },
+    footprint::{footprint, StaticFootprint},
+    simulation::{AbstractSimulator, Simulable},
+    Config,
+};
+
+extern crate alloc;
+
+/// Widths and depths of the `ref` and `box` variants.
+const GRID: [usize; 5] = [1, 2, 5, 10, 20];
+
+/// Stack size of the thread that runs the simulations, as ref models live in the stack.
+const STACK_SIZE: usize = 256 * 1024 * 1024;
+
+/// Global allocator that keeps track of the peak of heap memory in use.
+struct PeakAllocator;
+
+static HEAP_CURRENT: AtomicUsize = AtomicUsize::new(0);
+static HEAP_PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let current = HEAP_CURRENT.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            HEAP_PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        HEAP_CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// Resets the heap peak to the heap memory currently in use.
+fn reset_heap_peak() -> usize {
+    let current = HEAP_CURRENT.load(Ordering::Relaxed);
+    HEAP_PEAK.store(current, Ordering::Relaxed);
+    current
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Variant {
+    Ref,
+    Box,
+    Dyn,
+}
+
+impl Variant {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.to_ascii_lowercase().as_str() {
+            "ref" => Some(Self::Ref),
+            "box" => Some(Self::Box),
+            "dyn" => Some(Self::Dyn),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Ref => "ref",
+            Self::Box => "box",
+            Self::Dyn => "dyn",
+        }
+    }
+}
+
+fn parse_topology(raw: &str) -> Option<Topology> {
+    match raw.to_ascii_uppercase().as_str() {
+        "LI" => Some(Topology::LI),
+        "HI" => Some(Topology::HI),
+        "HO" => Some(Topology::HO),
+        "HOMOD" => Some(Topology::HOmod),
+        _ => None,
+    }
+}
+
+fn topology_name(topology: Topology) -> &'static str {
+    match topology {
+        Topology::LI => "LI",
+        Topology::HI => "HI",
+        Topology::HO => "HO",
+        Topology::HOmod => "HOmod",
+    }
+}
+
+/// Results of a single simulation run.
+struct Sample {
+    build: Duration,
+    simulate: Duration,
+    counts: Counts,
+    /// Bytes of the model outside the heap: its footprint (see [`xdevs::footprint::Footprint::total`])
+    /// plus, for `ref` models, the levels that it references in the stack.
+    model_bytes: usize,
+}
+
+/// DEVStone configuration to benchmark.
+struct Configuration {
+    topology: Topology,
+    variant: Variant,
+    width: usize,
+    depth: usize,
+    /// Builds and simulates the model. It receives the width, the depth and the delays,
+    /// although `ref` and `box` models ignore the width and depth, as they are fixed.
+    run: fn(usize, usize, Delays) -> Sample,
+}
+
+/// Simulates a model that has just been built, and measures its build and simulation times.
+/// `referenced` is the size of the levels of the model that are behind references.
+fn measure<T, K>(start: Instant, model: T, referenced: usize) -> Sample
+where
+    T: Simulable<K> + Devstone,
+    T: xdevs::simulation::SimpleSimulable<Simulator = <T as Simulable<K>>::Simulator>,
+    <T as Simulable<K>>::Simulator: core::ops::Deref<Target = T> + StaticFootprint,
+{
+    let mut simulator = Simulable::to_simulator(model);
+    let build = start.elapsed();
+    let start = Instant::now();
+    simulator.simulate_vt(&Config::new(0.0, 10.0, 1.0, None));
+    let simulate = start.elapsed();
+    Sample {
+        build,
+        simulate,
+        counts: Counts::of(&*simulator),
+        model_bytes: footprint::<T>().total() + referenced,
+    }
+}
+
+/// Returns a function that builds and simulates a DEVStone model.
+macro_rules! simulate {
+    (li, $w:literal, $d:literal) => {
+        |_, _, delays: Delays| {
+            let start = Instant::now();
+            xdevs::generate_li!($w, $d, delays.int_delay, delays.ext_delay);
+            // Every level of the model is a variable in the stack
+            let levels = $d * core::mem::size_of_val(&model_li);
+            let top_model = xdevs::devstone::li::TopModel::<{ $w - 1 }>::build(
+                JobGenerator::new(5),
+                &mut model_li,
+            );
+            measure(start, top_model, levels)
+        }
+    };
+    (hi, $w:literal, $d:literal) => {
+        |_, _, delays: Delays| {
+            let start = Instant::now();
+            xdevs::generate_hi!($w, $d, delays.int_delay, delays.ext_delay);
+            // Every level of the model is a variable in the stack
+            let levels = $d * core::mem::size_of_val(&model_hi);
+            let top_model = xdevs::devstone::hi::TopModel::<{ $w - 1 }>::build(
+                JobGenerator::new(5),
+                &mut model_hi,
+            );
+            measure(start, top_model, levels)
+        }
+    };
+    (ho, $w:literal, $d:literal) => {
+        |_, _, delays: Delays| {
+            let start = Instant::now();
+            xdevs::generate_ho!($w, $d, delays.int_delay, delays.ext_delay);
+            // Every level of the model is a variable in the stack
+            let levels = $d * core::mem::size_of_val(&model_ho);
+            let top_model =
+                xdevs::devstone::ho::TopModel::build(JobGenerator::new(5), &mut model_ho);
+            measure(start, top_model, levels)
+        }
+    };
+    (homod, $w:literal, $d:literal) => {
+        |_, _, delays: Delays| {
+            let start = Instant::now();
+            xdevs::generate_homod!($w, $d, delays.int_delay, delays.ext_delay);
+            // Every level of the model is a variable in the stack
+            let levels = $d * core::mem::size_of_val(&model_homod);
+            let top_model =
+                xdevs::devstone::homod::TopModel::build(JobGenerator::new(5), &mut model_homod);
+            measure(start, top_model, levels)
+        }
+    };
+    (li_box, $w:literal, $d:literal) => {
+        |_, _, delays: Delays| {
+            let start = Instant::now();
+            xdevs::generate_li_box!($w, $d, delays.int_delay, delays.ext_delay);
+            let top_model = xdevs::devstone::li_box::TopModel::<{ $w - 1 }>::build(
+                JobGenerator::new(5),
+                model_li,
+            );
+            measure(start, top_model, 0)
+        }
+    };
+    (hi_box, $w:literal, $d:literal) => {
+        |_, _, delays: Delays| {
+            let start = Instant::now();
+            xdevs::generate_hi_box!($w, $d, delays.int_delay, delays.ext_delay);
+            let top_model = xdevs::devstone::hi_box::TopModel::<{ $w - 1 }>::build(
+                JobGenerator::new(5),
+                model_hi,
+            );
+            measure(start, top_model, 0)
+        }
+    };
+    (ho_box, $w:literal, $d:literal) => {
+        |_, _, delays: Delays| {
+            let start = Instant::now();
+            xdevs::generate_ho_box!($w, $d, delays.int_delay, delays.ext_delay);
+            let top_model =
+                xdevs::devstone::ho_box::TopModel::build(JobGenerator::new(5), model_ho);
+            measure(start, top_model, 0)
+        }
+    };
+    (homod_box, $w:literal, $d:literal) => {
+        |_, _, delays: Delays| {
+            let start = Instant::now();
+            xdevs::generate_homod_box!($w, $d, delays.int_delay, delays.ext_delay);
+            let top_model =
+                xdevs::devstone::homod_box::TopModel::build(JobGenerator::new(5), model_homod);
+            measure(start, top_model, 0)
+        }
+    };
+    (homod_dyn) => {
+        |width, depth, delays: Delays| {
+            let start = Instant::now();
+            let model = xdevs::devstone::homod_dyn::build(width, depth, delays);
+            let top_model = xdevs::devstone::homod_dyn::TopModel::with_width(
+                JobGenerator::new(5),
+                width,
+                model,
+            );
+            measure(start, top_model, 0)
+        }
+    };
+    ($module:ident) => {
+        |width, depth, delays: Delays| {
+            let start = Instant::now();
+            let model = xdevs::devstone::$module::build(width, depth, delays);
+            let top_model = xdevs::devstone::$module::TopModel::build(JobGenerator::new(5), model);
+            measure(start, top_model, 0)
+        }
+    };
+}
+
+/// Adds the configurations of a topology and variant for every width and depth.
+macro_rules! grid {
+    ($configs:ident, $module:ident, $topology:ident, $variant:ident; [$($w:literal),*]; $depths:tt) => {
+        $(grid!(@width $configs, $module, $topology, $variant, $w; $depths);)*
+    };
+    (@width $configs:ident, $module:ident, $topology:ident, $variant:ident, $w:literal; [$($d:literal),*]) => {
+        $($configs.push(Configuration {
+            topology: Topology::$topology,
+            variant: Variant::$variant,
+            width: $w,
+            depth: $d,
+            run: simulate!($module, $w, $d),
+        });)*
+    };
+}
+
+/// Returns the configurations of the `ref` and `box` variants, sized at compile time.
+fn grid_configurations() -> Vec<Configuration> {
+    let mut configs = Vec::new();
+    grid!(configs, li, LI, Ref; [1, 2, 5, 10, 20]; [1, 2, 5, 10, 20]);
+    grid!(configs, hi, HI, Ref; [1, 2, 5, 10, 20]; [1, 2, 5, 10, 20]);
+    grid!(configs, ho, HO, Ref; [1, 2, 5, 10, 20]; [1, 2, 5, 10, 20]);
+    grid!(configs, homod, HOmod, Ref; [1, 2, 5, 10, 20]; [1, 2, 5, 10, 20]);
+    grid!(configs, li_box, LI, Box; [1, 2, 5, 10, 20]; [1, 2, 5, 10, 20]);
+    grid!(configs, hi_box, HI, Box; [1, 2, 5, 10, 20]; [1, 2, 5, 10, 20]);
+    grid!(configs, ho_box, HO, Box; [1, 2, 5, 10, 20]; [1, 2, 5, 10, 20]);
+    grid!(configs, homod_box, HOmod, Box; [1, 2, 5, 10, 20]; [1, 2, 5, 10, 20]);
+    configs
+}
+
+/// Returns the configurations that match the options.
+/// `dyn` configurations are built for every width and depth of the given ranges.
+fn configurations(options: &Options) -> Vec<Configuration> {
+    let mut configs: Vec<Configuration> = grid_configurations()
+        .into_iter()
+        .filter(|config| options.matches(config))
+        .collect();
+    if options.variants.contains(&Variant::Dyn) {
+        let values = |range: &Option<RangeInclusive<usize>>| match range {
+            Some(range) => range.clone().collect(),
+            None => GRID.to_vec(),
+        };
+        let (widths, depths): (Vec<usize>, Vec<usize>) =
+            (values(&options.widths), values(&options.depths));
+        for &topology in &options.topologies {
+            let run: fn(usize, usize, Delays) -> Sample = match topology {
+                Topology::LI => simulate!(li_dyn),
+                Topology::HI => simulate!(hi_dyn),
+                Topology::HO => simulate!(ho_dyn),
+                Topology::HOmod => simulate!(homod_dyn),
+            };
+            for &width in &widths {
+                for &depth in &depths {
+                    configs.push(Configuration {
+                        topology,
+                        variant: Variant::Dyn,
+                        width,
+                        depth,
+                        run,
+                    });
+                }
+            }
+        }
+    }
+    configs
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Json,
+}
+
+/// Options of the runner.
+struct Options {
+    topologies: Vec<Topology>,
+    variants: Vec<Variant>,
+    /// Range of widths (by default, the widths of the grid).
+    widths: Option<RangeInclusive<usize>>,
+    /// Range of depths (by default, the depths of the grid).
+    depths: Option<RangeInclusive<usize>>,
+    runs: usize,
+    delays: Delays,
+    format: Format,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            topologies: Topology::ALL.to_vec(),
+            variants: vec![Variant::Ref, Variant::Box, Variant::Dyn],
+            widths: None,
+            depths: None,
+            runs: 5,
+            delays: Delays::ZERO,
+            format: Format::Csv,
+        }
+    }
+}
+
+impl Options {
+    fn matches(&self, config: &Configuration) -> bool {
+        self.topologies.contains(&config.topology)
+            && self.variants.contains(&config.variant)
+            && self
+                .widths
+                .as_ref()
+                .is_none_or(|r| r.contains(&config.width))
+            && self
+                .depths
+                .as_ref()
+                .is_none_or(|r| r.contains(&config.depth))
+    }
+}
+
+fn main() {
+    if let Err(message) = run() {
+        eprintln!("{message}");
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", usage());
+        return Ok(());
+    }
+    let options = parse_options(&args)?;
+
+    let configs = configurations(&options);
+    if configs.is_empty() {
+        return Err(format!(
+            "No configuration matches the given options.\n\n{}",
+            usage()
+        ));
+    }
+
+    // Ref models live in the stack, so we run them in a thread with a large stack
+    std::thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || benchmark(&options, &configs))
+        .map_err(|err| format!("Failed to spawn benchmark thread: {err}"))?
+        .join()
+        .map_err(|_| "Benchmark thread panicked".to_string())
+}
+
+fn benchmark(options: &Options, configs: &[Configuration]) {
+    let mut rows = Vec::new();
+    if options.format == Format::Csv {
+        println!("{}", CSV_HEADER);
+    }
+    for config in configs {
+        for run in 0..options.runs {
+            let baseline = reset_heap_peak();
+            let sample = (config.run)(config.width, config.depth, options.delays);
+            let peak_heap = HEAP_PEAK.load(Ordering::Relaxed) - baseline;
+            // Check the counts against the analytical DEVStone counts
+            let verified = sample.counts == config.topology.expected(config.width, config.depth);
+            let row = Row {
+                config,
+                delays: options.delays,
+                run,
+                sample,
+                peak_heap,
+                verified,
+            };
+            match options.format {
+                Format::Csv => println!("{}", row.csv()),
+                Format::Json => rows.push(row.json()),
+            }
+        }
+    }
+    if options.format == Format::Json {
+        println!("[\n  {}\n]", rows.join(",\n  "));
+    }
+}
+
+const CSV_HEADER: &str = "topology,variant,width,depth,int_delay,ext_delay,run,build_ns,simulate_ns,n_atomics,n_internals,n_externals,n_events,model_bytes,peak_heap_bytes,verified";
+
+/// Results of a run, ready to be written.
+struct Row<'a> {
+    config: &'a Configuration,
+    delays: Delays,
+    run: usize,
+    sample: Sample,
+    peak_heap: usize,
+    verified: bool,
+}
+
+impl Row<'_> {
+    fn csv(&self) -> String {
+        let Counts {
+            n_atomics,
+            n_internals,
+            n_externals,
+            n_events,
+        } = self.sample.counts;
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            topology_name(self.config.topology),
+            self.config.variant.name(),
+            self.config.width,
+            self.config.depth,
+            self.delays.int_delay,
+            self.delays.ext_delay,
+            self.run,
+            self.sample.build.as_nanos(),
+            self.sample.simulate.as_nanos(),
+            n_atomics,
+            n_internals,
+            n_externals,
+            n_events,
+            self.sample.model_bytes,
+            self.peak_heap,
+            self.verified,
+        )
+    }
+
+    fn json(&self) -> String {
+        let Counts {
+            n_atomics,
+            n_internals,
+            n_externals,
+            n_events,
+        } = self.sample.counts;
+        format!(
+            "{{\"topology\": \"{}\", \"variant\": \"{}\", \"width\": {}, \"depth\": {}, \"int_delay\": {}, \"ext_delay\": {}, \"run\": {}, \"build_ns\": {}, \"simulate_ns\": {}, \"n_atomics\": {}, \"n_internals\": {}, \"n_externals\": {}, \"n_events\": {}, \"model_bytes\": {}, \"peak_heap_bytes\": {}, \"verified\": {}}}",
+            topology_name(self.config.topology),
+            self.config.variant.name(),
+            self.config.width,
+            self.config.depth,
+            self.delays.int_delay,
+            self.delays.ext_delay,
+            self.run,
+            self.sample.build.as_nanos(),
+            self.sample.simulate.as_nanos(),
+            n_atomics,
+            n_internals,
+            n_externals,
+            n_events,
+            self.sample.model_bytes,
+            self.peak_heap,
+            self.verified,
+        )
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for '{flag}'.\n\n{}", usage()))?;
+        match flag.as_str() {
+            "--topology" => {
+                options.topologies = parse_list(value, "topology", parse_topology)?;
+            }
+            "--variant" => options.variants = parse_list(value, "variant", Variant::parse)?,
+            "--width" => options.widths = Some(parse_range(value, "width")?),
+            "--depth" => options.depths = Some(parse_range(value, "depth")?),
+            "--runs" => options.runs = parse_usize(value, "runs")?,
+            "--int-delay" => options.delays.int_delay = parse_usize(value, "internal delay")?,
+            "--ext-delay" => options.delays.ext_delay = parse_usize(value, "external delay")?,
+            "--format" => {
+                options.format = match value.to_ascii_lowercase().as_str() {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    _ => return Err(format!("Invalid format '{value}'. Use csv or json.")),
+                }
+            }
+            _ => return Err(format!("Unknown option '{flag}'.\n\n{}", usage())),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_list<T>(raw: &str, label: &str, parse: fn(&str) -> Option<T>) -> Result<Vec<T>, String> {
+    raw.split(',')
+        .map(|item| parse(item.trim()).ok_or_else(|| format!("Invalid {label} '{item}'.")))
+        .collect()
+}
+
+fn parse_usize(raw: &str, label: &str) -> Result<usize, String> {
+    raw.parse()
+        .map_err(|_| format!("Invalid {label} '{raw}'. Expected a non-negative integer."))
+}
+
+/// Parses a single value (e.g., `5`) or an inclusive range (e.g., `2..10`).
+/// Values must be at least 1.
+fn parse_range(raw: &str, label: &str) -> Result<RangeInclusive<usize>, String> {
+    let range = match raw.split_once("..") {
+        Some((min, max)) => parse_usize(min, label)?..=parse_usize(max, label)?,
+        None => {
+            let value = parse_usize(raw, label)?;
+            value..=value
+        }
+    };
+    if range.is_empty() || *range.start() == 0 {
+        return Err(format!(
+            "Invalid {label} '{raw}'. Expected a non-empty range of values greater than zero."
+        ));
+    }
+    Ok(range)
+}
+
+fn usage() -> String {
+    let mut message = String::new();
+    message.push_str("Usage: devstone [OPTIONS]\n\n");
+    message.push_str("Options:\n");
+    message.push_str(
+        "  --topology <LIST>   LI, HI, HO and/or HOMOD, comma-separated (default: all)\n",
+    );
+    message.push_str("  --variant <LIST>    ref, box and/or dyn, comma-separated (default: all)\n");
+    message.push_str("  --width <RANGE>     width or inclusive range of widths, e.g. 5 or 2..10\n");
+    message.push_str("  --depth <RANGE>     depth or inclusive range of depths, e.g. 5 or 2..10\n");
+    message.push_str("  --runs <N>          simulations per configuration (default: 5)\n");
+    message.push_str(
+        "  --int-delay <N>     busy loop iterations of internal transitions (default: 0)\n",
+    );
+    message.push_str(
+        "  --ext-delay <N>     busy loop iterations of external transitions (default: 0)\n",
+    );
+    message.push_str("  --format <FORMAT>   csv or json (default: csv)\n\n");
+    message.push_str(&format!(
+        "Widths and depths of ref and box models: {GRID:?}\n"
+    ));
+    message.push_str("Dyn models accept any width and depth (by default, the same as above).\n");
+    message.push_str(
+        "Model bytes exclude the heap (ref models keep all their levels in the stack), so\nmodel bytes plus peak heap bytes are comparable across variants.\n",
+    );
+    message
+}
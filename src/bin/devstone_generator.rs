@@ -1,195 +0,0 @@
-/// A simple code generator for devstone examples.
-/// It takes the model type, width, and depth as arguments and
-/// generates a Rust file with the corresponding example code.
-use std::{env, fs, path::PathBuf};
-
-const OUTPUT_RELATIVE_PATH: &str = "src/bin/generated_devstone.rs";
-
-#[derive(Clone, Copy, Debug)]
-enum ModelType {
-    Li,
-    LiBox,
-    Hi,
-    HiBox,
-    Ho,
-    HoBox,
-}
-
-impl ModelType {
-    fn parse(raw: &str) -> Option<Self> {
-        match raw.to_ascii_uppercase().as_str() {
-            "LI" => Some(Self::Li),
-            "LI_BOX" => Some(Self::LiBox),
-            "HI" => Some(Self::Hi),
-            "HI_BOX" => Some(Self::HiBox),
-            "HO" => Some(Self::Ho),
-            "HO_BOX" => Some(Self::HoBox),
-            _ => None,
-        }
-    }
-
-    fn is_box(self) -> bool {
-        match self {
-            Self::LiBox | Self::HiBox | Self::HoBox => true,
-            Self::Li | Self::Hi | Self::Ho => false,
-        }
-    }
-
-    fn module(self) -> &'static str {
-        match self {
-            Self::Li => "li",
-            Self::LiBox => "li_box",
-            Self::Hi => "hi",
-            Self::HiBox => "hi_box",
-            Self::Ho => "ho",
-            Self::HoBox => "ho_box",
-        }
-    }
-
-    fn macro_name(self) -> &'static str {
-        match self {
-            Self::Li => "generate_li",
-            Self::LiBox => "generate_li_box",
-            Self::Hi => "generate_hi",
-            Self::HiBox => "generate_hi_box",
-            Self::Ho => "generate_ho",
-            Self::HoBox => "generate_ho_box",
-        }
-    }
-
-    fn model_var(self) -> &'static str {
-        match self {
-            Self::Li | Self::LiBox => "model_li",
-            Self::Hi | Self::HiBox => "model_hi",
-            Self::Ho | Self::HoBox => "model_ho",
-        }
-    }
-}
-
-fn main() {
-    if let Err(message) = run() {
-        eprintln!("{message}");
-        std::process::exit(1);
-    }
-}
-
-fn run() -> Result<(), String> {
-    let args: Vec<String> = env::args().skip(1).collect();
-
-    if args.len() == 1 && (args[0] == "-h" || args[0] == "--help") {
-        println!("{}", usage());
-        return Ok(());
-    }
-
-    if args.len() != 3 {
-        return Err(format!("Expected 3 arguments.\n\n{}", usage()));
-    }
-
-    let model = ModelType::parse(&args[0]).ok_or_else(|| {
-        format!(
-            "Invalid model type '{}'. Use LI, LI_BOX, HI, HI_BOX, HO, or HO_BOX.\n\n{}",
-            args[0],
-            usage()
-        )
-    })?;
-
-    let width = parse_positive_usize(&args[1], "width")?;
-    let depth = parse_positive_usize(&args[2], "depth")?;
-
-    let output_path = output_path();
-    if let Some(parent) = output_path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|err| format!("Failed to create output directory: {err}"))?;
-    }
-
-    let contents = render_example(model, width, depth);
-    fs::write(&output_path, contents)
-        .map_err(|err| format!("Failed to write example file: {err}"))?;
-
-    //println!("Wrote example to {}", output_path.display());
-    Ok(())
-}
-
-fn parse_positive_usize(raw: &str, label: &str) -> Result<usize, String> {
-    let value: usize = raw
-        .parse()
-        .map_err(|_| format!("Invalid {label} '{raw}'. Expected a positive integer."))?;
-    if value < 1 {
-        return Err(format!("{label} must be at least 1."));
-    }
-    Ok(value)
-}
-
-fn output_path() -> PathBuf {
-    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(OUTPUT_RELATIVE_PATH)
-}
-
-fn usage() -> String {
-    let mut message = String::new();
-    message.push_str("Usage: devstone_generator <MODEL_TYPE> <WIDTH> <DEPTH>\n\n");
-    message.push_str("MODEL_TYPE: LI | LI_BOX | HI | HI_BOX | HO | HO_BOX\n");
-    message.push_str("WIDTH and DEPTH must be positive integers.\n");
-    message
-}
-
-fn render_example(model: ModelType, width: usize, depth: usize) -> String {
-    let module = model.module();
-    let macro_name = model.macro_name();
-    let model_var = model.model_var();
-    let is_box = model.is_box();
-
-    let alloc_line = if is_box {
-        "\n    extern crate alloc;\n"
-    } else {
-        ""
-    };
-    let top_model_type = if is_box {
-        format!("xdevs::devstone::{module}::TopModel<W>")
-    } else {
-        format!("xdevs::devstone::{module}::TopModel<'_, W>")
-    };
-    let model_ref = if is_box {
-        model_var.to_string()
-    } else {
-        format!("&mut {model_var}")
-    };
-
-    format!(
-        r#"// Auto-generated by devstone_generator. Do not edit by hand.
-    use std::time::Instant;
-{alloc_line}
-    fn main() {{
-        use xdevs::{{AbstractSimulator, Simulable}};
-
-        const WIDTH: usize = {width};
-        const W: usize = WIDTH - 1;
-
-        let start = Instant::now();
-
-        xdevs::{macro_name}!({width}, {depth});
-
-        let generator = xdevs::devstone::common::JobGenerator::new(5);
-
-        let top_model: {top_model_type} = xdevs::devstone::{module}::TopModel::build(generator, {model_ref});
-        let duration = start.elapsed();
-        println!("Model creation time: {{:?}}", duration);
-        let start = Instant::now();
-        let mut simulator = top_model.to_simulator();
-        let config = xdevs::Config::new(0.0, 10.0, 1.0, None);
-        let duration = start.elapsed();
-        println!("Simulator creation time: {{:?}}", duration);
-        let start = Instant::now();
-        simulator.simulate_vt(&config);
-        let duration = start.elapsed();
-        println!("Simulation time: {{:?}}", duration);
-    }}
-    "#,
-        alloc_line = alloc_line,
-        module = module,
-        macro_name = macro_name,
-        width = width,
-        depth = depth,
-        top_model_type = top_model_type,
-        model_ref = model_ref,
-    )
-}
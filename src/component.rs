@@ -9,6 +9,11 @@ pub struct AtomicKind;
 
 impl Sealed for AtomicKind {}
 
+/// Marker type for fallible atomic DEVS models.
+pub struct TryAtomicKind;
+
+impl Sealed for TryAtomicKind {}
+
 /// Marker type for coupled DEVS models.
 pub struct CoupledKind;
 
@@ -25,7 +30,7 @@ impl Sealed for ComponentsKind {}
 
 /// Interface for DEVS components. All DEVS components must implement this trait.
 pub trait Component {
-    /// Kind of DEVS model. It can be [`AtomicKind`], [`CoupledKind`], or [`ComponentsKind`].
+    /// Kind of DEVS model. It can be [`AtomicKind`], [`TryAtomicKind`], [`CoupledKind`], or [`ComponentsKind`].
     type Kind: Sealed;
 
     /// Input event bag of the model.
@@ -53,6 +58,13 @@ impl<T: Component> Component for &mut T {
     type Kind = T::Kind;
 }
 
+#[cfg(feature = "alloc")]
+impl<T: Component> Component for alloc::vec::Vec<T> {
+    type Kind = alloc::vec::Vec<T::Kind>;
+    type Input = alloc::vec::Vec<T::Input>;
+    type Output = alloc::vec::Vec<T::Output>;
+}
+
 #[cfg(feature = "alloc")]
 impl<T: Component> Component for alloc::boxed::Box<T> {
     type Input = T::Input;
@@ -89,6 +101,8 @@ pub(crate) mod sealed {
 
     impl<T: Sealed, const N: usize> Sealed for [T; N] {}
     impl<T: Sealed> Sealed for Option<T> {}
+    #[cfg(feature = "alloc")]
+    impl<T: Sealed> Sealed for alloc::vec::Vec<T> {}
 
     macro_rules! impl_sealed_for_tuple {
         ($($T:ident),+) => {
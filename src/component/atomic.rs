@@ -1,4 +1,4 @@
-use crate::component::{AtomicKind, Component};
+use crate::component::{AtomicKind, Component, TryAtomicKind};
 
 /// Interface for DEVS atomic models. All DEVS atomic models must implement this trait.
 pub trait Atomic: Component<Kind = AtomicKind> {
@@ -109,6 +109,47 @@ impl<T: Atomic> Atomic for alloc::boxed::Box<T> {
     }
 }
 
+/// Interface for fallible DEVS atomic models.
+///
+/// It is equivalent to [`Atomic`], but transition and output functions may fail.
+/// When a function fails, the model becomes passive and the error is propagated to the parent
+/// model with the path of the model attached. Coupled models decide how to handle the error
+/// via [`Coupled::ERROR_STRATEGY`](crate::Coupled::ERROR_STRATEGY).
+pub trait TryAtomic: Component<Kind = TryAtomicKind> {
+    /// Error type of the model.
+    type Error: core::fmt::Debug;
+
+    /// Method for performing any operation before simulating. By default, it does nothing.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn start(&mut self) {}
+
+    /// Method for performing any operation after simulating. By default, it does nothing.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn stop(&mut self) {}
+
+    /// Fallible internal transition function.
+    fn try_delta_int(&mut self) -> Result<(), Self::Error>;
+
+    /// Fallible external transition function.
+    fn try_delta_ext(&mut self, elapsed: f64, input: &Self::Input) -> Result<(), Self::Error>;
+
+    /// Fallible confluent transition function.
+    /// By default, it calls [`TryAtomic::try_delta_int`] and [`TryAtomic::try_delta_ext`] with `elapsed = 0`, in that order.
+    #[inline(always)]
+    fn try_delta_conf(&mut self, input: &Self::Input) -> Result<(), Self::Error> {
+        Self::try_delta_int(self)?;
+        Self::try_delta_ext(self, 0., input)
+    }
+
+    /// Fallible output function.
+    fn try_lambda(&self, output: &mut Self::Output) -> Result<(), Self::Error>;
+
+    /// Time advance function. It returns the time until the next internal event happens.
+    fn ta(&self) -> f64;
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Atomic, AtomicKind, Component, Port};
@@ -309,4 +350,128 @@ mod tests {
         <alloc::boxed::Box<CallTracker> as Atomic>::stop(&mut raw);
         assert!(raw.stop, "stop delegates through Box<T>");
     }
+
+    crate::state_machine! {
+        /// Processor that drops jobs while busy.
+        struct Processor {
+            time: f64,
+            job: Option<usize> = None,
+        }
+
+        input: Port<usize, 1>;
+        output: Port<usize, 1>;
+        initial: Idle;
+
+        phases {
+            Idle => f64::INFINITY,
+            Busy => self.time,
+        }
+
+        internal {
+            Busy -> Idle {
+                output { output.add_value(self.job.unwrap()).unwrap(); }
+                update { self.job = None; }
+            }
+        }
+
+        external {
+            Idle on input(jobs) => {
+                self.job = jobs.last().copied();
+                Some(Busy)
+            }
+        }
+    }
+
+    #[derive(crate::Bag)]
+    struct GeneratorInput {
+        stop: Port<bool, 1>,
+        period: Port<f64, 1>,
+    }
+
+    crate::state_machine! {
+        struct Generator {
+            period: f64,
+            count: usize = 0,
+        }
+
+        input: GeneratorInput;
+        output: Port<usize, 1>;
+        initial: Active(0.0);
+
+        phases {
+            Active => self.period,
+            Passive => f64::INFINITY,
+        }
+
+        internal {
+            Active -> Active {
+                output { output.add_value(self.count).unwrap(); }
+                update { self.count += 1; }
+            }
+        }
+
+        external {
+            Active | Passive on period(periods) => {
+                self.period = periods[0];
+                None
+            }
+            _ on stop(stops) => stops.last().filter(|stop| **stop).map(|_| Passive),
+        }
+    }
+
+    #[test]
+    fn state_machine_sigma_bookkeeping() {
+        let mut model = Processor::new(2.0);
+        assert_eq!(model.phase(), ProcessorPhase::Idle);
+        assert_eq!(model.ta(), f64::INFINITY);
+
+        let mut input = Port::<usize, 1>::new();
+        input.add_value(5).unwrap();
+        model.delta_ext(1.0, &input);
+        assert_eq!(model.phase(), ProcessorPhase::Busy);
+        assert_eq!(model.ta(), 2.0, "entering a phase resets sigma");
+
+        input.clear();
+        input.add_value(6).unwrap();
+        model.delta_ext(0.5, &input);
+        assert_eq!(model.phase(), ProcessorPhase::Busy, "no rule for busy");
+        assert_eq!(model.ta(), 1.5, "sigma keeps the remaining time");
+
+        let mut output = Port::<usize, 1>::new();
+        model.lambda(&mut output);
+        assert_eq!(output.get_values(), &[5]);
+        model.delta_int();
+        assert_eq!(model.phase(), ProcessorPhase::Idle);
+        assert_eq!(model.job, None);
+        assert_eq!(model.ta(), f64::INFINITY);
+    }
+
+    #[test]
+    fn state_machine_ports_and_confluence() {
+        let mut model = Generator::new(1.0);
+        assert_eq!(model.phase(), GeneratorPhase::Active);
+        assert_eq!(model.ta(), 0.0, "initial sigma");
+
+        let mut output = Port::<usize, 1>::new();
+        model.lambda(&mut output);
+        assert_eq!(output.get_values(), &[0]);
+        model.delta_int();
+        assert_eq!((model.count, model.ta()), (1, 1.0));
+
+        let mut input = <GeneratorInput as crate::port::Bag>::build();
+        input.period.add_value(3.0).unwrap();
+        input.stop.add_value(false).unwrap();
+        model.delta_ext(0.25, &input);
+        assert_eq!(model.period, 3.0);
+        assert_eq!(model.phase(), GeneratorPhase::Active);
+        assert_eq!(model.ta(), 0.75, "new period applies after the next output");
+
+        // Stop arrives together with the internal transition
+        crate::port::Bag::clear(&mut input);
+        input.stop.add_value(true).unwrap();
+        model.delta_conf(&input);
+        assert_eq!(model.count, 2, "internal transition runs first");
+        assert_eq!(model.phase(), GeneratorPhase::Passive);
+        assert_eq!(model.sigma(), f64::INFINITY);
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
     component::{Component, CoupledKind},
-    simulation::AbstractSimulator,
+    simulation::{
+        error::{ErrorStrategy, ModelError},
+        AbstractSimulator,
+    },
 };
 
 /// Partial interface for DEVS coupled models. All DEVS coupled models must implement this trait.
@@ -12,6 +15,10 @@ pub trait PartialCoupled: Component<Kind = CoupledKind> {
             Output = <<Self as PartialCoupled>::Components as Component>::Output,
         >;
 
+    /// Bags that coordinators keep for the inner components of this coupled model.
+    /// See [`Buffers`](crate::simulation::coordinator::Buffers).
+    type Buffers;
+
     fn get_components(&self) -> &Components<Self>;
 
     fn get_components_mut(&mut self) -> &mut Components<Self>;
@@ -42,10 +49,20 @@ pub trait Coupled: PartialCoupled {
     #[allow(unused_variables)]
     #[inline(always)]
     fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {}
+
+    /// Strategy for handling errors of failed inner components. By default, errors abort the simulation.
+    const ERROR_STRATEGY: ErrorStrategy = ErrorStrategy::Abort;
+
+    /// Forwards the error of a failed inner component to the coupled model's output.
+    /// It is only called if [`Coupled::ERROR_STRATEGY`] is [`ErrorStrategy::Forward`].
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn forward_error(error: &ModelError, to: &mut Self::Output) {}
 }
 
 impl<T: PartialCoupled> PartialCoupled for &mut T {
     type Components = T::Components;
+    type Buffers = T::Buffers;
 
     fn get_components(&self) -> &Components<Self> {
         T::get_components(&**self)
@@ -69,11 +86,19 @@ impl<T: Coupled> Coupled for &mut T {
     fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
         T::eoc(from, to);
     }
+
+    const ERROR_STRATEGY: ErrorStrategy = T::ERROR_STRATEGY;
+
+    #[inline(always)]
+    fn forward_error(error: &ModelError, to: &mut Self::Output) {
+        T::forward_error(error, to);
+    }
 }
 
 #[cfg(feature = "alloc")]
 impl<T: PartialCoupled> PartialCoupled for alloc::boxed::Box<T> {
     type Components = T::Components;
+    type Buffers = T::Buffers;
 
     fn get_components(&self) -> &Components<Self> {
         T::get_components(&**self)
@@ -98,6 +123,13 @@ impl<T: Coupled> Coupled for alloc::boxed::Box<T> {
     fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
         T::eoc(from, to);
     }
+
+    const ERROR_STRATEGY: ErrorStrategy = T::ERROR_STRATEGY;
+
+    #[inline(always)]
+    fn forward_error(error: &ModelError, to: &mut Self::Output) {
+        T::forward_error(error, to);
+    }
 }
 
 #[cfg(test)]
@@ -1,12 +1,29 @@
 pub mod common;
+pub mod expected;
 
 pub mod hi;
+pub mod hi_shared;
 pub mod ho;
+pub mod ho_shared;
+pub mod homod;
 pub mod li;
+pub mod li_shared;
 
 #[cfg(feature = "alloc")]
 pub mod hi_box;
 #[cfg(feature = "alloc")]
+pub mod hi_dyn;
+#[cfg(feature = "alloc")]
 pub mod ho_box;
 #[cfg(feature = "alloc")]
+pub mod ho_box_shared;
+#[cfg(feature = "alloc")]
+pub mod ho_dyn;
+#[cfg(feature = "alloc")]
+pub mod homod_box;
+#[cfg(feature = "alloc")]
+pub mod homod_dyn;
+#[cfg(feature = "alloc")]
 pub mod li_box;
+#[cfg(feature = "alloc")]
+pub mod li_dyn;
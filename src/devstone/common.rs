@@ -1,4 +1,8 @@
+#[cfg(feature = "alloc")]
+use xdevs::simulation::simulator::Simulator;
+
 /// Simple atomic model that generates jobs and sends them to the input port of the model
+#[derive(Clone)]
 pub struct JobGenerator {
     sigma: f64,
     count: usize,
@@ -28,6 +32,12 @@ impl xdevs::Atomic for JobGenerator {
     }
 }
 
+impl xdevs::Reset for JobGenerator {
+    fn reset(&mut self) {
+        self.sigma = 0.0;
+    }
+}
+
 impl JobGenerator {
     pub fn new(val_count: usize) -> Self {
         Self {
@@ -37,22 +47,27 @@ impl JobGenerator {
     }
 }
 
-/// Simple atomic model
-pub struct AtomicModel {
+/// Simple atomic model.
+///
+/// `N` is the capacity of its input port. It defaults to 1, but topologies where several models
+/// send events to the same atomic (e.g., HOmod) need a larger capacity.
+pub struct AtomicModel<const N: usize = 1> {
     sigma: f64,
     n_internals: usize,
     n_externals: usize,
     n_events: usize,
+    delays: Delays,
 }
 
-impl xdevs::Component for AtomicModel {
+impl<const N: usize> xdevs::Component for AtomicModel<N> {
     type Kind = xdevs::AtomicKind;
-    type Input = xdevs::Port<usize, 1>;
+    type Input = xdevs::Port<usize, N>;
     type Output = xdevs::Port<usize, 1>;
 }
 
-impl xdevs::Atomic for AtomicModel {
+impl<const N: usize> xdevs::Atomic for AtomicModel<N> {
     fn delta_int(&mut self) {
+        busy_work(self.delays.int_delay);
         self.sigma = f64::INFINITY;
         self.n_internals += 1;
     }
@@ -66,29 +81,146 @@ impl xdevs::Atomic for AtomicModel {
     }
 
     fn delta_ext(&mut self, _elapsed: f64, input: &Self::Input) {
-        self.sigma = 0.0;
-        self.n_externals += 1;
-        self.n_events += input.get_values().len();
+        self.external(input.len());
     }
 }
 
-impl Default for AtomicModel {
+/// Resetting the model clears its counters, but keeps its delays.
+impl<const N: usize> xdevs::Reset for AtomicModel<N> {
+    fn reset(&mut self) {
+        *self = Self::with_delays(self.delays);
+    }
+}
+
+impl<const N: usize> Default for AtomicModel<N> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl AtomicModel {
+impl<const N: usize> AtomicModel<N> {
     pub fn new() -> Self {
+        Self::with_delays(Delays::ZERO)
+    }
+
+    /// Creates a new atomic model that performs synthetic CPU load in its transitions.
+    pub fn with_delays(delays: Delays) -> Self {
         Self {
             sigma: f64::INFINITY,
             n_internals: 0,
             n_externals: 0,
             n_events: 0,
+            delays,
+        }
+    }
+
+    /// External transition after receiving `n_events` events.
+    fn external(&mut self, n_events: usize) {
+        busy_work(self.delays.ext_delay);
+        self.sigma = 0.0;
+        self.n_externals += 1;
+        self.n_events += n_events;
+    }
+}
+
+/// Simple atomic model with an unbounded input port.
+///
+/// It behaves exactly as [`AtomicModel`], but it is meant for models built at runtime, where
+/// the number of models that send events to the same atomic is not known at compile time.
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+pub struct DynAtomicModel(AtomicModel);
+
+#[cfg(feature = "alloc")]
+impl xdevs::Component for DynAtomicModel {
+    type Kind = xdevs::AtomicKind;
+    type Input = xdevs::DynPort<usize>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+#[cfg(feature = "alloc")]
+impl xdevs::Atomic for DynAtomicModel {
+    fn delta_int(&mut self) {
+        xdevs::Atomic::delta_int(&mut self.0)
+    }
+
+    fn lambda(&self, output: &mut Self::Output) {
+        xdevs::Atomic::lambda(&self.0, output)
+    }
+
+    fn ta(&self) -> f64 {
+        xdevs::Atomic::ta(&self.0)
+    }
+
+    fn delta_ext(&mut self, _elapsed: f64, input: &Self::Input) {
+        self.0.external(input.len());
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl xdevs::Reset for DynAtomicModel {
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DynAtomicModel {
+    pub fn new() -> Self {
+        Self::with_delays(Delays::ZERO)
+    }
+
+    /// Creates a new atomic model that performs synthetic CPU load in its transitions.
+    pub fn with_delays(delays: Delays) -> Self {
+        Self(AtomicModel::with_delays(delays))
+    }
+}
+
+/// Synthetic CPU load of DEVStone atomic models, as in the internal and external delay
+/// parameters of the standard DEVStone benchmark.
+///
+/// Delays are given in iterations of a deterministic, Dhrystone-like busy loop instead of in
+/// wall-clock time, so they behave the same with and without `std` and do not depend on the
+/// clock resolution. By default, atomic models perform no work at all.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Delays {
+    /// Busy loop iterations performed in every internal transition.
+    pub int_delay: usize,
+    /// Busy loop iterations performed in every external transition.
+    pub ext_delay: usize,
+}
+
+impl Delays {
+    /// No synthetic CPU load.
+    pub const ZERO: Self = Self::new(0, 0);
+
+    pub const fn new(int_delay: usize, ext_delay: usize) -> Self {
+        Self {
+            int_delay,
+            ext_delay,
         }
     }
 }
 
+/// Deterministic busy loop with a mix of integer arithmetic, branches and array accesses.
+/// It returns a checksum that only depends on the number of iterations.
+pub fn busy_work(iterations: usize) -> u64 {
+    let mut regs = [0x2545_f491_u64, 0x9e37_79b9, 0x7f4a_7c15, 0x6a09_e667];
+    for i in 0..iterations {
+        let i = core::hint::black_box(i) as u64;
+        let idx = (i % 4) as usize;
+        regs[idx] = regs[idx]
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(i);
+        if regs[idx] & 1 == 0 {
+            regs[(idx + 1) % 4] ^= regs[idx] >> 7;
+        } else {
+            regs[(idx + 3) % 4] = regs[(idx + 3) % 4].rotate_left(13);
+        }
+    }
+    core::hint::black_box(regs.iter().fold(0, |acc, r| acc ^ r))
+}
+
 pub trait Devstone {
     fn get_n_internals(&self) -> usize;
     fn get_n_externals(&self) -> usize;
@@ -96,7 +228,7 @@ pub trait Devstone {
     fn get_n_atomics(&self) -> usize;
 }
 
-impl Devstone for AtomicModel {
+impl<const N: usize> Devstone for AtomicModel<N> {
     fn get_n_internals(&self) -> usize {
         self.n_internals
     }
@@ -114,8 +246,88 @@ impl Devstone for AtomicModel {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl Devstone for DynAtomicModel {
+    fn get_n_internals(&self) -> usize {
+        self.0.get_n_internals()
+    }
+
+    fn get_n_externals(&self) -> usize {
+        self.0.get_n_externals()
+    }
+
+    fn get_n_events(&self) -> usize {
+        self.0.get_n_events()
+    }
+
+    fn get_n_atomics(&self) -> usize {
+        self.0.get_n_atomics()
+    }
+}
+
+/// Flat models report the counts of all their atomic models.
+#[cfg(feature = "alloc")]
+impl<S, const I: usize, const O: usize, In, Out> Devstone
+    for xdevs::simulation::flat::Flat<S, I, O, In, Out>
+where
+    S: xdevs::AbstractSimulator + core::ops::Deref,
+    S::Target: Devstone,
+{
+    fn get_n_internals(&self) -> usize {
+        self.atomics().iter().map(|a| a.get_n_internals()).sum()
+    }
+
+    fn get_n_externals(&self) -> usize {
+        self.atomics().iter().map(|a| a.get_n_externals()).sum()
+    }
+
+    fn get_n_events(&self) -> usize {
+        self.atomics().iter().map(|a| a.get_n_events()).sum()
+    }
+
+    fn get_n_atomics(&self) -> usize {
+        self.atomics().iter().map(|a| a.get_n_atomics()).sum()
+    }
+}
+
+/// Output events of a DEVStone model at every step of a simulation.
+/// Every step keeps its super-dense time and the values of every output port.
+#[cfg(all(test, feature = "alloc"))]
+pub(crate) type Trace = alloc::vec::Vec<(
+    xdevs::simulation::SuperDenseTime,
+    alloc::vec::Vec<alloc::vec::Vec<usize>>,
+)>;
+
+/// Simulates a DEVStone model that receives one job at `t = 0, 1, ..., n_jobs - 1`.
+/// `inject` sends a job to the input ports of the model, and `events` returns the values
+/// of its output ports, which are recorded whenever the model is imminent.
+#[cfg(all(test, feature = "alloc"))]
+pub(crate) fn trace<S: xdevs::AbstractSimulator>(
+    model: &mut S,
+    n_jobs: usize,
+    inject: impl Fn(&mut S::Input, usize),
+    events: impl Fn(&S::Output) -> alloc::vec::Vec<alloc::vec::Vec<usize>>,
+) -> Trace {
+    let mut trace = Trace::new();
+    let mut job = 0;
+    model.simulate_rt_dense(
+        &xdevs::Config::new(0.0, n_jobs as f64 + 10.0, 1.0, None),
+        |_, t_until, input| {
+            let t_job = job as f64;
+            if job < n_jobs && t_job <= t_until {
+                inject(input, job);
+                job += 1;
+                return t_job;
+            }
+            t_until
+        },
+        |t, output| trace.push((t, events(output))),
+    );
+    trace
+}
+
 /// Leaf coupled model with only one atomic in LI models and HI leaf model
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct LeafModel {
     atomic: AtomicModel,
 }
@@ -128,7 +340,11 @@ impl xdevs::Component for LeafModel {
 
 impl LeafModel {
     pub fn new() -> Self {
-        Self::build(AtomicModel::new())
+        Self::with_delays(Delays::ZERO)
+    }
+
+    pub fn with_delays(delays: Delays) -> Self {
+        Self::build(AtomicModel::with_delays(delays))
     }
 }
 
@@ -165,6 +381,51 @@ impl xdevs::Coupled for LeafModel {
     }
 }
 
+/// Leaf coupled model of the shared variants of LI and HI models
+///
+/// The atomic model shares the input and output bags of the leaf model.
+#[xdevs::coupled(shared, reset)]
+pub struct SharedLeafModel {
+    atomic: AtomicModel,
+}
+
+impl xdevs::Component for SharedLeafModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl SharedLeafModel {
+    pub fn new() -> Self {
+        Self::with_delays(Delays::ZERO)
+    }
+
+    pub fn with_delays(delays: Delays) -> Self {
+        Self::build(AtomicModel::with_delays(delays))
+    }
+}
+
+impl Devstone for SharedLeafModel {
+    crate::impl_devstone_leaf!();
+}
+
+impl Default for SharedLeafModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The leaf model couples its ports to the ports of its atomic model.
+#[cfg(feature = "alloc")]
+impl xdevs::simulation::flat::Flatten<Simulator<AtomicModel>> for LeafModel {
+    fn flatten(
+        self,
+        flat: &mut xdevs::simulation::flat::FlatBuilder<Simulator<AtomicModel>>,
+    ) -> xdevs::simulation::flat::FlatPorts {
+        xdevs::simulation::flat::Flatten::flatten(self.components.atomic, flat)
+    }
+}
+
 #[macro_export]
 macro_rules! impl_devstone_leaf {
     () => {
@@ -213,6 +474,22 @@ macro_rules! impl_devstone_enum {
     };
 }
 
+// Flattens the variant of DEVStone enums
+#[macro_export]
+macro_rules! impl_flatten_enum {
+    ($atomic:ty) => {
+        fn flatten(
+            self,
+            flat: &mut xdevs::simulation::flat::FlatBuilder<$atomic>,
+        ) -> xdevs::simulation::flat::FlatPorts {
+            match self {
+                Self::Leaf(leaf) => xdevs::simulation::flat::Flatten::flatten(leaf, flat),
+                Self::Branch(branch) => xdevs::simulation::flat::Flatten::flatten(branch, flat),
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! impl_devstone_coupled {
     () => {
@@ -295,6 +572,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn busy_work_is_deterministic() {
+        assert_eq!(busy_work(1000), busy_work(1000));
+        assert_ne!(busy_work(1000), busy_work(1001));
+    }
+
+    #[test]
+    fn delays_do_not_change_behavior() {
+        let mut atomic: AtomicModel = AtomicModel::with_delays(Delays::new(100, 200));
+        let mut input = <AtomicModel as xdevs::Component>::Input::default();
+        input.add_value(3).unwrap();
+        atomic.delta_ext(0.0, &input);
+        assert_eq!(atomic.ta(), 0.0);
+        atomic.delta_int();
+        assert_eq!(atomic.ta(), f64::INFINITY);
+        assert_eq!((atomic.get_n_internals(), atomic.get_n_externals()), (1, 1));
+        assert_eq!(atomic.get_n_events(), 1);
+    }
+
     #[test]
     fn leaf_model_contains_single_atomic() {
         // Verify that the LeafModel contains exactly one atomic model
@@ -0,0 +1,453 @@
+//! Theoretical DEVStone counts, used to verify the results of simulating DEVStone models.
+//!
+//! Counts refer to a simulation in which the [`super::common::JobGenerator`] emits one job
+//! (as the `TopModel` of every topology does), no matter the simulation interval.
+
+use super::common::Devstone;
+
+/// DEVStone topologies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    LI,
+    HI,
+    HO,
+    HOmod,
+}
+
+impl Topology {
+    /// All the DEVStone topologies.
+    pub const ALL: [Self; 4] = [Self::LI, Self::HI, Self::HO, Self::HOmod];
+
+    /// Returns the expected number of atomic models.
+    pub const fn n_atomics(self, width: usize, depth: usize) -> usize {
+        let (w, d) = (width - 1, depth - 1);
+        match self {
+            Self::LI | Self::HI | Self::HO => w * d + 1,
+            Self::HOmod => (w + w * (w + 1) / 2) * d + 1,
+        }
+    }
+
+    /// Returns the expected number of internal transitions.
+    /// In DEVStone, it always matches the number of external transitions.
+    pub const fn n_internals(self, width: usize, depth: usize) -> usize {
+        let (w, d) = (width - 1, depth - 1);
+        match self {
+            Self::LI => w * d + 1,
+            Self::HI | Self::HO => w * (w + 1) / 2 * d + 1,
+            Self::HOmod => match (w, d) {
+                (_, 0) | (0, _) => 1,
+                _ => w * d * (w * w * (d - 1) + 3 * w * (d + 1) + 10) / 4 + 2,
+            },
+        }
+    }
+
+    /// Returns the expected number of external transitions.
+    pub const fn n_externals(self, width: usize, depth: usize) -> usize {
+        self.n_internals(width, depth)
+    }
+
+    /// Returns the expected number of events received by atomic models.
+    pub const fn n_events(self, width: usize, depth: usize) -> usize {
+        let (w, d) = (width - 1, depth - 1);
+        match self {
+            Self::LI => w * d + 1,
+            Self::HI | Self::HO => w * (w + 1) / 2 * d + 1,
+            Self::HOmod => match (w, d) {
+                (0, _) => 1,
+                (_, 0) => 2,
+                _ => w * d * ((d - 1) * w * (7 * w - 1) + 18 * w - 2) / 4 + 3 * w + 1 - 2 * w * w,
+            },
+        }
+    }
+
+    /// Returns all the expected counts.
+    pub const fn expected(self, width: usize, depth: usize) -> Counts {
+        Counts {
+            n_atomics: self.n_atomics(width, depth),
+            n_internals: self.n_internals(width, depth),
+            n_externals: self.n_externals(width, depth),
+            n_events: self.n_events(width, depth),
+        }
+    }
+}
+
+/// Counts reported by a DEVStone model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Counts {
+    pub n_atomics: usize,
+    pub n_internals: usize,
+    pub n_externals: usize,
+    pub n_events: usize,
+}
+
+impl Counts {
+    /// Returns the counts reported by a DEVStone model.
+    pub fn of<D: Devstone + ?Sized>(model: &D) -> Self {
+        Self {
+            n_atomics: model.get_n_atomics(),
+            n_internals: model.get_n_internals(),
+            n_externals: model.get_n_externals(),
+            n_events: model.get_n_events(),
+        }
+    }
+}
+
+/// Error returned by [`verify`] when the counts of a model do not match the expected ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mismatch {
+    pub topology: Topology,
+    pub width: usize,
+    pub depth: usize,
+    pub expected: Counts,
+    pub actual: Counts,
+}
+
+impl core::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:?} (width {}, depth {}): expected {:?}, got {:?}",
+            self.topology, self.width, self.depth, self.expected, self.actual
+        )
+    }
+}
+
+/// Checks the counts of a simulated DEVStone model against the expected ones.
+/// On success, it returns the counts of the model.
+pub fn verify<D: Devstone + ?Sized>(
+    model: &D,
+    topology: Topology,
+    width: usize,
+    depth: usize,
+) -> Result<Counts, Mismatch> {
+    let expected = topology.expected(width, depth);
+    let actual = Counts::of(model);
+    match expected == actual {
+        true => Ok(actual),
+        false => Err(Mismatch {
+            topology,
+            width,
+            depth,
+            expected,
+            actual,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::devstone::common::JobGenerator;
+    use crate::simulation::{AbstractSimulator, Config, Simulable};
+
+    /// Generates and simulates a DEVStone model, and verifies its counts.
+    macro_rules! simulate {
+        (li, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_li!($w, $d);
+            verify_model(
+                crate::devstone::li::TopModel::<{ $w - 1 }>::build(
+                    JobGenerator::new(5),
+                    &mut model_li,
+                ),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (hi, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_hi!($w, $d);
+            verify_model(
+                crate::devstone::hi::TopModel::<{ $w - 1 }>::build(
+                    JobGenerator::new(5),
+                    &mut model_hi,
+                ),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (ho, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_ho!($w, $d);
+            verify_model(
+                crate::devstone::ho::TopModel::build(JobGenerator::new(5), &mut model_ho),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (li_shared, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_li_shared!($w, $d);
+            verify_model(
+                crate::devstone::li_shared::TopModel::<{ $w - 1 }>::build(
+                    JobGenerator::new(5),
+                    &mut model_li,
+                ),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (hi_shared, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_hi_shared!($w, $d);
+            verify_model(
+                crate::devstone::hi_shared::TopModel::<{ $w - 1 }>::build(
+                    JobGenerator::new(5),
+                    &mut model_hi,
+                ),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (ho_shared, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_ho_shared!($w, $d);
+            verify_model(
+                crate::devstone::ho_shared::TopModel::build(JobGenerator::new(5), &mut model_ho),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (homod, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_homod!($w, $d);
+            verify_model(
+                crate::devstone::homod::TopModel::build(JobGenerator::new(5), &mut model_homod),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (li_box, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_li_box!($w, $d);
+            verify_model(
+                crate::devstone::li_box::TopModel::<{ $w - 1 }>::build(
+                    JobGenerator::new(5),
+                    model_li,
+                ),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (hi_box, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_hi_box!($w, $d);
+            verify_model(
+                crate::devstone::hi_box::TopModel::<{ $w - 1 }>::build(
+                    JobGenerator::new(5),
+                    model_hi,
+                ),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (ho_box, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_ho_box!($w, $d);
+            verify_model(
+                crate::devstone::ho_box::TopModel::build(JobGenerator::new(5), model_ho),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (ho_box_shared, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_ho_box_shared!($w, $d);
+            verify_model(
+                crate::devstone::ho_box_shared::TopModel::build(JobGenerator::new(5), model_ho),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+        (homod_box, $topology:ident, $w:literal, $d:literal) => {{
+            crate::generate_homod_box!($w, $d);
+            verify_model(
+                crate::devstone::homod_box::TopModel::build(JobGenerator::new(5), model_homod),
+                Topology::$topology,
+                $w,
+                $d,
+            )
+        }};
+    }
+
+    /// Verifies the counts of a DEVStone model for every width and depth.
+    macro_rules! verify_matrix {
+        ($module:ident, $topology:ident; $($w:literal => [$($d:literal),*]),* $(,)?) => {
+            $($(
+                if let Err(mismatch) = simulate!($module, $topology, $w, $d) {
+                    panic!("{}", mismatch);
+                }
+            )*)*
+        };
+    }
+
+    /// Checks that the shared variant of a DEVStone model gives the same results as the reference one.
+    macro_rules! verify_shared_matrix {
+        ($reference:ident, $shared:ident, $topology:ident; $($w:literal => [$($d:literal),*]),* $(,)?) => {
+            $($(
+                assert_eq!(
+                    simulate!($reference, $topology, $w, $d),
+                    simulate!($shared, $topology, $w, $d),
+                    "{} (width {}, depth {})",
+                    stringify!($shared),
+                    $w,
+                    $d
+                );
+            )*)*
+        };
+    }
+
+    fn verify_model<T, K>(
+        model: T,
+        topology: Topology,
+        width: usize,
+        depth: usize,
+    ) -> Result<Counts, Mismatch>
+    where
+        T: Simulable<K> + Devstone,
+        T::Simulator: core::ops::Deref<Target = T>,
+    {
+        let mut simulator = model.to_simulator();
+        simulator.simulate_vt(&Config::new(0.0, 10.0, 1.0, None));
+        verify(&*simulator, topology, width, depth)
+    }
+
+    #[test]
+    fn verify_reports_mismatches() {
+        let model = crate::devstone::common::LeafModel::new();
+        assert_eq!(
+            verify(&model, Topology::LI, 1, 1),
+            Err(Mismatch {
+                topology: Topology::LI,
+                width: 1,
+                depth: 1,
+                expected: Topology::LI.expected(1, 1),
+                actual: Counts {
+                    n_atomics: 1,
+                    n_internals: 0,
+                    n_externals: 0,
+                    n_events: 0,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn li_matches_expected() {
+        verify_matrix!(li, LI;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[test]
+    fn hi_matches_expected() {
+        verify_matrix!(hi, HI;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[test]
+    fn ho_matches_expected() {
+        verify_matrix!(ho, HO;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[test]
+    fn homod_matches_expected() {
+        verify_matrix!(homod, HOmod;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 5]);
+    }
+
+    #[test]
+    fn li_shared_matches_reference() {
+        verify_shared_matrix!(li, li_shared, LI;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[test]
+    fn hi_shared_matches_reference() {
+        verify_shared_matrix!(hi, hi_shared, HI;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[test]
+    fn ho_shared_matches_reference() {
+        verify_shared_matrix!(ho, ho_shared, HO;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn ho_box_shared_matches_reference() {
+        verify_shared_matrix!(ho_box, ho_box_shared, HO;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn li_box_matches_expected() {
+        verify_matrix!(li_box, LI;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn hi_box_matches_expected() {
+        verify_matrix!(hi_box, HI;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn ho_box_matches_expected() {
+        verify_matrix!(ho_box, HO;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 8]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn homod_box_matches_expected() {
+        verify_matrix!(homod_box, HOmod;
+            1 => [1, 2, 5], 2 => [1, 2, 5], 3 => [1, 3, 4], 5 => [1, 2, 6], 8 => [3, 5]);
+    }
+
+    /// Couples a generator to a runtime-sized DEVStone model.
+    #[cfg(feature = "alloc")]
+    macro_rules! dyn_top {
+        (homod_dyn, $model:expr, $width:expr) => {
+            crate::devstone::homod_dyn::TopModel::with_width(JobGenerator::new(5), $width, $model)
+        };
+        ($module:ident, $model:expr, $width:expr) => {
+            crate::devstone::$module::TopModel::build(JobGenerator::new(5), $model)
+        };
+    }
+
+    /// Simulates a runtime-sized DEVStone model for every width and depth, and verifies its counts.
+    #[cfg(feature = "alloc")]
+    macro_rules! verify_dyn_matrix {
+        ($module:ident, $topology:ident, $widths:expr, $depths:expr) => {
+            for width in $widths {
+                for depth in $depths {
+                    let model = crate::devstone::$module::build(
+                        width,
+                        depth,
+                        crate::devstone::common::Delays::ZERO,
+                    );
+                    let top_model = dyn_top!($module, model, width);
+                    if let Err(mismatch) =
+                        verify_model(top_model, Topology::$topology, width, depth)
+                    {
+                        panic!("{}", mismatch);
+                    }
+                }
+            }
+        };
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn dyn_models_match_expected() {
+        verify_dyn_matrix!(li_dyn, LI, 1..=12, 1..=12);
+        verify_dyn_matrix!(hi_dyn, HI, 1..=12, 1..=12);
+        verify_dyn_matrix!(ho_dyn, HO, 1..=12, 1..=12);
+        verify_dyn_matrix!(homod_dyn, HOmod, 1..=8, 1..=6);
+    }
+}
@@ -1,8 +1,8 @@
-use super::common::{AtomicModel, Devstone, JobGenerator, LeafModel};
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator, LeafModel};
 use crate::Component;
 
 /// HI model enum (ref version)
-#[crate::to_component]
+#[crate::to_component(reset)]
 pub enum HIEnum<'a, const W: usize> {
     Leaf(LeafModel),
     Branch(HIModel<'a, W>),
@@ -13,7 +13,7 @@ impl<'a, const W: usize> Devstone for HIEnum<'a, W> {
 }
 
 /// HI coupled model (ref version)
-#[crate::coupled]
+#[crate::coupled(reset)]
 pub struct HIModel<'a, const W: usize> {
     atomics: [AtomicModel; W],
     inner: &'a mut HIEnum<'a, W>,
@@ -47,7 +47,14 @@ impl<'a, const W: usize> crate::Coupled for HIModel<'a, W> {
 
 impl<'a, const W: usize> HIModel<'a, W> {
     pub fn new(inner: &'a mut HIEnum<'a, W>) -> Self {
-        Self::build(core::array::from_fn(|_| AtomicModel::default()), inner)
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: &'a mut HIEnum<'a, W>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
     }
 }
 
@@ -56,7 +63,7 @@ impl<'a, const W: usize> Devstone for HIModel<'a, W> {
 }
 
 /// End model with Generator and HI model coupled together (ref version)
-#[crate::coupled]
+#[crate::coupled(reset)]
 pub struct TopModel<'a, const W: usize> {
     generator: JobGenerator,
     hi_model: &'a mut HIEnum<'a, W>,
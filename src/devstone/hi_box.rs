@@ -1,9 +1,9 @@
-use super::common::{AtomicModel, Devstone, JobGenerator, LeafModel};
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator, LeafModel};
 use crate::Component;
 use alloc::boxed::Box;
 
 /// HI model enum
-#[xdevs::to_component]
+#[xdevs::to_component(reset)]
 pub enum HIEnum<const W: usize> {
     Leaf(LeafModel),
     Branch(HIModel<W>),
@@ -14,9 +14,10 @@ impl<const W: usize> Devstone for HIEnum<W> {
 }
 
 /// HI coupled model
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct HIModel<const W: usize> {
     atomics: [AtomicModel; W],
+    #[shared]
     inner: Box<HIEnum<W>>,
 }
 
@@ -31,12 +32,6 @@ impl<const W: usize> xdevs::Coupled for HIModel<W> {
         for atom_ports in to.atomics.iter_mut() {
             let _ = from.couple(atom_ports);
         }
-
-        let _ = from.couple(&mut to.inner);
-    }
-
-    fn eoc(from: &xdevs::ComponentsOutput<Self>, to: &mut Self::Output) {
-        let _ = from.inner.couple(to);
     }
 
     fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
@@ -48,7 +43,14 @@ impl<const W: usize> xdevs::Coupled for HIModel<W> {
 
 impl<const W: usize> HIModel<W> {
     pub fn new(inner: Box<HIEnum<W>>) -> Self {
-        Self::build(core::array::from_fn(|_| AtomicModel::default()), inner)
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: Box<HIEnum<W>>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
     }
 }
 
@@ -57,7 +59,7 @@ impl<const W: usize> Devstone for HIModel<W> {
 }
 
 /// End model with Generator and HI model coupled together
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct TopModel<const W: usize> {
     generator: JobGenerator,
     hi_model: HIEnum<W>,
@@ -0,0 +1,225 @@
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator, LeafModel};
+use alloc::{boxed::Box, vec::Vec};
+use xdevs::{
+    simulation::{
+        flat::{Flat, FlatBuilder, FlatPorts, Flatten},
+        simulator::Simulator,
+    },
+    Component, Simulable,
+};
+
+/// HI model enum
+#[xdevs::to_component(reset)]
+pub enum HIEnum {
+    Leaf(LeafModel),
+    Branch(HIModel),
+}
+
+impl Devstone for HIEnum {
+    crate::impl_devstone_enum!();
+}
+
+impl Flatten<Simulator<AtomicModel>> for HIEnum {
+    crate::impl_flatten_enum!(Simulator<AtomicModel>);
+}
+
+/// HI coupled model with a width only known at runtime
+#[xdevs::coupled(reset)]
+pub struct HIModel {
+    atomics: Vec<AtomicModel>,
+    #[shared]
+    inner: Box<HIEnum>,
+}
+
+impl Component for HIModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl xdevs::Coupled for HIModel {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        for atom_ports in to.atomics.iter_mut() {
+            let _ = from.couple(atom_ports);
+        }
+    }
+
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        for (atom_output_ports, atom_ports) in
+            from.atomics.iter().zip(to.atomics.iter_mut().skip(1))
+        {
+            let _ = atom_output_ports.couple(atom_ports);
+        }
+    }
+}
+
+impl HIModel {
+    /// Creates a new HI coupled model with `width - 1` atomic models.
+    pub fn new(width: usize, inner: Box<HIEnum>) -> Self {
+        Self::with_delays(width, inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(width: usize, inner: Box<HIEnum>, delays: Delays) -> Self {
+        Self::build(
+            (1..width)
+                .map(|_| AtomicModel::with_delays(delays))
+                .collect(),
+            inner,
+        )
+    }
+}
+
+impl Devstone for HIModel {
+    crate::impl_devstone_coupled!();
+}
+
+impl Flatten<Simulator<AtomicModel>> for HIModel {
+    fn flatten(self, flat: &mut FlatBuilder<Simulator<AtomicModel>>) -> FlatPorts {
+        let atomics: Vec<_> = (self.components.atomics.into_iter())
+            .map(|atomic| atomic.flatten(flat))
+            .collect();
+        let inner = self.components.inner.flatten(flat);
+
+        let mut ports = FlatPorts::new(1, 1);
+        for atomic in atomics.iter() {
+            ports.eic(0, atomic, 0);
+        }
+        ports.eic(0, &inner, 0);
+        for (from, to) in atomics.iter().zip(atomics.iter().skip(1)) {
+            flat.ic(from, 0, to, 0);
+        }
+        ports.eoc(&inner, 0, 0);
+        ports
+    }
+}
+
+/// Builds an HI model of the given width and depth.
+/// It is the runtime counterpart of [`generate_hi_box`](crate::generate_hi_box).
+///
+/// # Panics
+///
+/// Panics if `width` or `depth` are zero.
+pub fn build(width: usize, depth: usize, delays: Delays) -> HIEnum {
+    assert!(width >= 1, "width must be at least 1");
+    assert!(depth >= 1, "depth must be at least 1");
+
+    let mut model = HIEnum::Leaf(LeafModel::with_delays(delays).to_simulator());
+    for _ in 1..depth {
+        let branch = HIModel::with_delays(width, Box::new(model), delays);
+        model = HIEnum::Branch(branch.to_simulator());
+    }
+    model
+}
+
+/// End model with Generator and HI model coupled together
+#[xdevs::coupled(reset)]
+pub struct TopModel {
+    generator: JobGenerator,
+    hi_model: HIEnum,
+}
+
+impl Component for TopModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl Devstone for TopModel {
+    crate::impl_devstone_top!(hi_model);
+}
+
+impl xdevs::Coupled for TopModel {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.hi_model);
+    }
+}
+
+/// End model with Generator and a flat HI model coupled together
+#[xdevs::coupled(reset)]
+pub struct FlatTopModel {
+    generator: JobGenerator,
+    hi_model: Flat<Simulator<AtomicModel>>,
+}
+
+impl FlatTopModel {
+    /// Flattens the given HI model and couples a generator to it.
+    pub fn flatten(generator: JobGenerator, hi_model: HIEnum) -> Self {
+        Self::build(generator, Flat::new(hi_model))
+    }
+}
+
+impl Component for FlatTopModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl Devstone for FlatTopModel {
+    crate::impl_devstone_top!(hi_model);
+}
+
+impl xdevs::Coupled for FlatTopModel {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.hi_model[0]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::devstone::{
+        common::trace,
+        expected::{verify, Topology},
+    };
+    use alloc::vec;
+
+    #[test]
+    fn simulation_matches_expected_counts() {
+        use xdevs::AbstractSimulator;
+
+        let (width, depth) = (10, 10);
+        let top_model = TopModel::build(JobGenerator::new(5), build(width, depth, Delays::ZERO));
+        let mut simulator = top_model.to_simulator();
+        simulator.simulate_vt(&xdevs::Config::new(0.0, 10.0, 1.0, None));
+
+        assert_eq!(
+            verify(&*simulator, Topology::HI, width, depth),
+            Ok(Topology::HI.expected(width, depth))
+        );
+    }
+
+    #[test]
+    fn flat_simulation_matches_hierarchical_trace() {
+        let (width, depth) = (10, 10);
+        let mut hierarchical = build(width, depth, Delays::ZERO);
+        let mut flat: Flat<Simulator<AtomicModel>> = Flat::new(build(width, depth, Delays::ZERO));
+
+        let expected = trace(
+            &mut hierarchical,
+            3,
+            |input, job| {
+                let _ = input.add_value(job);
+            },
+            |output| vec![output.get_values().to_vec()],
+        );
+        let actual = trace(
+            &mut flat,
+            3,
+            |input, job| {
+                let _ = input[0].add_value(job);
+            },
+            |output| vec![output[0].get_values().to_vec()],
+        );
+        assert!(expected.iter().any(|(_, events)| !events[0].is_empty()));
+        assert_eq!(actual, expected);
+
+        let counts = |model: &dyn Devstone| {
+            (
+                model.get_n_internals(),
+                model.get_n_externals(),
+                model.get_n_events(),
+            )
+        };
+        assert_eq!(counts(&flat), counts(&hierarchical));
+    }
+}
@@ -0,0 +1,100 @@
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator, SharedLeafModel};
+use crate::Component;
+
+/// HI model enum (shared version)
+#[crate::to_component(reset)]
+pub enum HIEnum<'a, const W: usize> {
+    Leaf(SharedLeafModel),
+    Branch(HIModel<'a, W>),
+}
+
+impl<'a, const W: usize> Devstone for HIEnum<'a, W> {
+    crate::impl_devstone_enum!();
+}
+
+/// HI coupled model (shared version)
+#[crate::coupled(reset)]
+pub struct HIModel<'a, const W: usize> {
+    atomics: [AtomicModel; W],
+    #[shared]
+    inner: &'a mut HIEnum<'a, W>,
+}
+
+impl<'a, const W: usize> crate::Component for HIModel<'a, W> {
+    type Kind = crate::CoupledKind;
+    type Input = crate::Port<usize, 1>;
+    type Output = crate::Port<usize, 1>;
+}
+
+impl<'a, const W: usize> crate::Coupled for HIModel<'a, W> {
+    fn eic(from: &Self::Input, to: &mut crate::ComponentsInput<Self>) {
+        for atom_ports in to.atomics.iter_mut() {
+            let _ = from.couple(atom_ports);
+        }
+    }
+
+    fn ic(from: &crate::ComponentsOutput<Self>, to: &mut crate::ComponentsInput<Self>) {
+        for i in 0..(W.saturating_sub(1)) {
+            let _ = from.atomics[i].couple(&mut to.atomics[i + 1]);
+        }
+    }
+}
+
+impl<'a, const W: usize> HIModel<'a, W> {
+    pub fn new(inner: &'a mut HIEnum<'a, W>) -> Self {
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: &'a mut HIEnum<'a, W>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
+    }
+}
+
+impl<'a, const W: usize> Devstone for HIModel<'a, W> {
+    crate::impl_devstone_coupled!();
+}
+
+/// End model with Generator and HI model coupled together (shared version)
+#[crate::coupled(reset)]
+pub struct TopModel<'a, const W: usize> {
+    generator: JobGenerator,
+    hi_model: &'a mut HIEnum<'a, W>,
+}
+
+impl<'a, const W: usize> Component for TopModel<'a, W> {
+    type Kind = crate::CoupledKind;
+    type Input = crate::Port<usize, 1>;
+    type Output = crate::Port<usize, 1>;
+}
+
+impl<'a, const W: usize> Devstone for TopModel<'a, W> {
+    crate::impl_devstone_top!(hi_model);
+}
+
+impl<'a, const W: usize> crate::Coupled for TopModel<'a, W> {
+    fn ic(from: &crate::ComponentsOutput<Self>, to: &mut crate::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.hi_model);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inner_level_shares_bags() {
+        use crate::footprint::{footprint, StaticFootprint};
+        type Level = HIModel<'static, 4>;
+        type Buffers = <Level as crate::component::coupled::PartialCoupled>::Buffers;
+
+        // Only the atomic models have bags: the inner level uses the bags of its parent
+        let atomic_bags = core::mem::size_of::<[crate::Port<usize, 1>; 4]>();
+        let buffers = <Buffers as StaticFootprint>::FOOTPRINT;
+        assert_eq!(buffers.fields[0].footprint.size, atomic_bags);
+        assert_eq!(buffers.fields[1].footprint.size, atomic_bags);
+        assert_eq!(footprint::<Level>().fields[1].footprint, buffers);
+    }
+}
@@ -1,16 +1,16 @@
 use crate::Component;
 
-use super::common::{AtomicModel, Devstone, JobGenerator};
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator};
 
 /// Output struct for HO models (ref version)
 #[derive(Debug, Default, crate::Bag)]
 pub struct HOModelOutput<const W: usize> {
-    output_port_1: xdevs::Port<usize, 1>,
-    output_port_2: xdevs::Port<usize, W>,
+    pub output_port_1: xdevs::Port<usize, 1>,
+    pub output_port_2: xdevs::Port<usize, W>,
 }
 
 /// Leaf coupled model with only one atomic in HO models (ref version)
-#[crate::coupled]
+#[crate::coupled(reset)]
 pub struct LeafModel<const W: usize> {
     atomic: AtomicModel,
 }
@@ -38,7 +38,11 @@ impl<const W: usize> Default for LeafModel<W> {
 
 impl<const W: usize> LeafModel<W> {
     pub fn new() -> Self {
-        Self::build(AtomicModel::default())
+        Self::with_delays(Delays::ZERO)
+    }
+
+    pub fn with_delays(delays: Delays) -> Self {
+        Self::build(AtomicModel::with_delays(delays))
     }
 }
 
@@ -47,7 +51,7 @@ impl<const W: usize> Devstone for LeafModel<W> {
 }
 
 /// HO model enum (ref version)
-#[crate::to_component]
+#[crate::to_component(reset)]
 pub enum HOEnum<'a, const W: usize> {
     Leaf(LeafModel<W>),
     Branch(HOModel<'a, W>),
@@ -58,7 +62,7 @@ impl<'a, const W: usize> Devstone for HOEnum<'a, W> {
 }
 
 /// HO coupled model (ref version)
-#[crate::coupled]
+#[crate::coupled(reset)]
 pub struct HOModel<'a, const W: usize> {
     atomics: [AtomicModel; W],
     inner: &'a mut HOEnum<'a, W>,
@@ -66,7 +70,14 @@ pub struct HOModel<'a, const W: usize> {
 
 impl<'a, const W: usize> HOModel<'a, W> {
     pub fn new(inner: &'a mut HOEnum<'a, W>) -> Self {
-        Self::build(core::array::from_fn(|_| AtomicModel::default()), inner)
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: &'a mut HOEnum<'a, W>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
     }
 }
 
@@ -103,7 +114,7 @@ impl<'a, const W: usize> crate::Coupled for HOModel<'a, W> {
 }
 
 /// End model with Generator and HO model coupled together (ref version)
-#[crate::coupled]
+#[crate::coupled(reset)]
 pub struct TopModel<'a, const W: usize> {
     generator: JobGenerator,
     ho_model: &'a mut HOEnum<'a, W>,
@@ -158,6 +169,26 @@ mod test {
         assert_eq!(simulator.get_n_internals(), simulator.get_n_externals());
     }
 
+    #[test]
+    fn simulation_with_delays_matches_expected_counts() {
+        use crate::simulation::Simulable;
+        const WIDTH: usize = 4;
+        const DEPTH: usize = 3;
+        const W: usize = WIDTH - 1;
+
+        crate::generate_ho!(4, 3, 200, 2 * 50);
+
+        let generator = JobGenerator::new(5);
+        let top_model: TopModel<'_, W> = TopModel::build(generator, &mut model_ho);
+        let mut simulator = top_model.to_simulator();
+        let config = crate::simulation::Config::new(0.0, 10.0, 1.0, None);
+        simulator.simulate_vt(&config);
+
+        assert_eq!(expected_n_atomic(WIDTH, DEPTH), simulator.get_n_atomics());
+        assert_eq!(expected_n_events(WIDTH, DEPTH), simulator.get_n_events());
+        assert_eq!(simulator.get_n_internals(), simulator.get_n_externals());
+    }
+
     #[test]
     fn leaf_model_contains_single_atomic() {
         // Verify that the LeafModel contains exactly one atomic model independent of the width parameter
@@ -1,4 +1,4 @@
-use super::common::{AtomicModel, Devstone, JobGenerator};
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator};
 use alloc::boxed::Box;
 use xdevs::Component;
 
@@ -10,7 +10,7 @@ pub struct HOModelOutput<const W: usize> {
 }
 
 /// Leaf coupled model with only one atomic in HO models
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct LeafModel<const W: usize> {
     atomic: AtomicModel,
 }
@@ -38,7 +38,11 @@ impl<const W: usize> Default for LeafModel<W> {
 
 impl<const W: usize> LeafModel<W> {
     pub fn new() -> Self {
-        Self::build(AtomicModel::default())
+        Self::with_delays(Delays::ZERO)
+    }
+
+    pub fn with_delays(delays: Delays) -> Self {
+        Self::build(AtomicModel::with_delays(delays))
     }
 }
 
@@ -47,7 +51,7 @@ impl<const W: usize> Devstone for LeafModel<W> {
 }
 
 /// HO model enum
-#[xdevs::to_component]
+#[xdevs::to_component(reset)]
 pub enum HOEnum<const W: usize> {
     Leaf(LeafModel<W>),
     Branch(HOModel<W>),
@@ -58,14 +62,21 @@ impl<const W: usize> Devstone for HOEnum<W> {
 }
 
 /// HO coupled model
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct HOModel<const W: usize> {
     atomics: [AtomicModel; W],
     inner: Box<HOEnum<W>>,
 }
 impl<const W: usize> HOModel<W> {
     pub fn new(inner: Box<HOEnum<W>>) -> Self {
-        Self::build(core::array::from_fn(|_| AtomicModel::default()), inner)
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: Box<HOEnum<W>>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
     }
 }
 
@@ -101,7 +112,7 @@ impl<const W: usize> xdevs::Coupled for HOModel<W> {
 }
 
 /// End model with Generator and HO model coupled together
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct TopModel<const W: usize> {
     generator: JobGenerator,
     ho_model: HOEnum<W>,
@@ -156,6 +167,27 @@ mod test {
         assert_eq!(simulator.get_n_internals(), simulator.get_n_externals());
     }
 
+    #[test]
+    fn reset_simulation_matches_expected_counts() {
+        use xdevs::{AbstractSimulator, Reset, Simulable};
+
+        const WIDTH: usize = 6;
+        const DEPTH: usize = 4;
+        const W: usize = WIDTH - 1;
+
+        xdevs::generate_ho_box!(6, 4);
+
+        let top_model: TopModel<W> = TopModel::build(JobGenerator::new(5), model_ho);
+        let mut simulator = top_model.to_simulator();
+        let config = xdevs::Config::new(0.0, 10.0, 1.0, None);
+        for _ in 0..3 {
+            simulator.reset();
+            assert_eq!(simulator.get_n_events(), 0);
+            simulator.simulate_vt(&config);
+            assert_eq!(expected_n_events(WIDTH, DEPTH), simulator.get_n_events());
+        }
+    }
+
     #[test]
     fn leaf_model_contains_single_atomic() {
         // Verify that the LeafModel contains exactly one atomic model independent of the width parameter
@@ -0,0 +1,117 @@
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator};
+pub use super::ho_box::{HOModelOutput, LeafModel};
+use alloc::boxed::Box;
+use xdevs::Component;
+
+/// HO model enum (shared version)
+#[xdevs::to_component(reset)]
+pub enum HOEnum<const W: usize> {
+    Leaf(LeafModel<W>),
+    Branch(HOModel<W>),
+}
+
+impl<const W: usize> Devstone for HOEnum<W> {
+    crate::impl_devstone_enum!();
+}
+
+/// HO coupled model (shared version)
+#[xdevs::coupled(reset)]
+pub struct HOModel<const W: usize> {
+    atomics: [AtomicModel; W],
+    #[shared(input)]
+    inner: Box<HOEnum<W>>,
+}
+impl<const W: usize> HOModel<W> {
+    pub fn new(inner: Box<HOEnum<W>>) -> Self {
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: Box<HOEnum<W>>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
+    }
+}
+
+impl<const W: usize> Devstone for HOModel<W> {
+    crate::impl_devstone_coupled!();
+}
+impl<const W: usize> xdevs::Component for HOModel<W> {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = HOModelOutput<W>;
+}
+
+impl<const W: usize> xdevs::Coupled for HOModel<W> {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        for atom_ports in to.atomics.iter_mut() {
+            let _ = from.couple(atom_ports);
+        }
+    }
+
+    fn eoc(from: &xdevs::ComponentsOutput<Self>, to: &mut Self::Output) {
+        let _ = from.inner.output_port_1.couple(&mut to.output_port_1);
+        for atom_output_ports in from.atomics.iter() {
+            let _ = atom_output_ports.couple(&mut to.output_port_2);
+        }
+    }
+
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        for i in 0..(W.saturating_sub(1)) {
+            let _ = from.atomics[i].couple(&mut to.atomics[i + 1]);
+        }
+    }
+}
+
+/// End model with Generator and HO model coupled together (shared version)
+#[xdevs::coupled(reset)]
+pub struct TopModel<const W: usize> {
+    generator: JobGenerator,
+    ho_model: HOEnum<W>,
+}
+
+impl<const W: usize> Component for TopModel<W> {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl<const W: usize> Devstone for TopModel<W> {
+    crate::impl_devstone_top!(ho_model);
+}
+
+impl<const W: usize> xdevs::Coupled for TopModel<W> {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.ho_model);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reset_simulation_matches_reference() {
+        use xdevs::{AbstractSimulator, Reset, Simulable};
+
+        const W: usize = 5; // WIDTH - 1
+
+        xdevs::generate_ho_box!(6, 4);
+        let mut reference =
+            super::super::ho_box::TopModel::<W>::build(JobGenerator::new(5), model_ho)
+                .to_simulator();
+        xdevs::generate_ho_box_shared!(6, 4);
+        let mut shared = TopModel::<W>::build(JobGenerator::new(5), model_ho).to_simulator();
+
+        let config = xdevs::Config::new(0.0, 10.0, 1.0, None);
+        reference.simulate_vt(&config);
+        for _ in 0..3 {
+            shared.reset();
+            shared.simulate_vt(&config);
+            assert_eq!(reference.get_n_events(), shared.get_n_events());
+            assert_eq!(reference.get_n_internals(), shared.get_n_internals());
+            assert_eq!(reference.get_n_externals(), shared.get_n_externals());
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator};
+use alloc::{boxed::Box, vec::Vec};
+use xdevs::{
+    simulation::{
+        flat::{Flat, FlatBuilder, FlatPorts, Flatten},
+        simulator::Simulator,
+    },
+    Component, Simulable,
+};
+
+/// Output struct for HO models with a width only known at runtime
+#[derive(Debug, Default, xdevs::Bag)]
+pub struct HOModelOutput {
+    pub output_port_1: xdevs::Port<usize, 1>,
+    pub output_port_2: xdevs::DynPort<usize>,
+}
+
+/// Leaf coupled model with only one atomic in HO models
+#[xdevs::coupled(reset)]
+pub struct LeafModel {
+    atomic: AtomicModel,
+}
+
+impl Component for LeafModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = HOModelOutput;
+}
+
+impl xdevs::Coupled for LeafModel {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.couple(&mut to.atomic);
+    }
+    fn eoc(from: &xdevs::ComponentsOutput<Self>, to: &mut Self::Output) {
+        let _ = from.atomic.couple(&mut to.output_port_1);
+    }
+}
+
+impl Default for LeafModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LeafModel {
+    pub fn new() -> Self {
+        Self::with_delays(Delays::ZERO)
+    }
+
+    pub fn with_delays(delays: Delays) -> Self {
+        Self::build(AtomicModel::with_delays(delays))
+    }
+}
+
+impl Devstone for LeafModel {
+    crate::impl_devstone_leaf!();
+}
+
+impl Flatten<Simulator<AtomicModel>> for LeafModel {
+    fn flatten(self, flat: &mut FlatBuilder<Simulator<AtomicModel>>) -> FlatPorts {
+        let atomic = self.components.atomic.flatten(flat);
+
+        let mut ports = FlatPorts::new(1, 2);
+        ports.eic(0, &atomic, 0);
+        ports.eoc(&atomic, 0, 0);
+        ports
+    }
+}
+
+/// HO model enum
+#[xdevs::to_component(reset)]
+pub enum HOEnum {
+    Leaf(LeafModel),
+    Branch(HOModel),
+}
+
+impl Devstone for HOEnum {
+    crate::impl_devstone_enum!();
+}
+
+impl Flatten<Simulator<AtomicModel>> for HOEnum {
+    crate::impl_flatten_enum!(Simulator<AtomicModel>);
+}
+
+/// HO coupled model with a width only known at runtime
+#[xdevs::coupled(reset)]
+pub struct HOModel {
+    atomics: Vec<AtomicModel>,
+    #[shared(input)]
+    inner: Box<HOEnum>,
+}
+
+impl HOModel {
+    /// Creates a new HO coupled model with `width - 1` atomic models.
+    pub fn new(width: usize, inner: Box<HOEnum>) -> Self {
+        Self::with_delays(width, inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(width: usize, inner: Box<HOEnum>, delays: Delays) -> Self {
+        Self::build(
+            (1..width)
+                .map(|_| AtomicModel::with_delays(delays))
+                .collect(),
+            inner,
+        )
+    }
+}
+
+impl Devstone for HOModel {
+    crate::impl_devstone_coupled!();
+}
+
+/// Ports `0` and `1` of the flat model are `output_port_1` and `output_port_2`, respectively.
+impl Flatten<Simulator<AtomicModel>> for HOModel {
+    fn flatten(self, flat: &mut FlatBuilder<Simulator<AtomicModel>>) -> FlatPorts {
+        let atomics: Vec<_> = (self.components.atomics.into_iter())
+            .map(|atomic| atomic.flatten(flat))
+            .collect();
+        let inner = self.components.inner.flatten(flat);
+
+        let mut ports = FlatPorts::new(1, 2);
+        ports.eic(0, &inner, 0);
+        for atomic in atomics.iter() {
+            ports.eic(0, atomic, 0);
+            ports.eoc(atomic, 0, 1);
+        }
+        for (from, to) in atomics.iter().zip(atomics.iter().skip(1)) {
+            flat.ic(from, 0, to, 0);
+        }
+        ports.eoc(&inner, 0, 0);
+        ports
+    }
+}
+
+impl Component for HOModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = HOModelOutput;
+}
+
+impl xdevs::Coupled for HOModel {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        for atom_ports in to.atomics.iter_mut() {
+            let _ = from.couple(atom_ports);
+        }
+    }
+
+    fn eoc(from: &xdevs::ComponentsOutput<Self>, to: &mut Self::Output) {
+        let _ = from.inner.output_port_1.couple(&mut to.output_port_1);
+        for atom_output_ports in from.atomics.iter() {
+            to.output_port_2.add_values(atom_output_ports.get_values());
+        }
+    }
+
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        for (atom_output_ports, atom_ports) in
+            from.atomics.iter().zip(to.atomics.iter_mut().skip(1))
+        {
+            let _ = atom_output_ports.couple(atom_ports);
+        }
+    }
+}
+
+/// Builds an HO model of the given width and depth.
+/// It is the runtime counterpart of [`generate_ho_box`](crate::generate_ho_box).
+///
+/// # Panics
+///
+/// Panics if `width` or `depth` are zero.
+pub fn build(width: usize, depth: usize, delays: Delays) -> HOEnum {
+    assert!(width >= 1, "width must be at least 1");
+    assert!(depth >= 1, "depth must be at least 1");
+
+    let mut model = HOEnum::Leaf(LeafModel::with_delays(delays).to_simulator());
+    for _ in 1..depth {
+        let branch = HOModel::with_delays(width, Box::new(model), delays);
+        model = HOEnum::Branch(branch.to_simulator());
+    }
+    model
+}
+
+/// End model with Generator and HO model coupled together
+#[xdevs::coupled(reset)]
+pub struct TopModel {
+    generator: JobGenerator,
+    ho_model: HOEnum,
+}
+
+impl Component for TopModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl Devstone for TopModel {
+    crate::impl_devstone_top!(ho_model);
+}
+
+impl xdevs::Coupled for TopModel {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.ho_model);
+    }
+}
+
+/// Flat HO model. All the atomic models of the top level send their events through
+/// `output_port_2`, so its output ports have unbounded bags.
+pub type FlatHOModel =
+    Flat<Simulator<AtomicModel>, 1, 2, xdevs::Port<usize, 1>, xdevs::DynPort<usize>>;
+
+/// End model with Generator and a flat HO model coupled together
+#[xdevs::coupled(reset)]
+pub struct FlatTopModel {
+    generator: JobGenerator,
+    ho_model: FlatHOModel,
+}
+
+impl FlatTopModel {
+    /// Flattens the given HO model and couples a generator to it.
+    pub fn flatten(generator: JobGenerator, ho_model: HOEnum) -> Self {
+        Self::build(generator, Flat::new(ho_model))
+    }
+}
+
+impl Component for FlatTopModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl Devstone for FlatTopModel {
+    crate::impl_devstone_top!(ho_model);
+}
+
+impl xdevs::Coupled for FlatTopModel {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.ho_model[0]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::devstone::{
+        common::trace,
+        expected::{verify, Topology},
+    };
+    use alloc::vec;
+
+    #[test]
+    fn simulation_matches_expected_counts() {
+        use xdevs::AbstractSimulator;
+
+        let (width, depth) = (10, 10);
+        let top_model = TopModel::build(JobGenerator::new(5), build(width, depth, Delays::ZERO));
+        let mut simulator = top_model.to_simulator();
+        simulator.simulate_vt(&xdevs::Config::new(0.0, 10.0, 1.0, None));
+
+        assert_eq!(
+            verify(&*simulator, Topology::HO, width, depth),
+            Ok(Topology::HO.expected(width, depth))
+        );
+    }
+
+    #[test]
+    fn leaf_model_contains_single_atomic() {
+        assert_eq!(LeafModel::default().get_n_atomics(), 1);
+    }
+
+    #[test]
+    fn flat_simulation_matches_hierarchical_trace() {
+        let (width, depth) = (10, 10);
+        let mut hierarchical = build(width, depth, Delays::ZERO);
+        let mut flat = FlatHOModel::new(build(width, depth, Delays::ZERO));
+
+        let expected = trace(
+            &mut hierarchical,
+            3,
+            |input, job| {
+                let _ = input.add_value(job);
+            },
+            |output| {
+                vec![
+                    output.output_port_1.get_values().to_vec(),
+                    output.output_port_2.get_values().to_vec(),
+                ]
+            },
+        );
+        let actual = trace(
+            &mut flat,
+            3,
+            |input, job| {
+                let _ = input[0].add_value(job);
+            },
+            |output| {
+                output
+                    .iter()
+                    .map(|port| port.get_values().to_vec())
+                    .collect()
+            },
+        );
+        // All the atomic models of the top level send their events through `output_port_2`
+        assert_eq!(expected[0].1[1], vec![1; width - 1]);
+        assert_eq!(actual, expected);
+
+        let counts = |model: &dyn Devstone| {
+            (
+                model.get_n_internals(),
+                model.get_n_externals(),
+                model.get_n_events(),
+            )
+        };
+        assert_eq!(counts(&flat), counts(&hierarchical));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::Component;
+
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator};
+pub use super::ho::{HOModelOutput, LeafModel};
+
+/// HO model enum (shared version)
+#[crate::to_component(reset)]
+pub enum HOEnum<'a, const W: usize> {
+    Leaf(LeafModel<W>),
+    Branch(HOModel<'a, W>),
+}
+
+impl<'a, const W: usize> Devstone for HOEnum<'a, W> {
+    crate::impl_devstone_enum!();
+}
+
+/// HO coupled model (shared version)
+#[crate::coupled(reset)]
+pub struct HOModel<'a, const W: usize> {
+    atomics: [AtomicModel; W],
+    #[shared(input)]
+    inner: &'a mut HOEnum<'a, W>,
+}
+
+impl<'a, const W: usize> HOModel<'a, W> {
+    pub fn new(inner: &'a mut HOEnum<'a, W>) -> Self {
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: &'a mut HOEnum<'a, W>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
+    }
+}
+
+impl<'a, const W: usize> Devstone for HOModel<'a, W> {
+    crate::impl_devstone_coupled!();
+}
+
+impl<'a, const W: usize> crate::Component for HOModel<'a, W> {
+    type Kind = crate::CoupledKind;
+    type Input = crate::Port<usize, 1>;
+    type Output = HOModelOutput<W>;
+}
+
+impl<'a, const W: usize> crate::Coupled for HOModel<'a, W> {
+    fn eic(from: &Self::Input, to: &mut crate::ComponentsInput<Self>) {
+        for atom_ports in to.atomics.iter_mut() {
+            let _ = from.couple(atom_ports);
+        }
+    }
+
+    fn eoc(from: &crate::ComponentsOutput<Self>, to: &mut Self::Output) {
+        let _ = from.inner.output_port_1.couple(&mut to.output_port_1);
+        for atom_output_ports in from.atomics.iter() {
+            let _ = atom_output_ports.couple(&mut to.output_port_2);
+        }
+    }
+
+    fn ic(from: &crate::ComponentsOutput<Self>, to: &mut crate::ComponentsInput<Self>) {
+        for i in 0..(W.saturating_sub(1)) {
+            let _ = from.atomics[i].couple(&mut to.atomics[i + 1]);
+        }
+    }
+}
+
+/// End model with Generator and HO model coupled together (shared version)
+#[crate::coupled(reset)]
+pub struct TopModel<'a, const W: usize> {
+    generator: JobGenerator,
+    ho_model: &'a mut HOEnum<'a, W>,
+}
+
+impl<'a, const W: usize> Component for TopModel<'a, W> {
+    type Kind = crate::CoupledKind;
+    type Input = crate::Port<usize, 1>;
+    type Output = crate::Port<usize, 1>;
+}
+
+impl<'a, const W: usize> Devstone for TopModel<'a, W> {
+    crate::impl_devstone_top!(ho_model);
+}
+
+impl<'a, const W: usize> crate::Coupled for TopModel<'a, W> {
+    fn ic(from: &crate::ComponentsOutput<Self>, to: &mut crate::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.ho_model);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inner_level_shares_input_bag() {
+        use crate::footprint::StaticFootprint;
+        type Level = HOModel<'static, 4>;
+        type Buffers = <Level as crate::component::coupled::PartialCoupled>::Buffers;
+
+        // The inner level reads the input of its parent, but its output is only partially coupled
+        let buffers = <Buffers as StaticFootprint>::FOOTPRINT;
+        assert_eq!(
+            buffers.fields[0].footprint.size,
+            core::mem::size_of::<[crate::Port<usize, 1>; 4]>()
+        );
+        assert_eq!(
+            buffers.fields[1].footprint.size,
+            core::mem::size_of::<([crate::Port<usize, 1>; 4], HOModelOutput<4>)>()
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator};
+use crate::Component;
+
+/// Input struct for HOmod models (ref version)
+#[derive(Debug, Default, crate::Bag)]
+pub struct HOmodInput<const W: usize> {
+    input_port_1: crate::Port<usize, 1>,
+    input_port_2: crate::Port<usize, W>,
+}
+
+/// Leaf coupled model with only one atomic in HOmod models (ref version)
+#[crate::coupled(reset)]
+pub struct LeafModel<const W: usize, const N: usize> {
+    atomic: AtomicModel<N>,
+}
+
+impl<const W: usize, const N: usize> crate::Component for LeafModel<W, N> {
+    type Kind = crate::CoupledKind;
+    type Input = HOmodInput<W>;
+    type Output = crate::Port<usize, 1>;
+}
+
+impl<const W: usize, const N: usize> crate::Coupled for LeafModel<W, N> {
+    fn eic(from: &Self::Input, to: &mut crate::ComponentsInput<Self>) {
+        let _ = from.input_port_1.couple(&mut to.atomic);
+        let _ = from.input_port_2.couple(&mut to.atomic);
+    }
+    fn eoc(from: &crate::ComponentsOutput<Self>, to: &mut Self::Output) {
+        let _ = from.atomic.couple(to);
+    }
+}
+
+impl<const W: usize, const N: usize> Default for LeafModel<W, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const N: usize> LeafModel<W, N> {
+    pub fn new() -> Self {
+        Self::with_delays(Delays::ZERO)
+    }
+
+    pub fn with_delays(delays: Delays) -> Self {
+        Self::build(AtomicModel::with_delays(delays))
+    }
+}
+
+impl<const W: usize, const N: usize> Devstone for LeafModel<W, N> {
+    crate::impl_devstone_leaf!();
+}
+
+/// HOmod model enum (ref version)
+#[crate::to_component(reset)]
+pub enum HOmodEnum<'a, const W: usize, const N: usize> {
+    Leaf(LeafModel<W, N>),
+    Branch(HOmodModel<'a, W, N>),
+}
+
+impl<'a, const W: usize, const N: usize> Devstone for HOmodEnum<'a, W, N> {
+    crate::impl_devstone_enum!();
+}
+
+/// HOmod coupled model (ref version)
+///
+/// `W` is the width minus one, and `N` is the input capacity of the atomic models, which must be
+/// at least `2 * W` (and at least 2).
+/// The first row of atomics feeds the second input of the inner model. The remaining rows form a
+/// triangle, where row `k` has its first atomic at column `k`: the first row of the triangle is
+/// fully connected to the first row, and the other rows are connected column-wise to the
+/// previous row. The second input feeds the first row and the first atomic of each triangle row.
+#[crate::coupled(reset)]
+pub struct HOmodModel<'a, const W: usize, const N: usize> {
+    first_row: [AtomicModel<N>; W],
+    triangle: [[Option<AtomicModel<N>>; W]; W],
+    #[shared(output)]
+    inner: &'a mut HOmodEnum<'a, W, N>,
+}
+
+impl<'a, const W: usize, const N: usize> HOmodModel<'a, W, N> {
+    pub fn new(inner: &'a mut HOmodEnum<'a, W, N>) -> Self {
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: &'a mut HOmodEnum<'a, W, N>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            core::array::from_fn(|k| {
+                core::array::from_fn(|c| (c >= k).then(|| AtomicModel::with_delays(delays)))
+            }),
+            inner,
+        )
+    }
+
+    /// Iterates over the atomic models of this level (i.e., the first row and the triangle).
+    fn atomics(&self) -> impl Iterator<Item = &AtomicModel<N>> {
+        let triangle = self.components.triangle.iter().flatten().flatten();
+        self.components
+            .first_row
+            .iter()
+            .chain(triangle)
+            .map(|a| &**a)
+    }
+}
+
+impl<'a, const W: usize, const N: usize> Devstone for HOmodModel<'a, W, N> {
+    fn get_n_internals(&self) -> usize {
+        self.atomics().map(|a| a.get_n_internals()).sum::<usize>()
+            + self.components.inner.get_n_internals()
+    }
+    fn get_n_externals(&self) -> usize {
+        self.atomics().map(|a| a.get_n_externals()).sum::<usize>()
+            + self.components.inner.get_n_externals()
+    }
+    fn get_n_events(&self) -> usize {
+        self.atomics().map(|a| a.get_n_events()).sum::<usize>()
+            + self.components.inner.get_n_events()
+    }
+    fn get_n_atomics(&self) -> usize {
+        self.atomics().count() + self.components.inner.get_n_atomics()
+    }
+}
+
+impl<'a, const W: usize, const N: usize> crate::Component for HOmodModel<'a, W, N> {
+    type Kind = crate::CoupledKind;
+    type Input = HOmodInput<W>;
+    type Output = crate::Port<usize, 1>;
+}
+
+impl<'a, const W: usize, const N: usize> crate::Coupled for HOmodModel<'a, W, N> {
+    fn eic(from: &Self::Input, to: &mut crate::ComponentsInput<Self>) {
+        let _ = from.input_port_1.couple(&mut to.inner.input_port_1);
+        for atom_ports in to.first_row.iter_mut() {
+            let _ = from.input_port_2.couple(atom_ports);
+        }
+        for k in 0..W {
+            let _ = from.input_port_2.couple(&mut to.triangle[k][k]);
+        }
+    }
+
+    fn ic(from: &crate::ComponentsOutput<Self>, to: &mut crate::ComponentsInput<Self>) {
+        for atom_output_ports in from.first_row.iter() {
+            let _ = atom_output_ports.couple(&mut to.inner.input_port_2);
+        }
+        if let Some(first_triangle_row) = from.triangle.first() {
+            for atom_output_ports in first_triangle_row.iter() {
+                for atom_ports in to.first_row.iter_mut() {
+                    let _ = atom_output_ports.couple(atom_ports);
+                }
+            }
+        }
+        for k in 1..W {
+            for c in k..W {
+                let _ = from.triangle[k][c].couple(&mut to.triangle[k - 1][c]);
+            }
+        }
+    }
+}
+
+/// End model with Generator and HOmod model coupled together (ref version)
+#[crate::coupled(reset)]
+pub struct TopModel<'a, const W: usize, const N: usize> {
+    generator: JobGenerator,
+    homod_model: &'a mut HOmodEnum<'a, W, N>,
+}
+
+impl<'a, const W: usize, const N: usize> Component for TopModel<'a, W, N> {
+    type Kind = crate::CoupledKind;
+    type Input = crate::Port<usize, 1>;
+    type Output = crate::Port<usize, 1>;
+}
+
+impl<'a, const W: usize, const N: usize> Devstone for TopModel<'a, W, N> {
+    crate::impl_devstone_top!(homod_model);
+}
+
+impl<'a, const W: usize, const N: usize> crate::Coupled for TopModel<'a, W, N> {
+    fn ic(from: &crate::ComponentsOutput<Self>, to: &mut crate::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.homod_model.input_port_1);
+        let _ = from.generator.couple(&mut to.homod_model.input_port_2);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::devstone::expected::Topology;
+    use crate::simulation::AbstractSimulator;
+
+    #[test]
+    fn simulation_matches_expected_counts() {
+        use crate::simulation::Simulable;
+        const WIDTH: usize = 6;
+        const DEPTH: usize = 5;
+        const W: usize = WIDTH - 1;
+        const N: usize = 2 * W;
+
+        crate::generate_homod!(6, 5);
+
+        let generator = JobGenerator::new(5);
+        let top_model: TopModel<'_, W, N> = TopModel::build(generator, &mut model_homod);
+        let mut simulator = top_model.to_simulator();
+        let config = crate::simulation::Config::new(0.0, 10.0, 1.0, None);
+        simulator.simulate_vt(&config);
+
+        assert_eq!(
+            Topology::HOmod.n_atomics(WIDTH, DEPTH),
+            simulator.get_n_atomics()
+        );
+        assert_eq!(
+            Topology::HOmod.n_internals(WIDTH, DEPTH),
+            simulator.get_n_internals()
+        );
+        assert_eq!(simulator.get_n_internals(), simulator.get_n_externals());
+        assert_eq!(
+            Topology::HOmod.n_events(WIDTH, DEPTH),
+            simulator.get_n_events()
+        );
+    }
+
+    #[test]
+    fn simulation_matches_expected_counts_small() {
+        use crate::simulation::Simulable;
+
+        crate::generate_homod!(2, 3);
+        let top_model: TopModel<'_, 1, 2> = TopModel::build(JobGenerator::new(5), &mut model_homod);
+        let mut simulator = top_model.to_simulator();
+        simulator.simulate_vt(&crate::simulation::Config::new(0.0, 10.0, 1.0, None));
+        assert_eq!(Topology::HOmod.n_atomics(2, 3), simulator.get_n_atomics());
+        assert_eq!(
+            Topology::HOmod.n_internals(2, 3),
+            simulator.get_n_internals()
+        );
+        assert_eq!(Topology::HOmod.n_events(2, 3), simulator.get_n_events());
+
+        crate::generate_homod!(3, 1);
+        let top_model: TopModel<'_, 2, 4> = TopModel::build(JobGenerator::new(5), &mut model_homod);
+        let mut simulator = top_model.to_simulator();
+        simulator.simulate_vt(&crate::simulation::Config::new(0.0, 10.0, 1.0, None));
+        assert_eq!(Topology::HOmod.n_atomics(3, 1), simulator.get_n_atomics());
+        assert_eq!(
+            Topology::HOmod.n_internals(3, 1),
+            simulator.get_n_internals()
+        );
+        assert_eq!(Topology::HOmod.n_events(3, 1), simulator.get_n_events());
+
+        crate::generate_homod!(1, 3);
+        let top_model: TopModel<'_, 0, 2> = TopModel::build(JobGenerator::new(5), &mut model_homod);
+        let mut simulator = top_model.to_simulator();
+        simulator.simulate_vt(&crate::simulation::Config::new(0.0, 10.0, 1.0, None));
+        assert_eq!(Topology::HOmod.n_atomics(1, 3), simulator.get_n_atomics());
+        assert_eq!(
+            Topology::HOmod.n_internals(1, 3),
+            simulator.get_n_internals()
+        );
+        assert_eq!(Topology::HOmod.n_events(1, 3), simulator.get_n_events());
+    }
+
+    #[test]
+    fn leaf_model_contains_single_atomic() {
+        assert_eq!(LeafModel::<5, 10>::default().get_n_atomics(), 1);
+    }
+}
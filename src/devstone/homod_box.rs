@@ -0,0 +1,221 @@
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator};
+use alloc::boxed::Box;
+use xdevs::Component;
+
+/// Input struct for HOmod models
+#[derive(Debug, Default, xdevs::Bag)]
+pub struct HOmodInput<const W: usize> {
+    pub input_port_1: xdevs::Port<usize, 1>,
+    pub input_port_2: xdevs::Port<usize, W>,
+}
+
+/// Leaf coupled model with only one atomic in HOmod models
+#[xdevs::coupled(reset)]
+pub struct LeafModel<const W: usize, const N: usize> {
+    atomic: AtomicModel<N>,
+}
+
+impl<const W: usize, const N: usize> xdevs::Component for LeafModel<W, N> {
+    type Kind = xdevs::CoupledKind;
+    type Input = HOmodInput<W>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl<const W: usize, const N: usize> xdevs::Coupled for LeafModel<W, N> {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.input_port_1.couple(&mut to.atomic);
+        let _ = from.input_port_2.couple(&mut to.atomic);
+    }
+    fn eoc(from: &xdevs::ComponentsOutput<Self>, to: &mut Self::Output) {
+        let _ = from.atomic.couple(to);
+    }
+}
+
+impl<const W: usize, const N: usize> Default for LeafModel<W, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const N: usize> LeafModel<W, N> {
+    pub fn new() -> Self {
+        Self::with_delays(Delays::ZERO)
+    }
+
+    pub fn with_delays(delays: Delays) -> Self {
+        Self::build(AtomicModel::with_delays(delays))
+    }
+}
+
+impl<const W: usize, const N: usize> Devstone for LeafModel<W, N> {
+    crate::impl_devstone_leaf!();
+}
+
+/// HOmod model enum
+#[xdevs::to_component(reset)]
+pub enum HOmodEnum<const W: usize, const N: usize> {
+    Leaf(LeafModel<W, N>),
+    Branch(HOmodModel<W, N>),
+}
+
+impl<const W: usize, const N: usize> Devstone for HOmodEnum<W, N> {
+    crate::impl_devstone_enum!();
+}
+
+/// HOmod coupled model
+///
+/// See [`super::homod::HOmodModel`] for a description of the topology.
+#[xdevs::coupled(reset)]
+pub struct HOmodModel<const W: usize, const N: usize> {
+    first_row: [AtomicModel<N>; W],
+    triangle: [[Option<AtomicModel<N>>; W]; W],
+    #[shared(output)]
+    inner: Box<HOmodEnum<W, N>>,
+}
+
+impl<const W: usize, const N: usize> HOmodModel<W, N> {
+    pub fn new(inner: Box<HOmodEnum<W, N>>) -> Self {
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: Box<HOmodEnum<W, N>>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            core::array::from_fn(|k| {
+                core::array::from_fn(|c| (c >= k).then(|| AtomicModel::with_delays(delays)))
+            }),
+            inner,
+        )
+    }
+
+    /// Iterates over the atomic models of this level (i.e., the first row and the triangle).
+    fn atomics(&self) -> impl Iterator<Item = &AtomicModel<N>> {
+        let triangle = self.components.triangle.iter().flatten().flatten();
+        self.components
+            .first_row
+            .iter()
+            .chain(triangle)
+            .map(|a| &**a)
+    }
+}
+
+impl<const W: usize, const N: usize> Devstone for HOmodModel<W, N> {
+    fn get_n_internals(&self) -> usize {
+        self.atomics().map(|a| a.get_n_internals()).sum::<usize>()
+            + self.components.inner.get_n_internals()
+    }
+    fn get_n_externals(&self) -> usize {
+        self.atomics().map(|a| a.get_n_externals()).sum::<usize>()
+            + self.components.inner.get_n_externals()
+    }
+    fn get_n_events(&self) -> usize {
+        self.atomics().map(|a| a.get_n_events()).sum::<usize>()
+            + self.components.inner.get_n_events()
+    }
+    fn get_n_atomics(&self) -> usize {
+        self.atomics().count() + self.components.inner.get_n_atomics()
+    }
+}
+
+impl<const W: usize, const N: usize> xdevs::Component for HOmodModel<W, N> {
+    type Kind = xdevs::CoupledKind;
+    type Input = HOmodInput<W>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl<const W: usize, const N: usize> xdevs::Coupled for HOmodModel<W, N> {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.input_port_1.couple(&mut to.inner.input_port_1);
+        for atom_ports in to.first_row.iter_mut() {
+            let _ = from.input_port_2.couple(atom_ports);
+        }
+        for k in 0..W {
+            let _ = from.input_port_2.couple(&mut to.triangle[k][k]);
+        }
+    }
+
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        for atom_output_ports in from.first_row.iter() {
+            let _ = atom_output_ports.couple(&mut to.inner.input_port_2);
+        }
+        if let Some(first_triangle_row) = from.triangle.first() {
+            for atom_output_ports in first_triangle_row.iter() {
+                for atom_ports in to.first_row.iter_mut() {
+                    let _ = atom_output_ports.couple(atom_ports);
+                }
+            }
+        }
+        for k in 1..W {
+            for c in k..W {
+                let _ = from.triangle[k][c].couple(&mut to.triangle[k - 1][c]);
+            }
+        }
+    }
+}
+
+/// End model with Generator and HOmod model coupled together
+#[xdevs::coupled(reset)]
+pub struct TopModel<const W: usize, const N: usize> {
+    generator: JobGenerator,
+    homod_model: HOmodEnum<W, N>,
+}
+
+impl<const W: usize, const N: usize> Component for TopModel<W, N> {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl<const W: usize, const N: usize> Devstone for TopModel<W, N> {
+    crate::impl_devstone_top!(homod_model);
+}
+
+impl<const W: usize, const N: usize> xdevs::Coupled for TopModel<W, N> {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.homod_model.input_port_1);
+        let _ = from.generator.couple(&mut to.homod_model.input_port_2);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::devstone::expected::Topology;
+
+    #[test]
+    fn simulation_matches_expected_counts() {
+        use xdevs::{AbstractSimulator, Simulable};
+
+        const WIDTH: usize = 6;
+        const DEPTH: usize = 5;
+        const W: usize = WIDTH - 1;
+        const N: usize = 2 * W;
+
+        xdevs::generate_homod_box!(6, 5);
+
+        let generator = JobGenerator::new(5);
+        let top_model: TopModel<W, N> = TopModel::build(generator, model_homod);
+        let mut simulator = top_model.to_simulator();
+        let config = xdevs::Config::new(0.0, 10.0, 1.0, None);
+        simulator.simulate_vt(&config);
+
+        assert_eq!(
+            Topology::HOmod.n_atomics(WIDTH, DEPTH),
+            simulator.get_n_atomics()
+        );
+        assert_eq!(
+            Topology::HOmod.n_internals(WIDTH, DEPTH),
+            simulator.get_n_internals()
+        );
+        assert_eq!(simulator.get_n_internals(), simulator.get_n_externals());
+        assert_eq!(
+            Topology::HOmod.n_events(WIDTH, DEPTH),
+            simulator.get_n_events()
+        );
+    }
+
+    #[test]
+    fn leaf_model_contains_single_atomic() {
+        assert_eq!(LeafModel::<5, 10>::default().get_n_atomics(), 1);
+    }
+}
@@ -0,0 +1,390 @@
+use super::common::{Delays, Devstone, DynAtomicModel, JobGenerator};
+use alloc::{boxed::Box, vec::Vec};
+use xdevs::{
+    simulation::{
+        flat::{Flat, FlatBuilder, FlatPorts, Flatten},
+        simulator::Simulator,
+    },
+    Component, Simulable,
+};
+
+/// Input struct for HOmod models with a width only known at runtime
+#[derive(Debug, Default, xdevs::Bag)]
+pub struct HOmodInput {
+    pub input_port_1: xdevs::Port<usize, 1>,
+    pub input_port_2: xdevs::DynPort<usize>,
+}
+
+/// Leaf coupled model with only one atomic in HOmod models
+#[xdevs::coupled(reset)]
+pub struct LeafModel {
+    atomic: DynAtomicModel,
+}
+
+impl Component for LeafModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = HOmodInput;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl xdevs::Coupled for LeafModel {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        to.atomic.add_values(from.input_port_1.get_values());
+        from.input_port_2.couple(&mut to.atomic);
+    }
+    fn eoc(from: &xdevs::ComponentsOutput<Self>, to: &mut Self::Output) {
+        let _ = from.atomic.couple(to);
+    }
+}
+
+impl Default for LeafModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LeafModel {
+    pub fn new() -> Self {
+        Self::with_delays(Delays::ZERO)
+    }
+
+    pub fn with_delays(delays: Delays) -> Self {
+        Self::build(DynAtomicModel::with_delays(delays))
+    }
+}
+
+impl Devstone for LeafModel {
+    crate::impl_devstone_leaf!();
+}
+
+/// Ports `0` and `1` of the flat model are `input_port_1` and `input_port_2`, respectively.
+impl Flatten<Simulator<DynAtomicModel>> for LeafModel {
+    fn flatten(self, flat: &mut FlatBuilder<Simulator<DynAtomicModel>>) -> FlatPorts {
+        let atomic = self.components.atomic.flatten(flat);
+
+        let mut ports = FlatPorts::new(2, 1);
+        ports.eic(0, &atomic, 0);
+        ports.eic(1, &atomic, 0);
+        ports.eoc(&atomic, 0, 0);
+        ports
+    }
+}
+
+/// HOmod model enum
+#[xdevs::to_component(reset)]
+pub enum HOmodEnum {
+    Leaf(LeafModel),
+    Branch(HOmodModel),
+}
+
+impl Devstone for HOmodEnum {
+    crate::impl_devstone_enum!();
+}
+
+impl Flatten<Simulator<DynAtomicModel>> for HOmodEnum {
+    crate::impl_flatten_enum!(Simulator<DynAtomicModel>);
+}
+
+/// HOmod coupled model with a width only known at runtime
+///
+/// See [`super::homod::HOmodModel`] for a description of the topology.
+/// Here, row `k` of the triangle only stores its atomics (i.e., from column `k` onwards).
+#[xdevs::coupled(reset)]
+pub struct HOmodModel {
+    first_row: Vec<DynAtomicModel>,
+    triangle: Vec<Vec<DynAtomicModel>>,
+    #[shared(output)]
+    inner: Box<HOmodEnum>,
+}
+
+impl HOmodModel {
+    /// Creates a new HOmod coupled model for the given width.
+    pub fn new(width: usize, inner: Box<HOmodEnum>) -> Self {
+        Self::with_delays(width, inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(width: usize, inner: Box<HOmodEnum>, delays: Delays) -> Self {
+        let w = width - 1;
+        Self::build(
+            (0..w)
+                .map(|_| DynAtomicModel::with_delays(delays))
+                .collect(),
+            (0..w)
+                .map(|k| {
+                    (k..w)
+                        .map(|_| DynAtomicModel::with_delays(delays))
+                        .collect()
+                })
+                .collect(),
+            inner,
+        )
+    }
+
+    /// Iterates over the atomic models of this level (i.e., the first row and the triangle).
+    fn atomics(&self) -> impl Iterator<Item = &DynAtomicModel> {
+        let triangle = self.components.triangle.iter().flatten();
+        self.components
+            .first_row
+            .iter()
+            .chain(triangle)
+            .map(|a| &**a)
+    }
+}
+
+impl Devstone for HOmodModel {
+    fn get_n_internals(&self) -> usize {
+        self.atomics().map(|a| a.get_n_internals()).sum::<usize>()
+            + self.components.inner.get_n_internals()
+    }
+    fn get_n_externals(&self) -> usize {
+        self.atomics().map(|a| a.get_n_externals()).sum::<usize>()
+            + self.components.inner.get_n_externals()
+    }
+    fn get_n_events(&self) -> usize {
+        self.atomics().map(|a| a.get_n_events()).sum::<usize>()
+            + self.components.inner.get_n_events()
+    }
+    fn get_n_atomics(&self) -> usize {
+        self.atomics().count() + self.components.inner.get_n_atomics()
+    }
+}
+
+/// Ports `0` and `1` of the flat model are `input_port_1` and `input_port_2`, respectively.
+impl Flatten<Simulator<DynAtomicModel>> for HOmodModel {
+    fn flatten(self, flat: &mut FlatBuilder<Simulator<DynAtomicModel>>) -> FlatPorts {
+        let first_row: Vec<_> = (self.components.first_row.into_iter())
+            .map(|atomic| atomic.flatten(flat))
+            .collect();
+        let triangle: Vec<Vec<_>> = (self.components.triangle.into_iter())
+            .map(|row| row.into_iter().map(|atomic| atomic.flatten(flat)).collect())
+            .collect();
+        let inner = self.components.inner.flatten(flat);
+
+        let mut ports = FlatPorts::new(2, 1);
+        ports.eic(0, &inner, 0);
+        for atomic in first_row.iter() {
+            ports.eic(1, atomic, 0);
+            flat.ic(atomic, 0, &inner, 1);
+        }
+        for row in triangle.iter() {
+            if let Some(atomic) = row.first() {
+                ports.eic(1, atomic, 0);
+            }
+        }
+        if let Some(first_triangle_row) = triangle.first() {
+            for from in first_triangle_row.iter() {
+                for to in first_row.iter() {
+                    flat.ic(from, 0, to, 0);
+                }
+            }
+        }
+        for k in 1..triangle.len() {
+            for (j, from) in triangle[k].iter().enumerate() {
+                flat.ic(from, 0, &triangle[k - 1][j + 1], 0);
+            }
+        }
+        ports.eoc(&inner, 0, 0);
+        ports
+    }
+}
+
+impl Component for HOmodModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = HOmodInput;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl xdevs::Coupled for HOmodModel {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.input_port_1.couple(&mut to.inner.input_port_1);
+        for atom_ports in to.first_row.iter_mut() {
+            from.input_port_2.couple(atom_ports);
+        }
+        for row in to.triangle.iter_mut() {
+            if let Some(atom_ports) = row.first_mut() {
+                from.input_port_2.couple(atom_ports);
+            }
+        }
+    }
+
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        for atom_output_ports in from.first_row.iter() {
+            to.inner
+                .input_port_2
+                .add_values(atom_output_ports.get_values());
+        }
+        if let Some(first_triangle_row) = from.triangle.first() {
+            for atom_output_ports in first_triangle_row.iter() {
+                for atom_ports in to.first_row.iter_mut() {
+                    atom_ports.add_values(atom_output_ports.get_values());
+                }
+            }
+        }
+        // Atomic `j` of row `k` is at column `k + j`, i.e., atomic `j + 1` of row `k - 1`
+        for k in 1..from.triangle.len() {
+            for (j, atom_output_ports) in from.triangle[k].iter().enumerate() {
+                to.triangle[k - 1][j + 1].add_values(atom_output_ports.get_values());
+            }
+        }
+    }
+}
+
+/// Builds an HOmod model of the given width and depth.
+/// It is the runtime counterpart of [`generate_homod_box`](crate::generate_homod_box).
+///
+/// # Panics
+///
+/// Panics if `width` or `depth` are zero.
+pub fn build(width: usize, depth: usize, delays: Delays) -> HOmodEnum {
+    assert!(width >= 1, "width must be at least 1");
+    assert!(depth >= 1, "depth must be at least 1");
+
+    let mut model = HOmodEnum::Leaf(LeafModel::with_delays(delays).to_simulator());
+    for _ in 1..depth {
+        let branch = HOmodModel::with_delays(width, Box::new(model), delays);
+        model = HOmodEnum::Branch(branch.to_simulator());
+    }
+    model
+}
+
+/// End model with Generator and HOmod model coupled together
+///
+/// The second input of HOmod models has room for `width - 1` events, as in [`super::homod_box`].
+/// Thus, the generator only feeds it if the width of the model is greater than 1.
+#[xdevs::coupled(reset)]
+pub struct TopModel {
+    generator: JobGenerator,
+    second_generator: Option<JobGenerator>,
+    homod_model: HOmodEnum,
+}
+
+impl TopModel {
+    /// Couples a generator to an HOmod model of the given width.
+    pub fn with_width(generator: JobGenerator, width: usize, homod_model: HOmodEnum) -> Self {
+        let second_generator = (width > 1).then(|| generator.clone());
+        Self::build(generator, second_generator, homod_model)
+    }
+}
+
+impl Component for TopModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl Devstone for TopModel {
+    crate::impl_devstone_top!(homod_model);
+}
+
+impl xdevs::Coupled for TopModel {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.homod_model.input_port_1);
+        to.homod_model
+            .input_port_2
+            .add_values(from.second_generator.get_values());
+    }
+}
+
+/// End model with Generators and a flat HOmod model coupled together
+///
+/// As in [`TopModel`], the second generator only exists if the width of the model is greater than 1.
+#[xdevs::coupled(reset)]
+pub struct FlatTopModel {
+    generator: JobGenerator,
+    second_generator: Option<JobGenerator>,
+    homod_model: Flat<Simulator<DynAtomicModel>, 2, 1>,
+}
+
+impl FlatTopModel {
+    /// Flattens the given HOmod model of the given width and couples a generator to it.
+    pub fn flatten(generator: JobGenerator, width: usize, homod_model: HOmodEnum) -> Self {
+        let second_generator = (width > 1).then(|| generator.clone());
+        Self::build(generator, second_generator, Flat::new(homod_model))
+    }
+}
+
+impl Component for FlatTopModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl Devstone for FlatTopModel {
+    crate::impl_devstone_top!(homod_model);
+}
+
+impl xdevs::Coupled for FlatTopModel {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        to.homod_model[0].add_values(from.generator.get_values());
+        to.homod_model[1].add_values(from.second_generator.get_values());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::devstone::{
+        common::trace,
+        expected::{verify, Topology},
+    };
+    use alloc::vec;
+
+    #[test]
+    fn simulation_matches_expected_counts() {
+        use xdevs::AbstractSimulator;
+
+        let (width, depth) = (6, 5);
+        let model = build(width, depth, Delays::ZERO);
+        let top_model = TopModel::with_width(JobGenerator::new(5), width, model);
+        let mut simulator = top_model.to_simulator();
+        simulator.simulate_vt(&xdevs::Config::new(0.0, 10.0, 1.0, None));
+
+        assert_eq!(
+            verify(&*simulator, Topology::HOmod, width, depth),
+            Ok(Topology::HOmod.expected(width, depth))
+        );
+    }
+
+    #[test]
+    fn leaf_model_contains_single_atomic() {
+        assert_eq!(LeafModel::default().get_n_atomics(), 1);
+    }
+
+    #[test]
+    fn flat_simulation_matches_hierarchical_trace() {
+        let (width, depth) = (6, 5);
+        let mut hierarchical = build(width, depth, Delays::ZERO);
+        let mut flat: Flat<Simulator<DynAtomicModel>, 2, 1> =
+            Flat::new(build(width, depth, Delays::ZERO));
+
+        let expected = trace(
+            &mut hierarchical,
+            3,
+            |input, job| {
+                let _ = input.input_port_1.add_value(job);
+                input.input_port_2.add_value(job);
+            },
+            |output| vec![output.get_values().to_vec()],
+        );
+        let actual = trace(
+            &mut flat,
+            3,
+            |input, job| {
+                input[0].add_value(job);
+                input[1].add_value(job);
+            },
+            |output| vec![output[0].get_values().to_vec()],
+        );
+        assert!(expected.iter().any(|(_, events)| !events[0].is_empty()));
+        assert_eq!(actual, expected);
+
+        let counts = |model: &dyn Devstone| {
+            (
+                model.get_n_internals(),
+                model.get_n_externals(),
+                model.get_n_events(),
+            )
+        };
+        assert_eq!(counts(&flat), counts(&hierarchical));
+    }
+}
@@ -1,8 +1,8 @@
-use super::common::{AtomicModel, Devstone, JobGenerator, LeafModel};
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator, LeafModel};
 use crate::Component;
 
 /// LI model enum (ref version)
-#[crate::to_component]
+#[crate::to_component(reset)]
 pub enum LIEnum<'a, const W: usize> {
     Leaf(LeafModel),
     Branch(LIModel<'a, W>),
@@ -13,7 +13,7 @@ impl<'a, const W: usize> Devstone for LIEnum<'a, W> {
 }
 
 /// LI coupled model (ref version)
-#[crate::coupled]
+#[crate::coupled(reset)]
 pub struct LIModel<'a, const W: usize> {
     atomics: [AtomicModel; W],
     inner: &'a mut LIEnum<'a, W>,
@@ -41,7 +41,14 @@ impl<'a, const W: usize> crate::Coupled for LIModel<'a, W> {
 
 impl<'a, const W: usize> LIModel<'a, W> {
     pub fn new(inner: &'a mut LIEnum<'a, W>) -> Self {
-        Self::build(core::array::from_fn(|_| AtomicModel::default()), inner)
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: &'a mut LIEnum<'a, W>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
     }
 }
 
@@ -50,7 +57,7 @@ impl<'a, const W: usize> Devstone for LIModel<'a, W> {
 }
 
 /// End model with Generator and LI model coupled together (ref version)
-#[crate::coupled]
+#[crate::coupled(reset)]
 pub struct TopModel<'a, const W: usize> {
     generator: JobGenerator,
     li_model: &'a mut LIEnum<'a, W>,
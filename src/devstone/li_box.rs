@@ -1,8 +1,8 @@
-use super::common::{AtomicModel, Devstone, JobGenerator, LeafModel};
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator, LeafModel};
 use alloc::boxed::Box;
 use xdevs::Component;
 
-#[xdevs::to_component]
+#[xdevs::to_component(reset)]
 pub enum LIEnum<const W: usize> {
     Leaf(LeafModel),
     Branch(LIModel<W>),
@@ -13,9 +13,10 @@ impl<const W: usize> Devstone for LIEnum<W> {
 }
 
 /// LI coupled model
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct LIModel<const W: usize> {
     atomics: [AtomicModel; W],
+    #[shared]
     inner: Box<LIEnum<W>>,
 }
 
@@ -30,18 +31,19 @@ impl<const W: usize> xdevs::Coupled for LIModel<W> {
         for atom_ports in to.atomics.iter_mut() {
             let _ = from.couple(atom_ports);
         }
-
-        let _ = from.couple(&mut to.inner);
-    }
-
-    fn eoc(from: &xdevs::ComponentsOutput<Self>, to: &mut Self::Output) {
-        let _ = from.inner.couple(to);
     }
 }
 
 impl<const W: usize> LIModel<W> {
     pub fn new(inner: Box<LIEnum<W>>) -> Self {
-        Self::build(core::array::from_fn(|_| AtomicModel::default()), inner)
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: Box<LIEnum<W>>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
     }
 }
 
@@ -50,7 +52,7 @@ impl<const W: usize> Devstone for LIModel<W> {
 }
 
 /// End model with Generator and LI model coupled together
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct TopModel<const W: usize> {
     generator: JobGenerator,
     li_model: LIEnum<W>,
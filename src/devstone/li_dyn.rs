@@ -0,0 +1,219 @@
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator, LeafModel};
+use alloc::{boxed::Box, vec::Vec};
+use xdevs::{
+    simulation::{
+        flat::{Flat, FlatBuilder, FlatPorts, Flatten},
+        simulator::Simulator,
+    },
+    Component, Simulable,
+};
+
+#[xdevs::to_component(reset)]
+pub enum LIEnum {
+    Leaf(LeafModel),
+    Branch(LIModel),
+}
+
+impl Devstone for LIEnum {
+    crate::impl_devstone_enum!();
+}
+
+impl Flatten<Simulator<AtomicModel>> for LIEnum {
+    crate::impl_flatten_enum!(Simulator<AtomicModel>);
+}
+
+/// LI coupled model with a width only known at runtime
+#[xdevs::coupled(reset)]
+pub struct LIModel {
+    atomics: Vec<AtomicModel>,
+    #[shared]
+    inner: Box<LIEnum>,
+}
+
+impl Component for LIModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl xdevs::Coupled for LIModel {
+    fn eic(from: &Self::Input, to: &mut xdevs::ComponentsInput<Self>) {
+        for atom_ports in to.atomics.iter_mut() {
+            let _ = from.couple(atom_ports);
+        }
+    }
+}
+
+impl LIModel {
+    /// Creates a new LI coupled model with `width - 1` atomic models.
+    pub fn new(width: usize, inner: Box<LIEnum>) -> Self {
+        Self::with_delays(width, inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(width: usize, inner: Box<LIEnum>, delays: Delays) -> Self {
+        Self::build(
+            (1..width)
+                .map(|_| AtomicModel::with_delays(delays))
+                .collect(),
+            inner,
+        )
+    }
+}
+
+impl Devstone for LIModel {
+    crate::impl_devstone_coupled!();
+}
+
+impl Flatten<Simulator<AtomicModel>> for LIModel {
+    fn flatten(self, flat: &mut FlatBuilder<Simulator<AtomicModel>>) -> FlatPorts {
+        let atomics: Vec<_> = (self.components.atomics.into_iter())
+            .map(|atomic| atomic.flatten(flat))
+            .collect();
+        let inner = self.components.inner.flatten(flat);
+
+        let mut ports = FlatPorts::new(1, 1);
+        for atomic in atomics.iter() {
+            ports.eic(0, atomic, 0);
+        }
+        ports.eic(0, &inner, 0);
+        ports.eoc(&inner, 0, 0);
+        ports
+    }
+}
+
+/// Builds an LI model of the given width and depth.
+/// It is the runtime counterpart of [`generate_li_box`](crate::generate_li_box).
+///
+/// # Panics
+///
+/// Panics if `width` or `depth` are zero.
+pub fn build(width: usize, depth: usize, delays: Delays) -> LIEnum {
+    assert!(width >= 1, "width must be at least 1");
+    assert!(depth >= 1, "depth must be at least 1");
+
+    let mut model = LIEnum::Leaf(LeafModel::with_delays(delays).to_simulator());
+    for _ in 1..depth {
+        let branch = LIModel::with_delays(width, Box::new(model), delays);
+        model = LIEnum::Branch(branch.to_simulator());
+    }
+    model
+}
+
+/// End model with Generator and LI model coupled together
+#[xdevs::coupled(reset)]
+pub struct TopModel {
+    generator: JobGenerator,
+    li_model: LIEnum,
+}
+
+impl Component for TopModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = ();
+    type Output = ();
+}
+
+impl Devstone for TopModel {
+    crate::impl_devstone_top!(li_model);
+}
+
+impl xdevs::Coupled for TopModel {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.li_model);
+    }
+}
+
+/// End model with Generator and a flat LI model coupled together
+#[xdevs::coupled(reset)]
+pub struct FlatTopModel {
+    generator: JobGenerator,
+    li_model: Flat<Simulator<AtomicModel>>,
+}
+
+impl FlatTopModel {
+    /// Flattens the given LI model and couples a generator to it.
+    pub fn flatten(generator: JobGenerator, li_model: LIEnum) -> Self {
+        Self::build(generator, Flat::new(li_model))
+    }
+}
+
+impl Component for FlatTopModel {
+    type Kind = xdevs::CoupledKind;
+    type Input = xdevs::Port<usize, 1>;
+    type Output = xdevs::Port<usize, 1>;
+}
+
+impl Devstone for FlatTopModel {
+    crate::impl_devstone_top!(li_model);
+}
+
+impl xdevs::Coupled for FlatTopModel {
+    fn ic(from: &xdevs::ComponentsOutput<Self>, to: &mut xdevs::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.li_model[0]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::devstone::{
+        common::trace,
+        expected::{verify, Topology},
+    };
+    use alloc::vec;
+
+    #[test]
+    fn simulation_matches_expected_counts() {
+        use xdevs::AbstractSimulator;
+
+        let (width, depth) = (10, 10);
+        let top_model = TopModel::build(JobGenerator::new(5), build(width, depth, Delays::ZERO));
+        let mut simulator = top_model.to_simulator();
+        simulator.simulate_vt(&xdevs::Config::new(0.0, 10.0, 1.0, None));
+
+        assert_eq!(
+            verify(&*simulator, Topology::LI, width, depth),
+            Ok(Topology::LI.expected(width, depth))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "width must be at least 1")]
+    fn build_rejects_zero_width() {
+        build(0, 3, Delays::ZERO);
+    }
+
+    #[test]
+    fn flat_simulation_matches_hierarchical_trace() {
+        let (width, depth) = (10, 10);
+        let mut hierarchical = build(width, depth, Delays::ZERO);
+        let mut flat: Flat<Simulator<AtomicModel>> = Flat::new(build(width, depth, Delays::ZERO));
+
+        let expected = trace(
+            &mut hierarchical,
+            3,
+            |input, job| {
+                let _ = input.add_value(job);
+            },
+            |output| vec![output.get_values().to_vec()],
+        );
+        let actual = trace(
+            &mut flat,
+            3,
+            |input, job| {
+                let _ = input[0].add_value(job);
+            },
+            |output| vec![output[0].get_values().to_vec()],
+        );
+        assert!(expected.iter().any(|(_, events)| !events[0].is_empty()));
+        assert_eq!(actual, expected);
+
+        let counts = |model: &dyn Devstone| {
+            (
+                model.get_n_internals(),
+                model.get_n_externals(),
+                model.get_n_events(),
+            )
+        };
+        assert_eq!(counts(&flat), counts(&hierarchical));
+    }
+}
@@ -0,0 +1,107 @@
+use super::common::{AtomicModel, Delays, Devstone, JobGenerator, SharedLeafModel};
+use crate::Component;
+
+/// LI model enum (shared version)
+#[crate::to_component(reset)]
+pub enum LIEnum<'a, const W: usize> {
+    Leaf(SharedLeafModel),
+    Branch(LIModel<'a, W>),
+}
+
+impl<'a, const W: usize> Devstone for LIEnum<'a, W> {
+    crate::impl_devstone_enum!();
+}
+
+/// LI coupled model (shared version)
+#[crate::coupled(reset)]
+pub struct LIModel<'a, const W: usize> {
+    atomics: [AtomicModel; W],
+    #[shared]
+    inner: &'a mut LIEnum<'a, W>,
+}
+
+impl<'a, const W: usize> crate::Component for LIModel<'a, W> {
+    type Kind = crate::CoupledKind;
+    type Input = crate::Port<usize, 1>;
+    type Output = crate::Port<usize, 1>;
+}
+
+impl<'a, const W: usize> crate::Coupled for LIModel<'a, W> {
+    fn eic(from: &Self::Input, to: &mut crate::ComponentsInput<Self>) {
+        for atom_ports in to.atomics.iter_mut() {
+            let _ = from.couple(atom_ports);
+        }
+    }
+}
+
+impl<'a, const W: usize> LIModel<'a, W> {
+    pub fn new(inner: &'a mut LIEnum<'a, W>) -> Self {
+        Self::with_delays(inner, Delays::ZERO)
+    }
+
+    pub fn with_delays(inner: &'a mut LIEnum<'a, W>, delays: Delays) -> Self {
+        Self::build(
+            core::array::from_fn(|_| AtomicModel::with_delays(delays)),
+            inner,
+        )
+    }
+}
+
+impl<'a, const W: usize> Devstone for LIModel<'a, W> {
+    crate::impl_devstone_coupled!();
+}
+
+/// End model with Generator and LI model coupled together (shared version)
+#[crate::coupled(reset)]
+pub struct TopModel<'a, const W: usize> {
+    generator: JobGenerator,
+    li_model: &'a mut LIEnum<'a, W>,
+}
+
+impl<'a, const W: usize> Component for TopModel<'a, W> {
+    type Kind = crate::CoupledKind;
+    type Input = ();
+    type Output = ();
+}
+
+impl<'a, const W: usize> Devstone for TopModel<'a, W> {
+    crate::impl_devstone_top!(li_model);
+}
+
+impl<'a, const W: usize> crate::Coupled for TopModel<'a, W> {
+    fn ic(from: &crate::ComponentsOutput<Self>, to: &mut crate::ComponentsInput<Self>) {
+        let _ = from.generator.couple(&mut to.li_model);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn inner_level_shares_bags() {
+        use crate::footprint::{footprint, StaticFootprint};
+        type Level = LIModel<'static, 4>;
+        type Buffers = <Level as crate::component::coupled::PartialCoupled>::Buffers;
+
+        // Only the atomic models have bags: the inner level uses the bags of its parent
+        let atomic_bags = core::mem::size_of::<[crate::Port<usize, 1>; 4]>();
+        let buffers = <Buffers as StaticFootprint>::FOOTPRINT;
+        assert_eq!(buffers.fields[0].footprint.size, atomic_bags);
+        assert_eq!(buffers.fields[1].footprint.size, atomic_bags);
+        assert_eq!(footprint::<Level>().fields[1].footprint, buffers);
+    }
+
+    #[test]
+    fn leaf_model_shares_bags() {
+        use crate::devstone::common::LeafModel;
+        use crate::footprint::footprint;
+
+        // The reference leaf model keeps an input and an output bag for its atomic model
+        let bags = 2 * core::mem::size_of::<crate::Port<usize, 1>>();
+        assert_eq!(
+            footprint::<SharedLeafModel>().size + bags,
+            footprint::<LeafModel>().size
+        );
+    }
+}
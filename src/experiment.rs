@@ -0,0 +1,531 @@
+//! Monte Carlo replications and parameter sweeps (`std` feature).
+//!
+//! An [`Experiment`] simulates a model for every point of a design (e.g., a [`grid`] of parameters
+//! or a [`random`] sample) several times with different seeds. The simulations run in parallel,
+//! and a user-defined function extracts metrics from every simulated model.
+//! [`Results`] summarize the metrics of every point with their mean and confidence interval,
+//! and can be written as CSV.
+//!
+//! Replication `r` uses the same seed for every point of the design (common random numbers).
+//! Thus, differences between points are not due to different random streams.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use xdevs::{experiment::Experiment, gpt::*, Config};
+//!
+//! let results = Experiment::new(Config::new(0.0, 100.0, 1.0, None))
+//!     .replications(30)
+//!     .seed(42)
+//!     .run(
+//!         [1.0, 2.0, 3.0, 4.0],
+//!         |&period, _seed| {
+//!             GPT::build(Generator::new(period), Processor::new(3.0), Transducer::new(100.0))
+//!         },
+//!         |gpt| [gpt.transducer().throughput()],
+//!     );
+//! results.write_csv(std::io::stdout(), &["period"], ["throughput"])?;
+//! ```
+
+use crate::simulation::{AbstractSimulator, Config, SimpleSimulable};
+use std::{
+    format, io,
+    num::NonZeroUsize,
+    string::{String, ToString},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread, vec,
+    vec::Vec,
+};
+
+/// Pseudo-random number generator (SplitMix64) for seeding models and sampling designs.
+///
+/// It is not cryptographically secure, but it is fast and produces the same sequence on every target.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Creates a new generator with the given seed.
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Returns the next random integer.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number uniformly distributed in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random number uniformly distributed in `[low, high)`.
+    pub fn range(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+}
+
+/// Full factorial design with every combination of `xs` and `ys`.
+/// Nest it (e.g., `grid(grid(xs, ys), zs)`) for more parameters.
+pub fn grid<X: Clone, Y: Clone>(
+    xs: impl IntoIterator<Item = X>,
+    ys: impl IntoIterator<Item = Y>,
+) -> Vec<(X, Y)> {
+    let ys: Vec<Y> = ys.into_iter().collect();
+    xs.into_iter()
+        .flat_map(|x| ys.iter().map(move |y| (x.clone(), y.clone())))
+        .collect()
+}
+
+/// Random design with `n` points sampled by `sample`.
+pub fn random<P>(n: usize, seed: u64, mut sample: impl FnMut(&mut Rng) -> P) -> Vec<P> {
+    let mut rng = Rng::new(seed);
+    (0..n).map(|_| sample(&mut rng)).collect()
+}
+
+/// Configuration of Monte Carlo experiments.
+#[derive(Debug, Clone)]
+pub struct Experiment {
+    config: Config,
+    replications: usize,
+    seed: u64,
+    threads: usize,
+}
+
+impl Experiment {
+    /// Creates a new experiment that simulates models with the given configuration.
+    /// By default, every point is simulated once with seed 0, using all the available cores.
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            replications: 1,
+            seed: 0,
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    /// Sets the number of replications of every point of the design.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `replications` is zero.
+    pub fn replications(mut self, replications: usize) -> Self {
+        assert!(replications > 0, "replications must be at least 1");
+        self.replications = replications;
+        self
+    }
+
+    /// Sets the seed from which the seeds of the replications are derived.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the number of threads that run simulations in parallel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn threads(mut self, threads: usize) -> Self {
+        assert!(threads > 0, "threads must be at least 1");
+        self.threads = threads;
+        self
+    }
+
+    /// Seeds of the replications, which are the same for every point of the design.
+    pub fn seeds(&self) -> Vec<u64> {
+        let mut rng = Rng::new(self.seed);
+        (0..self.replications).map(|_| rng.next_u64()).collect()
+    }
+
+    /// Runs the experiment for every point of the `design`.
+    ///
+    /// For every point and replication, `build` creates the model from the parameters and the seed
+    /// of the replication. Once the simulation is over, `metrics` extracts `K` metrics from the
+    /// simulated model. Results do not depend on the number of threads.
+    pub fn run<P, M, const K: usize>(
+        &self,
+        design: impl IntoIterator<Item = P>,
+        build: impl Fn(&P, u64) -> M + Sync,
+        metrics: impl Fn(&M::Simulator) -> [f64; K] + Sync,
+    ) -> Results<P, K>
+    where
+        P: Sync,
+        M: SimpleSimulable,
+    {
+        let design: Vec<P> = design.into_iter().collect();
+        let seeds = self.seeds();
+        let n_jobs = design.len() * seeds.len();
+        let next_job = AtomicUsize::new(0);
+
+        // Workers pick the next job until there are no jobs left
+        let worker = || {
+            let mut samples = Vec::new();
+            loop {
+                let job = next_job.fetch_add(1, Ordering::Relaxed);
+                if job >= n_jobs {
+                    return samples;
+                }
+                let (point, replication) = (job / seeds.len(), job % seeds.len());
+                let mut simulator = build(&design[point], seeds[replication]).to_simulator();
+                simulator.simulate_vt(&self.config);
+                samples.push((job, metrics(&simulator)));
+            }
+        };
+        let mut samples = vec![[f64::NAN; K]; n_jobs];
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads.min(n_jobs))
+                .map(|_| scope.spawn(worker))
+                .collect();
+            for worker in workers {
+                for (job, sample) in worker.join().unwrap() {
+                    samples[job] = sample;
+                }
+            }
+        });
+
+        let mut samples = samples.chunks(seeds.len().max(1));
+        let points = design
+            .into_iter()
+            .map(|params| Point {
+                params,
+                samples: samples.next().unwrap_or_default().to_vec(),
+            })
+            .collect();
+        Results { points }
+    }
+}
+
+/// Summary statistics of the samples of a metric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    /// Number of samples.
+    pub n: usize,
+    /// Sample mean.
+    pub mean: f64,
+    /// Sample standard deviation. It is NaN for less than two samples.
+    pub std_dev: f64,
+    /// Half-width of the 95% confidence interval of the mean (Student's t).
+    /// It is NaN for less than two samples.
+    pub ci95: f64,
+}
+
+impl Summary {
+    /// Computes the summary statistics of the given samples.
+    pub fn new(samples: &[f64]) -> Self {
+        let n = samples.len();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance =
+            samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n as f64 - 1.0);
+        let std_dev = if n > 1 { variance.sqrt() } else { f64::NAN };
+        let ci95 = t_975(n.saturating_sub(1)) * std_dev / (n as f64).sqrt();
+        Self {
+            n,
+            mean,
+            std_dev,
+            ci95,
+        }
+    }
+
+    /// Lower bound of the 95% confidence interval of the mean.
+    pub fn lower(&self) -> f64 {
+        self.mean - self.ci95
+    }
+
+    /// Upper bound of the 95% confidence interval of the mean.
+    pub fn upper(&self) -> f64 {
+        self.mean + self.ci95
+    }
+}
+
+/// 0.975 quantile of Student's t distribution with `df` degrees of freedom.
+fn t_975(df: usize) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+        2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+        2.052, 2.048, 2.045, 2.042,
+    ];
+    match df {
+        0 => f64::NAN,
+        1..=30 => TABLE[df - 1],
+        // Cornish-Fisher expansion around the normal quantile
+        _ => {
+            const Z: f64 = 1.959_964;
+            Z + (Z * Z * Z + Z) / (4.0 * df as f64)
+        }
+    }
+}
+
+/// Point of the design and the metrics of its replications.
+#[derive(Debug, Clone)]
+pub struct Point<P, const K: usize> {
+    /// Parameters of the point.
+    pub params: P,
+    /// Metrics of every replication, in replication order.
+    pub samples: Vec<[f64; K]>,
+}
+
+impl<P, const K: usize> Point<P, K> {
+    /// Summary statistics of every metric.
+    pub fn summary(&self) -> [Summary; K] {
+        core::array::from_fn(|k| {
+            let samples: Vec<f64> = self.samples.iter().map(|sample| sample[k]).collect();
+            Summary::new(&samples)
+        })
+    }
+}
+
+/// Results of an [`Experiment`], in design order.
+#[derive(Debug, Clone)]
+pub struct Results<P, const K: usize> {
+    points: Vec<Point<P, K>>,
+}
+
+impl<P, const K: usize> Results<P, K> {
+    /// Points of the design with their metrics.
+    pub fn points(&self) -> &[Point<P, K>] {
+        &self.points
+    }
+
+    /// Writes the summary of every point as CSV.
+    ///
+    /// There is one column per parameter, the number of replications, and the mean, standard
+    /// deviation and half-width of the 95% confidence interval of every metric
+    /// (e.g., `throughput_mean`, `throughput_std` and `throughput_ci95`).
+    ///
+    /// It returns an [`io::ErrorKind::InvalidInput`] error if the number of parameter names
+    /// does not match the number of parameters.
+    pub fn write_csv(
+        &self,
+        mut writer: impl io::Write,
+        params: &[&str],
+        metrics: [&str; K],
+    ) -> io::Result<()>
+    where
+        P: Params,
+    {
+        let mut header: Vec<String> = params.iter().map(|name| name.to_string()).collect();
+        header.push("replications".to_string());
+        for metric in metrics {
+            for stat in ["mean", "std", "ci95"] {
+                header.push(format!("{metric}_{stat}"));
+            }
+        }
+        writeln!(writer, "{}", header.join(","))?;
+
+        for point in &self.points {
+            let mut row = point.params.columns();
+            if row.len() != params.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "expected {} parameter names, got {}",
+                        row.len(),
+                        params.len()
+                    ),
+                ));
+            }
+            row.push(point.samples.len().to_string());
+            for summary in point.summary() {
+                row.extend([summary.mean, summary.std_dev, summary.ci95].map(|x| x.to_string()));
+            }
+            writeln!(writer, "{}", row.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of a design point that can be written as CSV columns.
+pub trait Params {
+    /// Values of the parameters, one per column.
+    fn columns(&self) -> Vec<String>;
+}
+
+macro_rules! impl_params_for_scalar {
+    ($($T:ty),+) => {
+        $(impl Params for $T {
+            fn columns(&self) -> Vec<String> {
+                vec![self.to_string()]
+            }
+        })+
+    }
+}
+
+impl_params_for_scalar!(
+    f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, bool, char, String,
+    &str
+);
+
+macro_rules! impl_params_for_tuple {
+    ($($idx:tt => $T:ident),+) => {
+        impl<$($T: Params),+> Params for ($($T,)+) {
+            fn columns(&self) -> Vec<String> {
+                let mut columns = Vec::new();
+                $(columns.extend(self.$idx.columns());)+
+                columns
+            }
+        }
+    }
+}
+
+impl_params_for_tuple!(0 => T0);
+impl_params_for_tuple!(0 => T0, 1 => T1);
+impl_params_for_tuple!(0 => T0, 1 => T1, 2 => T2);
+impl_params_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3);
+impl_params_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4);
+impl_params_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gpt::{Generator, Processor, Transducer, GPT},
+        Atomic, AtomicKind, Component,
+    };
+
+    /// Atomic model with uniformly distributed times between internal transitions.
+    struct Noisy {
+        rng: Rng,
+        sigma: f64,
+        mean: f64,
+        count: usize,
+    }
+
+    impl Noisy {
+        fn new(mean: f64, seed: u64) -> Self {
+            let mut rng = Rng::new(seed);
+            Self {
+                sigma: rng.range(0.0, 2.0 * mean),
+                rng,
+                mean,
+                count: 0,
+            }
+        }
+    }
+
+    impl Component for Noisy {
+        type Kind = AtomicKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Atomic for Noisy {
+        fn delta_int(&mut self) {
+            self.count += 1;
+            self.sigma = self.rng.range(0.0, 2.0 * self.mean);
+        }
+        fn delta_ext(&mut self, _elapsed: f64, _input: &Self::Input) {}
+        fn lambda(&self, _output: &mut Self::Output) {}
+        fn ta(&self) -> f64 {
+            self.sigma
+        }
+    }
+
+    fn noisy_experiment(threads: usize) -> Results<f64, 1> {
+        Experiment::new(Config::new(0.0, 100.0, 1.0, None))
+            .replications(8)
+            .seed(7)
+            .threads(threads)
+            .run(
+                [1.0, 2.0, 4.0],
+                |&mean, seed| Noisy::new(mean, seed),
+                |noisy| [noisy.count as f64],
+            )
+    }
+
+    #[test]
+    fn rng_is_uniform() {
+        let samples = random(10_000, 1, |rng| rng.next_f64());
+        assert!(samples.iter().all(|x| (0.0..1.0).contains(x)));
+        let summary = Summary::new(&samples);
+        assert!((summary.mean - 0.5).abs() < 0.02);
+        assert_ne!(samples, random(10_000, 2, |rng| rng.next_f64()));
+    }
+
+    #[test]
+    fn grid_combines_all_values() {
+        assert_eq!(
+            grid([1, 2], ['a', 'b', 'c']),
+            [(1, 'a'), (1, 'b'), (1, 'c'), (2, 'a'), (2, 'b'), (2, 'c')]
+        );
+    }
+
+    #[test]
+    fn summary_of_samples() {
+        let summary = Summary::new(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(summary.n, 5);
+        assert_eq!(summary.mean, 3.0);
+        assert!((summary.std_dev - 2.5f64.sqrt()).abs() < 1e-12);
+        assert!((summary.ci95 - 2.776 * 2.5f64.sqrt() / 5f64.sqrt()).abs() < 1e-12);
+        assert!(summary.lower() < 3.0 && summary.upper() > 3.0);
+
+        let single = Summary::new(&[1.0]);
+        assert_eq!(single.mean, 1.0);
+        assert!(single.std_dev.is_nan() && single.ci95.is_nan());
+        assert!((t_975(1000) - 1.962).abs() < 1e-3);
+    }
+
+    #[test]
+    fn replications_are_reproducible() {
+        let results = noisy_experiment(1);
+        assert_eq!(results.points().len(), 3);
+        for (point, parallel) in results.points().iter().zip(noisy_experiment(4).points()) {
+            assert_eq!(point.samples, parallel.samples, "threads do not matter");
+            assert_eq!(point.samples.len(), 8);
+        }
+
+        let [short, _, long] = [0, 1, 2].map(|i| results.points()[i].summary()[0]);
+        assert!(short.std_dev > 0.0, "replications use different seeds");
+        assert!(short.lower() > long.upper(), "shorter periods, more events");
+    }
+
+    #[test]
+    fn gpt_sweep_writes_csv() {
+        let results = Experiment::new(Config::new(0.0, 100.0, 1.0, None))
+            .replications(3)
+            .run(
+                grid([1.0, 2.0], [3.0]),
+                |&(period, time), _seed| {
+                    GPT::build(
+                        Generator::new(period),
+                        Processor::new(time),
+                        Transducer::new(50.0),
+                    )
+                },
+                |gpt| {
+                    let transducer = gpt.transducer();
+                    [transducer.acceptance(), transducer.throughput()]
+                },
+            );
+
+        let mut csv = Vec::new();
+        results
+            .write_csv(&mut csv, &["period", "time"], ["acceptance", "throughput"])
+            .unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "period,time,replications,acceptance_mean,acceptance_std,acceptance_ci95,\
+                 throughput_mean,throughput_std,throughput_ci95"
+            )
+        );
+        let row: Vec<&str> = lines.next().unwrap().split(',').collect();
+        assert_eq!(row[..3], ["1", "3", "3"]);
+        assert_eq!(row[5], "0", "deterministic models have no variance");
+        let throughput: f64 = row[6].parse().unwrap();
+        assert!((throughput - 1.0 / 3.0).abs() < 0.05);
+        assert!(lines.next().unwrap().starts_with("2,3,3,"));
+        assert_eq!(lines.next(), None);
+
+        let error = results
+            .write_csv(io::sink(), &["period"], ["acceptance", "throughput"])
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -4,6 +4,9 @@ mod embassy;
 #[cfg(feature = "embassy")]
 pub use embassy::*;
 
+#[cfg(feature = "portable")]
+pub mod portable;
+
 #[cfg(feature = "std")]
 mod tokio;
 
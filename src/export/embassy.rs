@@ -1,4 +1,5 @@
 use crate::rt_engine::{sealed::Sealed, RtEngineInputChannel, RtEngineOutputChannel};
+use crate::transport::{EventSink, EventSource};
 use core::convert::Infallible;
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex as Mutex;
 
@@ -29,6 +30,13 @@ impl<'a, I, const N: usize> Sender<'a, I, N> {
     }
 }
 
+impl<'a, I, const N: usize> EventSink<I> for Sender<'a, I, N> {
+    async fn send_event(&self, event: I) -> bool {
+        self.sender.send(event).await;
+        true
+    }
+}
+
 #[repr(transparent)]
 pub struct Receiver<'a, O: Clone, const CAP: usize, const SUBS: usize> {
     subscriber: embassy_sync::pubsub::Subscriber<'a, Mutex, O, CAP, SUBS, 1>,
@@ -44,6 +52,18 @@ impl<'a, O: Clone, const CAP: usize, const SUBS: usize> Receiver<'a, O, CAP, SUB
     }
 }
 
+impl<'a, O: Clone, const CAP: usize, const SUBS: usize> EventSource<O>
+    for Receiver<'a, O, CAP, SUBS>
+{
+    async fn recv_event(&mut self) -> Option<O> {
+        loop {
+            if let Ok(event) = self.recv().await {
+                return Some(event);
+            }
+        }
+    }
+}
+
 #[repr(transparent)]
 pub struct InputChannel<'a, I, const N: usize> {
     channel: &'a Channel<I, N>,
@@ -89,6 +109,7 @@ impl<'a, O: Clone, const CAP: usize, const SUBS: usize> RtEngineOutputChannel
 {
     type Output = O;
     type Receiver = Receiver<'a, Self::Output, CAP, SUBS>;
+    type SubscribeError = SubscribeError;
 
     fn receiver(&self) -> Result<Self::Receiver, SubscribeError> {
         match self.channel.subscriber() {
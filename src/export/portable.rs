@@ -0,0 +1,733 @@
+//! Executor-agnostic backend built only on [`core::future`] and lock-free queues.
+//!
+//! It does not depend on any executor, so it works with smol, async-std, RTIC or custom
+//! bare-metal executors, and it can be enabled together with the `std` or `embassy` backends.
+//! Select it with `#[rt_engine(backend = portable)]`.
+//!
+//! Channels are meant to be placed in `static` variables (the `rt_engine` macro does it for you).
+//! Their capacities must be powers of two, and the target must support atomic compare-and-swap.
+
+use crate::rt_engine::{sealed::Sealed, RtEngineInputChannel, RtEngineOutputChannel};
+use crate::transport::{EventSink, EventSource};
+use atomic_waker::AtomicWaker;
+use core::{
+    cell::UnsafeCell,
+    convert::Infallible,
+    future::poll_fn,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+    task::Poll,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecvError {
+    Lagged(u64),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SubscribeError {
+    MaximumSubscribersReached,
+}
+
+/// Bounded lock-free multi-producer, multi-consumer queue.
+///
+/// It implements Dmitry Vyukov's bounded MPMC queue. `N` must be a power of two.
+/// Sequence numbers are doubled so that the queue also works with a single slot:
+/// the slot for position `pos` is free when its sequence is `2 * pos`, and ready to be read when it is `2 * pos + 1`.
+pub struct Queue<T, const N: usize> {
+    slots: [Slot<T>; N],
+    /// Position of the next element to dequeue.
+    head: AtomicUsize,
+    /// Position of the next element to enqueue.
+    tail: AtomicUsize,
+}
+
+struct Slot<T> {
+    /// Sequence number of the slot. It tells whether the slot is ready to be written or read.
+    seq: AtomicUsize,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// SAFETY: access to the slots is synchronized via their sequence numbers
+unsafe impl<T: Send, const N: usize> Sync for Queue<T, N> {}
+
+impl<T, const N: usize> Queue<T, N> {
+    pub const fn new() -> Self {
+        const { assert!(N.is_power_of_two(), "queue capacity must be a power of two") };
+
+        let mut slots = [const {
+            Slot {
+                seq: AtomicUsize::new(0),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            }
+        }; N];
+        let mut i = 0;
+        while i < N {
+            slots[i].seq = AtomicUsize::new(2 * i);
+            i += 1;
+        }
+        Self {
+            slots,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Adds a value to the queue. It returns the value back if the queue is full.
+    pub fn enqueue(&self, value: T) -> Result<(), T> {
+        let mut pos = self.tail.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos & (N - 1)];
+            let seq = slot.seq.load(Ordering::Acquire);
+            match (seq.wrapping_sub(pos.wrapping_mul(2)) as isize).signum() {
+                0 => match self.tail.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: the slot is reserved for this producer until `seq` is updated
+                        unsafe { (*slot.value.get()).write(value) };
+                        slot.seq
+                            .store(pos.wrapping_mul(2).wrapping_add(1), Ordering::Release);
+                        return Ok(());
+                    }
+                    Err(current) => pos = current,
+                },
+                // The slot has not been read yet: the queue is full
+                -1 => return Err(value),
+                // Another producer took the slot
+                _ => pos = self.tail.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Removes the oldest value of the queue. It returns `None` if the queue is empty.
+    pub fn dequeue(&self) -> Option<T> {
+        let mut pos = self.head.load(Ordering::Relaxed);
+        loop {
+            let slot = &self.slots[pos & (N - 1)];
+            let seq = slot.seq.load(Ordering::Acquire);
+            match (seq.wrapping_sub(pos.wrapping_mul(2).wrapping_add(1)) as isize).signum() {
+                0 => match self.head.compare_exchange_weak(
+                    pos,
+                    pos.wrapping_add(1),
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => {
+                        // SAFETY: the slot was written by a producer and is reserved for this consumer
+                        let value = unsafe { (*slot.value.get()).assume_init_read() };
+                        slot.seq
+                            .store(pos.wrapping_add(N).wrapping_mul(2), Ordering::Release);
+                        return Some(value);
+                    }
+                    Err(current) => pos = current,
+                },
+                // The slot has not been written yet: the queue is empty
+                -1 => return None,
+                // Another consumer took the slot
+                _ => pos = self.head.load(Ordering::Relaxed),
+            }
+        }
+    }
+
+    /// Removes all the values of the queue.
+    fn clear(&self) {
+        while self.dequeue().is_some() {}
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for Queue<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Multi-producer, single-consumer channel for input events.
+///
+/// Senders wait for room in the channel without polling: the receiver wakes the blocked sender
+/// after every received message. Only one blocked sender is registered at a time, so a sender that
+/// replaces another one wakes it, and they take turns until the channel has room.
+pub struct Channel<T, const N: usize> {
+    queue: Queue<T, N>,
+    /// Waker of the receiver, woken when a message is sent.
+    waker: AtomicWaker,
+    /// Waker of a blocked sender, woken when a message is received.
+    send_waker: AtomicWaker,
+}
+
+impl<T, const N: usize> Channel<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            queue: Queue::new(),
+            waker: AtomicWaker::new(),
+            send_waker: AtomicWaker::new(),
+        }
+    }
+
+    fn try_send(&self, msg: T) -> Result<(), T> {
+        self.queue.enqueue(msg)?;
+        self.waker.wake();
+        Ok(())
+    }
+
+    async fn send(&self, msg: T) {
+        let mut msg = Some(msg);
+        poll_fn(|cx| {
+            let m = match self.try_send(msg.take().unwrap()) {
+                Ok(()) => return Poll::Ready(()),
+                Err(m) => m,
+            };
+            // The channel is full: wait until the receiver takes a message.
+            // Another blocked sender may be registered, so it is woken to avoid losing it
+            if let Some(other) = self.send_waker.take() {
+                if !other.will_wake(cx.waker()) {
+                    other.wake();
+                }
+            }
+            self.send_waker.register(cx.waker());
+            // A message may have been received before registering the waker
+            match self.try_send(m) {
+                Ok(()) => Poll::Ready(()),
+                Err(m) => {
+                    msg = Some(m);
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    fn try_receive(&self) -> Option<T> {
+        let msg = self.queue.dequeue()?;
+        self.send_waker.wake();
+        Some(msg)
+    }
+
+    async fn receive(&self) -> T {
+        poll_fn(|cx| {
+            if let Some(msg) = self.try_receive() {
+                return Poll::Ready(msg);
+            }
+            self.waker.register(cx.waker());
+            // A message may have arrived before registering the waker
+            match self.try_receive() {
+                Some(msg) => Poll::Ready(msg),
+                None => Poll::Pending,
+            }
+        })
+        .await
+    }
+}
+
+impl<T, const N: usize> Default for Channel<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Broadcast channel for output events with up to `SUBS` subscribers.
+///
+/// Every subscriber has its own queue of `CAP` events. When it is full, the oldest event is dropped.
+pub struct PubSubChannel<T, const CAP: usize, const SUBS: usize> {
+    subscriptions: [Subscription<T, CAP>; SUBS],
+}
+
+/// The subscription is not in use.
+const FREE: u8 = 0;
+/// A subscriber claimed the subscription, but it does not receive events yet.
+const CLAIMED: u8 = 1;
+/// The subscription receives events.
+const ACTIVE: u8 = 2;
+
+struct Subscription<T, const CAP: usize> {
+    state: AtomicU8,
+    queue: Queue<T, CAP>,
+    lagged: AtomicUsize,
+    waker: AtomicWaker,
+}
+
+impl<T, const CAP: usize> Subscription<T, CAP> {
+    const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(FREE),
+            queue: Queue::new(),
+            lagged: AtomicUsize::new(0),
+            waker: AtomicWaker::new(),
+        }
+    }
+
+    fn reset(&self) {
+        self.queue.clear();
+        self.lagged.store(0, Ordering::Relaxed);
+    }
+}
+
+impl<T, const CAP: usize, const SUBS: usize> PubSubChannel<T, CAP, SUBS> {
+    pub const fn new() -> Self {
+        Self {
+            subscriptions: [const { Subscription::new() }; SUBS],
+        }
+    }
+
+    fn subscribe(&self) -> Result<usize, SubscribeError> {
+        let index = self
+            .subscriptions
+            .iter()
+            .position(|sub| {
+                sub.state
+                    .compare_exchange(FREE, CLAIMED, Ordering::AcqRel, Ordering::Relaxed)
+                    .is_ok()
+            })
+            .ok_or(SubscribeError::MaximumSubscribersReached)?;
+        // The queue is cleared before publishers see the subscription, so no event is lost
+        let sub = &self.subscriptions[index];
+        sub.reset();
+        sub.state.store(ACTIVE, Ordering::Release);
+        Ok(index)
+    }
+
+    fn unsubscribe(&self, index: usize) {
+        let sub = &self.subscriptions[index];
+        sub.state.store(CLAIMED, Ordering::Release);
+        sub.reset();
+        sub.state.store(FREE, Ordering::Release);
+    }
+}
+
+impl<T: Clone, const CAP: usize, const SUBS: usize> PubSubChannel<T, CAP, SUBS> {
+    fn publish(&self, msg: T) {
+        for sub in &self.subscriptions {
+            if sub.state.load(Ordering::Acquire) != ACTIVE {
+                continue;
+            }
+            let mut msg = msg.clone();
+            while let Err(m) = sub.queue.enqueue(msg) {
+                if sub.queue.dequeue().is_some() {
+                    sub.lagged.fetch_add(1, Ordering::Relaxed);
+                }
+                msg = m;
+            }
+            sub.waker.wake();
+        }
+    }
+}
+
+impl<T, const CAP: usize, const SUBS: usize> Default for PubSubChannel<T, CAP, SUBS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Simplified Senders/Subscribers
+pub struct Sender<'a, I, const N: usize> {
+    channel: &'a Channel<I, N>,
+}
+
+impl<'a, I, const N: usize> Clone for Sender<'a, I, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, I, const N: usize> Copy for Sender<'a, I, N> {}
+
+impl<'a, I, const N: usize> Sender<'a, I, N> {
+    pub async fn send(&self, msg: I) -> Result<(), Infallible> {
+        self.channel.send(msg).await;
+        Ok(())
+    }
+
+    /// Sends a message without waiting. It returns the message back if the channel is full.
+    ///
+    /// It never blocks, so it can be used from interrupt handlers.
+    pub fn try_send(&self, msg: I) -> Result<(), I> {
+        self.channel.try_send(msg)
+    }
+}
+
+impl<'a, I, const N: usize> EventSink<I> for Sender<'a, I, N> {
+    async fn send_event(&self, event: I) -> bool {
+        self.channel.send(event).await;
+        true
+    }
+}
+
+pub struct Receiver<'a, O, const CAP: usize, const SUBS: usize> {
+    channel: &'a PubSubChannel<O, CAP, SUBS>,
+    index: usize,
+}
+
+impl<'a, O, const CAP: usize, const SUBS: usize> Receiver<'a, O, CAP, SUBS> {
+    pub async fn recv(&mut self) -> Result<O, RecvError> {
+        let sub = &self.channel.subscriptions[self.index];
+        poll_fn(|cx| {
+            let lagged = sub.lagged.swap(0, Ordering::Relaxed);
+            if lagged > 0 {
+                return Poll::Ready(Err(RecvError::Lagged(lagged as u64)));
+            }
+            if let Some(msg) = sub.queue.dequeue() {
+                return Poll::Ready(Ok(msg));
+            }
+            sub.waker.register(cx.waker());
+            // A message may have arrived before registering the waker
+            match sub.queue.dequeue() {
+                Some(msg) => Poll::Ready(Ok(msg)),
+                None => Poll::Pending,
+            }
+        })
+        .await
+    }
+}
+
+impl<'a, O, const CAP: usize, const SUBS: usize> Drop for Receiver<'a, O, CAP, SUBS> {
+    fn drop(&mut self) {
+        self.channel.unsubscribe(self.index);
+    }
+}
+
+impl<'a, O, const CAP: usize, const SUBS: usize> EventSource<O> for Receiver<'a, O, CAP, SUBS> {
+    async fn recv_event(&mut self) -> Option<O> {
+        loop {
+            if let Ok(event) = self.recv().await {
+                return Some(event);
+            }
+        }
+    }
+}
+
+#[repr(transparent)]
+pub struct InputChannel<'a, I, const N: usize> {
+    channel: &'a Channel<I, N>,
+}
+
+impl<'a, I, const N: usize> InputChannel<'a, I, N> {
+    pub fn new(channel: &'a Channel<I, N>) -> Self {
+        Self { channel }
+    }
+}
+
+impl<'a, I: Send, const N: usize> RtEngineInputChannel for InputChannel<'a, I, N> {
+    type Input = I;
+    type Sender = Sender<'a, I, N>;
+
+    fn sender(&self) -> Self::Sender {
+        Sender {
+            channel: self.channel,
+        }
+    }
+
+    async fn recv(&mut self) -> Self::Input {
+        self.channel.receive().await
+    }
+}
+
+impl<'a, I, const N: usize> Sealed for InputChannel<'a, I, N> {}
+
+#[repr(transparent)]
+pub struct OutputChannel<'a, O, const CAP: usize, const SUBS: usize> {
+    channel: &'a PubSubChannel<O, CAP, SUBS>,
+}
+
+impl<'a, O, const CAP: usize, const SUBS: usize> OutputChannel<'a, O, CAP, SUBS> {
+    pub fn new(channel: &'a PubSubChannel<O, CAP, SUBS>) -> Self {
+        Self { channel }
+    }
+}
+
+impl<'a, O: Clone, const CAP: usize, const SUBS: usize> RtEngineOutputChannel
+    for OutputChannel<'a, O, CAP, SUBS>
+{
+    type Output = O;
+    type Receiver = Receiver<'a, Self::Output, CAP, SUBS>;
+    type SubscribeError = SubscribeError;
+
+    fn receiver(&self) -> Result<Self::Receiver, SubscribeError> {
+        let index = self.channel.subscribe()?;
+        Ok(Receiver {
+            channel: self.channel,
+            index,
+        })
+    }
+
+    fn publish(&self, output: Self::Output) {
+        self.channel.publish(output);
+    }
+}
+
+impl<'a, O, const CAP: usize, const SUBS: usize> Sealed for OutputChannel<'a, O, CAP, SUBS> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_fifo() {
+        let queue = Queue::<u32, 4>::new();
+        assert_eq!(queue.dequeue(), None);
+        for i in 0..4 {
+            assert_eq!(queue.enqueue(i), Ok(()));
+        }
+        assert_eq!(queue.enqueue(4), Err(4), "queue is full");
+        for i in 0..4 {
+            assert_eq!(queue.dequeue(), Some(i));
+        }
+        assert_eq!(queue.dequeue(), None, "queue is empty");
+
+        // Positions wrap around the slots
+        let queue = Queue::<u32, 1>::new();
+        for i in 0..10 {
+            assert_eq!(queue.enqueue(i), Ok(()));
+            assert_eq!(queue.enqueue(i), Err(i));
+            assert_eq!(queue.dequeue(), Some(i));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn queue_concurrent() {
+        extern crate std;
+        const PRODUCERS: u32 = 4;
+        const EVENTS: u32 = 10_000;
+
+        static QUEUE: Queue<u32, 8> = Queue::new();
+        let producers: std::vec::Vec<_> = (0..PRODUCERS)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    for i in 1..=EVENTS {
+                        while QUEUE.enqueue(i).is_err() {
+                            std::thread::yield_now();
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        let (mut received, mut sum) = (0, 0_u64);
+        while received < PRODUCERS * EVENTS {
+            match QUEUE.dequeue() {
+                Some(i) => {
+                    received += 1;
+                    sum += i as u64;
+                }
+                None => std::thread::yield_now(),
+            }
+        }
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        let expected = PRODUCERS as u64 * (EVENTS as u64 * (EVENTS as u64 + 1) / 2);
+        assert_eq!(sum, expected, "no event is lost or duplicated");
+        assert_eq!(QUEUE.dequeue(), None);
+    }
+
+    #[test]
+    fn subscriptions_receive_events_once_active() {
+        let channel = PubSubChannel::<u32, 4, 1>::new();
+        let sub = &channel.subscriptions[0];
+        sub.state.store(CLAIMED, Ordering::Release);
+        channel.publish(1);
+        assert_eq!(sub.queue.dequeue(), None, "claimed subscription is skipped");
+        sub.state.store(FREE, Ordering::Release);
+
+        let index = channel.subscribe().unwrap();
+        channel.publish(2);
+        assert_eq!(channel.subscriptions[index].queue.dequeue(), Some(2));
+        channel.unsubscribe(index);
+        assert_eq!(sub.state.load(Ordering::Acquire), FREE);
+    }
+
+    #[tokio::test]
+    async fn input_channel() {
+        static CHANNEL: Channel<u32, 2> = Channel::new();
+        let mut input = InputChannel::new(&CHANNEL);
+        let sender = input.sender();
+
+        assert_eq!(sender.try_send(1), Ok(()));
+        assert_eq!(sender.try_send(2), Ok(()));
+        assert_eq!(sender.try_send(3), Err(3), "channel is full");
+        let task = tokio::spawn(async move { sender.send(3).await });
+        assert_eq!(input.recv().await, 1);
+        assert_eq!(input.recv().await, 2);
+        assert_eq!(input.recv().await, 3, "blocked sender resumes");
+        task.await.unwrap().unwrap();
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn blocked_senders_wait_for_room() {
+        extern crate std;
+        use core::{future::Future, pin::pin, task::Context};
+        use std::{
+            sync::{atomic::AtomicUsize, Arc},
+            task::Wake,
+        };
+
+        struct CountWaker(AtomicUsize);
+
+        impl Wake for CountWaker {
+            fn wake(self: Arc<Self>) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let channel = Channel::<u32, 1>::new();
+        assert_eq!(channel.try_send(1), Ok(()));
+        let wakers = [(); 2].map(|_| Arc::new(CountWaker(AtomicUsize::new(0))));
+        let mut a = pin!(channel.send(2));
+        let mut b = pin!(channel.send(3));
+        let woken = |i: usize| wakers[i].0.load(Ordering::SeqCst);
+        let poll = |fut: core::pin::Pin<&mut _>, i: usize| {
+            let waker = wakers[i].clone().into();
+            Future::poll(fut, &mut Context::from_waker(&waker))
+        };
+
+        assert!(poll(a.as_mut(), 0).is_pending());
+        assert!(poll(a.as_mut(), 0).is_pending());
+        assert_eq!(woken(0), 0, "a full channel does not wake its sender");
+        assert!(poll(b.as_mut(), 1).is_pending());
+        assert_eq!(woken(0), 1, "a replaced sender is woken");
+
+        assert_eq!(channel.try_receive(), Some(1));
+        assert_eq!(woken(1), 1, "receiving wakes the blocked sender");
+        assert!(poll(b.as_mut(), 1).is_ready());
+        assert!(poll(a.as_mut(), 0).is_pending());
+        assert_eq!(channel.try_receive(), Some(3));
+        assert!(poll(a.as_mut(), 0).is_ready());
+        assert_eq!(channel.try_receive(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn output_channel() {
+        static CHANNEL: PubSubChannel<u32, 2, 2> = PubSubChannel::new();
+        let output = OutputChannel::new(&CHANNEL);
+
+        let mut a = output.receiver().unwrap();
+        let mut b = output.receiver().unwrap();
+        assert!(matches!(
+            output.receiver(),
+            Err(SubscribeError::MaximumSubscribersReached)
+        ));
+
+        output.publish(1);
+        assert_eq!(a.recv().await, Ok(1));
+        assert_eq!(b.recv().await, Ok(1), "every subscriber gets the event");
+
+        for i in 2..5 {
+            output.publish(i);
+        }
+        assert_eq!(a.recv().await, Err(RecvError::Lagged(1)));
+        assert_eq!(a.recv().await, Ok(3));
+        assert_eq!(a.recv().await, Ok(4));
+
+        drop(b);
+        let mut c = output.receiver().expect("subscription released on drop");
+        output.publish(5);
+        assert_eq!(c.recv().await, Ok(5), "no stale events");
+        assert_eq!(a.recv().await, Ok(5));
+    }
+
+    #[cfg(feature = "std")]
+    mod engine {
+        extern crate std;
+
+        use crate::{transport::select, Atomic, AtomicKind, Component, Config, Port};
+        use core::{
+            future::Future,
+            pin::pin,
+            task::{Context, Poll},
+        };
+        use std::{sync::Arc, task::Wake, thread::Thread};
+
+        #[derive(crate::Bag, crate::BagMux)]
+        pub struct EchoInput {
+            pub in_value: Port<u32, 1>,
+        }
+
+        #[derive(crate::Bag, crate::BagMux)]
+        pub struct EchoOutput {
+            pub out_value: Port<u32, 1>,
+        }
+
+        pub struct Echo {
+            value: Option<u32>,
+        }
+
+        #[crate::rt_engine(backend = portable, in_channel_size = 2, out_channel_size = 2)]
+        impl Component for Echo {
+            type Kind = AtomicKind;
+            type Input = EchoInput;
+            type Output = EchoOutput;
+        }
+
+        impl Atomic for Echo {
+            fn delta_int(&mut self) {
+                self.value = None;
+            }
+
+            fn lambda(&self, output: &mut Self::Output) {
+                output.out_value.add_value(self.value.unwrap()).unwrap();
+            }
+
+            fn ta(&self) -> f64 {
+                match self.value {
+                    Some(_) => 0.0,
+                    None => f64::INFINITY,
+                }
+            }
+
+            fn delta_ext(&mut self, _elapsed: f64, input: &Self::Input) {
+                self.value = input.in_value.get_values().last().copied();
+            }
+        }
+
+        /// Minimal executor that parks the current thread until the future is woken up.
+        fn block_on<F: Future>(future: F) -> F::Output {
+            struct ThreadWaker(Thread);
+
+            impl Wake for ThreadWaker {
+                fn wake(self: Arc<Self>) {
+                    self.0.unpark();
+                }
+            }
+
+            let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+            let mut cx = Context::from_waker(&waker);
+            let mut future = pin!(future);
+            loop {
+                match future.as_mut().poll(&mut cx) {
+                    Poll::Ready(output) => return output,
+                    Poll::Pending => std::thread::park(),
+                }
+            }
+        }
+
+        #[test]
+        fn custom_executor() {
+            let mut engine = Echo { value: None }.into_rt_engine();
+            let sender = engine.sender();
+            let mut receiver = engine.receiver().unwrap();
+            let config = Config::new(0.0, 5.0, 1.0, None);
+
+            let output = block_on(select(
+                async {
+                    engine.simulate_rt_async(&config).await;
+                    None
+                },
+                async {
+                    sender.send(EchoInputEnum::InValue(7)).await.unwrap();
+                    Some(receiver.recv().await)
+                },
+            ));
+            assert!(matches!(output, Some(Ok(EchoOutputEnum::OutValue(7)))));
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::rt_engine::{sealed::Sealed, RtEngineInputChannel, RtEngineOutputChannel};
+use crate::transport::{EventSink, EventSource};
 
 pub use tokio::sync::broadcast::error::RecvError;
 pub type SubscribeError = core::convert::Infallible;
@@ -8,12 +9,26 @@ use tokio::sync::mpsc::error::SendError;
 pub struct Sender<I> {
     sender: tokio::sync::mpsc::Sender<I>,
 }
+impl<I> Clone for Sender<I> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
 impl<I> Sender<I> {
     pub async fn send(&self, msg: I) -> Result<(), SendError<I>> {
         self.sender.send(msg).await
     }
 }
 
+impl<I> EventSink<I> for Sender<I> {
+    async fn send_event(&self, event: I) -> bool {
+        self.send(event).await.is_ok()
+    }
+}
+
 #[repr(transparent)]
 pub struct Receiver<O> {
     receiver: tokio::sync::broadcast::Receiver<O>,
@@ -24,6 +39,18 @@ impl<O: Clone> Receiver<O> {
     }
 }
 
+impl<O: Clone> EventSource<O> for Receiver<O> {
+    async fn recv_event(&mut self) -> Option<O> {
+        loop {
+            match self.recv().await {
+                Ok(event) => return Some(event),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
 pub struct InputChannel<I, const N: usize> {
     sender: tokio::sync::mpsc::Sender<I>,
     receiver: tokio::sync::mpsc::Receiver<I>,
@@ -81,6 +108,7 @@ impl<O: Clone, const N: usize> Default for OutputChannel<O, N> {
 impl<O: Clone, const N: usize> RtEngineOutputChannel for OutputChannel<O, N> {
     type Output = O;
     type Receiver = Receiver<O>;
+    type SubscribeError = SubscribeError;
 
     fn receiver(&self) -> Result<Self::Receiver, SubscribeError> {
         Ok(Receiver {
@@ -0,0 +1,440 @@
+//! Compile-time memory footprint of DEVS models.
+//!
+//! Every simulator implements [`StaticFootprint`], whose [`Footprint`] is a tree with the size in
+//! bytes of the simulator, its input and output bags, and its fields: atomic models, timing fields,
+//! the bags that coordinators keep for their inner components, port buffers, etc.
+//! [`footprint`] is a `const fn`, so footprints can be checked at compile time with [`assert_footprint`].
+//!
+//! Footprints only account for memory stored inline. Components behind references, `Box`es or
+//! `Vec`s only count the size of the pointer, as their memory lives elsewhere.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // The build fails if simulating the model needs more than 16 KiB
+//! xdevs::assert_footprint!(TopModel, 16 * 1024);
+//!
+//! // With the `std` feature, print the footprint of the model as a table
+//! xdevs::footprint::print::<TopModel>();
+//! ```
+
+use crate::{
+    component::{
+        atomic::{Atomic, TryAtomic},
+        coupled::Coupled,
+    },
+    simulation::{
+        coordinator::{Buffers, Coordinator, MixedBuffers, OwnedBuffers, SharedBuffers},
+        AbstractSimulator, SimpleSimulable,
+    },
+};
+use core::{fmt, mem::size_of};
+
+/// Memory footprint of a simulator, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Footprint {
+    /// Size of the simulator, including its fields.
+    pub size: usize,
+    /// Size of the input bag of the simulator.
+    pub input: usize,
+    /// Size of the output bag of the simulator.
+    pub output: usize,
+    /// `true` if only one of the fields is present at a time (e.g., variants of an enum).
+    pub exclusive: bool,
+    /// Breakdown of the fields of the simulator.
+    pub fields: &'static [Field],
+}
+
+/// Field of a simulator in a [`Footprint`] tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field {
+    /// Name of the field.
+    pub name: &'static str,
+    /// Number of elements of the field (e.g., the length of an array).
+    pub count: usize,
+    /// Footprint of one element of the field.
+    pub footprint: Footprint,
+}
+
+impl Footprint {
+    /// Footprint of a simulator with the given input and output bags, and fields.
+    pub const fn new<T: AbstractSimulator>(fields: &'static [Field]) -> Self {
+        Self {
+            size: size_of::<T>(),
+            input: size_of::<T::Input>(),
+            output: size_of::<T::Output>(),
+            exclusive: false,
+            fields,
+        }
+    }
+
+    /// Footprint of a value without inner fields nor bags.
+    pub const fn leaf(size: usize) -> Self {
+        Self {
+            size,
+            input: 0,
+            output: 0,
+            exclusive: false,
+            fields: &[],
+        }
+    }
+
+    /// Marks the fields of the footprint as mutually exclusive.
+    pub const fn exclusive(mut self) -> Self {
+        self.exclusive = true;
+        self
+    }
+
+    /// Bytes needed to simulate the model: its simulator plus the input and output bags
+    /// used by the simulation loop.
+    pub const fn total(&self) -> usize {
+        self.size + self.input + self.output
+    }
+
+    /// Bytes of the simulator that are not detailed by its fields (e.g., timing fields or padding).
+    pub const fn own(&self) -> usize {
+        let mut fields = 0;
+        let mut i = 0;
+        while i < self.fields.len() {
+            let field = &self.fields[i];
+            let size = field.count * field.footprint.size;
+            fields = match self.exclusive {
+                true if size > fields => size,
+                true => fields,
+                false => fields + size,
+            };
+            i += 1;
+        }
+        self.size.saturating_sub(fields)
+    }
+
+    fn fmt_fields(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        for field in self.fields {
+            let name_width = NAME_WIDTH.saturating_sub(2 * depth);
+            writeln!(
+                f,
+                "{:indent$}{:<name_width$} {:>8} {:>10} {:>10} {:>10} {:>10}",
+                "",
+                field.name,
+                field.count,
+                field.footprint.size,
+                field.footprint.own(),
+                field.footprint.input,
+                field.footprint.output,
+                indent = 2 * depth,
+            )?;
+            field.footprint.fmt_fields(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Field {
+    pub const fn new(name: &'static str, count: usize, footprint: Footprint) -> Self {
+        Self {
+            name,
+            count,
+            footprint,
+        }
+    }
+}
+
+const NAME_WIDTH: usize = 32;
+
+/// Formats the footprint as a table with one row per field.
+/// Sizes refer to one element of the field, and `own` is the part of the size not detailed by
+/// the inner fields (e.g., timing fields of simulators or padding).
+impl fmt::Display for Footprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<NAME_WIDTH$} {:>8} {:>10} {:>10} {:>10} {:>10}",
+            "field", "count", "size", "own", "input", "output"
+        )?;
+        writeln!(
+            f,
+            "{:<NAME_WIDTH$} {:>8} {:>10} {:>10} {:>10} {:>10}",
+            "(model)",
+            1,
+            self.size,
+            self.own(),
+            self.input,
+            self.output
+        )?;
+        self.fmt_fields(f, 1)?;
+        write!(f, "total: {} bytes", self.total())
+    }
+}
+
+/// Interface for simulators with a memory footprint known at compile time.
+///
+/// It is implemented for all the simulators of this crate and by the [`coupled`](crate::coupled)
+/// and [`to_component`](crate::to_component) macros.
+pub trait StaticFootprint {
+    /// Memory footprint of the simulator.
+    const FOOTPRINT: Footprint;
+}
+
+/// Returns the memory footprint of simulating a component.
+pub const fn footprint<T>() -> Footprint
+where
+    T: SimpleSimulable,
+    T::Simulator: StaticFootprint,
+{
+    <T::Simulator as StaticFootprint>::FOOTPRINT
+}
+
+/// Prints the memory footprint of simulating a component as a table.
+#[cfg(feature = "std")]
+pub fn print<T>()
+where
+    T: SimpleSimulable,
+    T::Simulator: StaticFootprint,
+{
+    std::println!("{}\n{}", core::any::type_name::<T>(), footprint::<T>());
+}
+
+/// Fails the build if simulating a component needs more bytes than the given budget.
+///
+/// See [`Footprint::total`] for the accounted memory.
+#[macro_export]
+macro_rules! assert_footprint {
+    ($component:ty, $budget:expr $(,)?) => {
+        const _: () = ::core::assert!(
+            $crate::footprint::footprint::<$component>().total() <= $budget,
+            ::core::concat!(
+                "memory footprint of `",
+                ::core::stringify!($component),
+                "` exceeds its budget"
+            ),
+        );
+    };
+}
+
+impl<T: Atomic> StaticFootprint for crate::simulation::simulator::Simulator<T> {
+    const FOOTPRINT: Footprint =
+        Footprint::new::<Self>(&[Field::new("component", 1, Footprint::leaf(size_of::<T>()))]);
+}
+
+impl<T: TryAtomic> StaticFootprint for crate::simulation::simulator::TrySimulator<T> {
+    const FOOTPRINT: Footprint =
+        Footprint::new::<Self>(&[Field::new("component", 1, Footprint::leaf(size_of::<T>()))]);
+}
+
+impl<T> StaticFootprint for Coordinator<T>
+where
+    T: Coupled,
+    T::Components: StaticFootprint,
+    T::Buffers: Buffers<T> + StaticFootprint,
+{
+    const FOOTPRINT: Footprint = Footprint::new::<Self>(&[
+        Field::new(
+            "components",
+            1,
+            <T::Components as StaticFootprint>::FOOTPRINT,
+        ),
+        Field::new("buffers", 1, <T::Buffers as StaticFootprint>::FOOTPRINT),
+    ]);
+}
+
+impl<I, O> StaticFootprint for OwnedBuffers<I, O> {
+    const FOOTPRINT: Footprint = Footprint {
+        fields: &[
+            Field::new("input", 1, Footprint::leaf(size_of::<I>())),
+            Field::new("output", 1, Footprint::leaf(size_of::<O>())),
+        ],
+        ..Footprint::leaf(size_of::<Self>())
+    };
+}
+
+impl<I, O> StaticFootprint for MixedBuffers<I, O> {
+    const FOOTPRINT: Footprint = <OwnedBuffers<I, O> as StaticFootprint>::FOOTPRINT;
+}
+
+impl StaticFootprint for SharedBuffers {
+    const FOOTPRINT: Footprint = Footprint::leaf(0);
+}
+
+impl<T: AbstractSimulator + StaticFootprint, const N: usize> StaticFootprint for [T; N] {
+    const FOOTPRINT: Footprint = Footprint::new::<Self>(&[Field::new("[_]", N, T::FOOTPRINT)]);
+}
+
+impl<T: AbstractSimulator + StaticFootprint> StaticFootprint for Option<T> {
+    const FOOTPRINT: Footprint =
+        Footprint::new::<Self>(&[Field::new("Some", 1, T::FOOTPRINT)]).exclusive();
+}
+
+impl<T: AbstractSimulator> StaticFootprint for &mut T {
+    const FOOTPRINT: Footprint = Footprint::new::<Self>(&[]);
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AbstractSimulator> StaticFootprint for alloc::boxed::Box<T> {
+    const FOOTPRINT: Footprint = Footprint::new::<Self>(&[]);
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AbstractSimulator> StaticFootprint for alloc::vec::Vec<T> {
+    const FOOTPRINT: Footprint = Footprint::new::<Self>(&[]);
+}
+
+#[cfg(feature = "std")]
+impl<I, O> StaticFootprint for crate::simulation::conservative::Remote<I, O>
+where
+    I: crate::port::Bag + crate::port::BagMux,
+    O: crate::port::Bag + crate::port::BagMux,
+{
+    const FOOTPRINT: Footprint = Footprint::new::<Self>(&[]);
+}
+
+/// Flat models keep their atomic models, bags and routes in the heap.
+#[cfg(feature = "alloc")]
+impl<S, const I: usize, const O: usize, In, Out> StaticFootprint
+    for crate::simulation::flat::Flat<S, I, O, In, Out>
+where
+    S: AbstractSimulator,
+    S::Output: crate::port::BagMux,
+    S::Input: crate::port::BagMux<Mux = <S::Output as crate::port::BagMux>::Mux>,
+    In: crate::port::BagMux<Mux = <S::Output as crate::port::BagMux>::Mux>,
+    Out: crate::port::BagMux<Mux = <S::Output as crate::port::BagMux>::Mux>,
+{
+    const FOOTPRINT: Footprint = Footprint::new::<Self>(&[]);
+}
+
+macro_rules! impl_static_footprint_for_tuple {
+    ($($idx:tt => $T:ident),+) => {
+        impl<$($T: AbstractSimulator + StaticFootprint),+> StaticFootprint for ($($T,)+) {
+            const FOOTPRINT: Footprint = Footprint::new::<Self>(&[
+                $(Field::new(stringify!($idx), 1, $T::FOOTPRINT),)+
+            ]);
+        }
+    }
+}
+
+impl_static_footprint_for_tuple!(0 => T0);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7, 8 => T8);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7, 8 => T8, 9 => T9);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7, 8 => T8, 9 => T9, 10 => T10);
+impl_static_footprint_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7, 8 => T8, 9 => T9, 10 => T10, 11 => T11);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpt::{Generator, Processor, Transducer, EF, EFP, GPT};
+    use crate::simulation::{coordinator::Coordinator, simulator::Simulator};
+
+    // The footprint is evaluated at compile time
+    crate::assert_footprint!(GPT, 4096);
+    const GPT_FOOTPRINT: Footprint = footprint::<GPT>();
+
+    #[test]
+    fn atomic_footprint_details_component() {
+        let footprint = footprint::<Processor>();
+        assert_eq!(footprint.size, size_of::<Simulator<Processor>>());
+        assert_eq!(footprint.input, size_of::<crate::Port<usize, 1>>());
+        assert_eq!(footprint.fields.len(), 1);
+        assert_eq!(footprint.fields[0].name, "component");
+        assert_eq!(footprint.fields[0].footprint.size, size_of::<Processor>());
+        // Timing fields of the simulator
+        assert!(footprint.own() >= 2 * size_of::<f64>());
+    }
+
+    #[test]
+    fn coupled_footprint_details_components_and_bags() {
+        assert_eq!(GPT_FOOTPRINT.size, size_of::<Coordinator<GPT>>());
+        assert_eq!(
+            GPT_FOOTPRINT.total(),
+            GPT_FOOTPRINT.size + GPT_FOOTPRINT.input + GPT_FOOTPRINT.output
+        );
+
+        let names: [&str; 2] = core::array::from_fn(|i| GPT_FOOTPRINT.fields[i].name);
+        assert_eq!(names, ["components", "buffers"]);
+
+        let components = GPT_FOOTPRINT.fields[0].footprint;
+        let names: [&str; 3] = core::array::from_fn(|i| components.fields[i].name);
+        assert_eq!(names, ["generator", "processor", "transducer"]);
+        assert_eq!(components.fields[0].footprint, footprint::<Generator>());
+        assert_eq!(components.fields[2].footprint, footprint::<Transducer>());
+        let buffers = GPT_FOOTPRINT.fields[1].footprint;
+        assert_eq!(buffers.fields[0].name, "input");
+        assert_eq!(
+            buffers.fields[0].footprint.size,
+            size_of::<crate::ComponentsInput<GPT>>()
+        );
+    }
+
+    #[test]
+    fn nested_footprint_includes_inner_coordinators() {
+        let efp = footprint::<EFP>();
+        let components = efp.fields[0].footprint;
+        assert_eq!(components.fields[0].name, "ef");
+        assert_eq!(components.fields[0].footprint, footprint::<EF>());
+        assert!(efp.size > footprint::<EF>().size + footprint::<Processor>().size);
+    }
+
+    #[test]
+    fn collections_footprint() {
+        let array = <[Simulator<Processor>; 4] as StaticFootprint>::FOOTPRINT;
+        assert_eq!(array.size, 4 * size_of::<Simulator<Processor>>());
+        assert_eq!(array.fields[0].count, 4);
+        assert_eq!(array.own(), 0);
+
+        let option = <Option<Simulator<Processor>> as StaticFootprint>::FOOTPRINT;
+        assert!(option.exclusive);
+        assert_eq!(
+            option.own(),
+            option.size - size_of::<Simulator<Processor>>()
+        );
+
+        let reference = <&mut Simulator<Processor> as StaticFootprint>::FOOTPRINT;
+        assert_eq!(reference.size, size_of::<usize>());
+        assert!(reference.fields.is_empty());
+    }
+
+    #[test]
+    fn enum_footprint_variants_are_exclusive() {
+        let footprint = footprint::<crate::devstone::li::LIEnum<'static, 3>>();
+        assert!(footprint.exclusive);
+        assert_eq!(footprint.fields[0].name, "Leaf");
+        assert_eq!(footprint.fields[1].name, "Branch");
+        let largest = footprint
+            .fields
+            .iter()
+            .map(|f| f.footprint.size)
+            .max()
+            .unwrap();
+        assert_eq!(footprint.own(), footprint.size - largest);
+    }
+
+    #[test]
+    fn footprint_is_displayed_as_table() {
+        use core::fmt::Write;
+
+        let mut table = heapless::String::<4096>::new();
+        write!(table, "{}", GPT_FOOTPRINT).unwrap();
+        assert!(table.starts_with("field"));
+        for name in [
+            "buffers",
+            "  input",
+            "  generator",
+            "    component",
+            "transducer",
+        ] {
+            assert!(table.contains(name), "missing {name} in\n{table}");
+        }
+        let total = table.lines().last().unwrap();
+        let total = total
+            .strip_prefix("total: ")
+            .unwrap()
+            .strip_suffix(" bytes")
+            .unwrap();
+        assert_eq!(total.parse(), Ok(GPT_FOOTPRINT.total()));
+    }
+}
@@ -1,4 +1,5 @@
 /// Generator that produces jobs at a fixed period until told to stop.
+#[derive(Clone)]
 pub struct Generator {
     sigma: f64,
     period: f64,
@@ -39,6 +40,12 @@ impl xdevs::Atomic for Generator {
     }
 }
 
+impl xdevs::Reset for Generator {
+    fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+}
+
 impl Generator {
     pub fn new(period: f64) -> Self {
         Self {
@@ -50,6 +57,7 @@ impl Generator {
 }
 
 /// Processor that receives a job, processes it for a fixed duration, then outputs it.
+#[derive(Clone)]
 pub struct Processor {
     sigma: f64,
     time: f64,
@@ -100,6 +108,12 @@ impl xdevs::Atomic for Processor {
     }
 }
 
+impl xdevs::Reset for Processor {
+    fn reset(&mut self) {
+        *self = Self::new(self.time);
+    }
+}
+
 impl Processor {
     pub fn new(time: f64) -> Self {
         Self {
@@ -111,7 +125,7 @@ impl Processor {
 }
 
 /// Input bag for the Transducer.
-#[derive(xdevs::Bag)]
+#[derive(xdevs::Bag, xdevs::BagMux)]
 pub struct TransducerInput {
     pub in_generator: xdevs::Port<usize, 1>,
     pub in_processor: xdevs::Port<usize, 1>,
@@ -119,7 +133,9 @@ pub struct TransducerInput {
 
 /// Transducer that observes generated and processed jobs, computes metrics,
 /// and sends a stop signal to the Generator.
+#[derive(Clone)]
 pub struct Transducer {
+    obs_time: f64,
     sigma: f64,
     clock: f64,
     n_generated: usize,
@@ -160,9 +176,16 @@ impl xdevs::Atomic for Transducer {
     }
 }
 
+impl xdevs::Reset for Transducer {
+    fn reset(&mut self) {
+        *self = Self::new(self.obs_time);
+    }
+}
+
 impl Transducer {
     pub fn new(obs_time: f64) -> Self {
         Self {
+            obs_time,
             sigma: obs_time,
             clock: 0.0,
             n_generated: 0,
@@ -187,7 +210,7 @@ impl Transducer {
     }
 }
 
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct GPT {
     generator: Generator,
     processor: Processor,
@@ -213,7 +236,14 @@ impl xdevs::Coupled for GPT {
     }
 }
 
-#[xdevs::coupled]
+impl GPT {
+    /// Transducer of the model, with the statistics of the simulation.
+    pub fn transducer(&self) -> &Transducer {
+        &self.components.transducer
+    }
+}
+
+#[xdevs::coupled(reset)]
 pub struct EF {
     generator: Generator,
     transducer: Transducer,
@@ -240,7 +270,7 @@ impl xdevs::Coupled for EF {
     }
 }
 
-#[xdevs::coupled]
+#[xdevs::coupled(reset)]
 pub struct EFP {
     ef: EF,
     processor: Processor,
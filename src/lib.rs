@@ -7,19 +7,26 @@ extern crate std;
 
 pub mod component;
 pub mod devstone;
+#[cfg(feature = "std")]
+pub mod experiment;
 pub mod export;
+pub mod footprint;
 pub mod gpt;
 pub mod port;
-#[cfg(any(feature = "embassy", feature = "std"))]
+#[cfg(any(feature = "embassy", feature = "std", feature = "portable"))]
 pub mod rt_engine;
 pub mod simulation;
+pub mod transport;
+pub mod wire;
 
 pub use component::{
-    atomic::Atomic,
+    atomic::{Atomic, TryAtomic},
     coupled::{ComponentsInput, ComponentsOutput, Coupled},
-    AtomicKind, Component, ComponentsKind, CoupledKind,
+    AtomicKind, Component, ComponentsKind, CoupledKind, TryAtomicKind,
 };
 pub use embassy_time::{Duration, Instant};
+#[cfg(feature = "alloc")]
+pub use port::DynPort;
 pub use port::Port;
-pub use simulation::{AbstractSimulator, Config, Simulable};
+pub use simulation::{reset::Reset, AbstractSimulator, Config, Pacing, Simulable};
 pub use xdevs_no_std_macros::*;
@@ -85,12 +85,126 @@ unsafe impl<T: Clone, const N: usize> Bag for Port<T, N> {
     }
 }
 
+unsafe impl<T: Clone, const N: usize> BagMux for Port<T, N> {
+    type Mux = T;
+
+    fn inject_event(&mut self, event: Self::Mux) -> Result<(), Self::Mux> {
+        self.add_value(event)
+    }
+
+    fn eject_events(&self, ejector: impl FnMut(Self::Mux)) {
+        self.get_values().iter().cloned().for_each(ejector);
+    }
+}
+
 impl<T: Clone, const N: usize> AsPort for Port<T, N> {
     type Item = T;
 }
 
 impl<T: Clone, const N: usize> Sealed for Port<T, N> {}
 
+/// Port with an unbounded, heap-allocated buffer.
+/// Unlike [`Port`], its capacity does not need to be known at compile time,
+/// which makes it useful for models whose fan-in is only known at runtime.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct DynPort<T: Clone>(alloc::vec::Vec<T>);
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> Default for DynPort<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> DynPort<T> {
+    /// Creates a new empty port.
+    #[inline]
+    pub const fn new() -> Self {
+        Self(alloc::vec::Vec::new())
+    }
+
+    /// Returns `true` if the port is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of elements in the port.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Clears the port, removing all values. The allocated buffer is kept for later use.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Adds a value to the port.
+    #[inline]
+    pub fn add_value(&mut self, item: T) {
+        self.0.push(item)
+    }
+
+    /// Adds multiple values to the port.
+    #[inline]
+    pub fn add_values(&mut self, items: &[T]) {
+        self.0.extend_from_slice(items)
+    }
+
+    /// Returns a slice of the port's values.
+    #[inline]
+    pub fn get_values(&self) -> &[T] {
+        self.0.as_slice()
+    }
+
+    /// Easy port mapping method
+    #[inline]
+    pub fn couple(&self, to: &mut DynPort<T>) {
+        to.add_values(self.get_values())
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T: Clone> Bag for DynPort<T> {
+    fn build() -> Self {
+        Self::new()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.clear()
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T: Clone> BagMux for DynPort<T> {
+    type Mux = T;
+
+    fn inject_event(&mut self, event: Self::Mux) -> Result<(), Self::Mux> {
+        self.add_value(event);
+        Ok(())
+    }
+
+    fn eject_events(&self, ejector: impl FnMut(Self::Mux)) {
+        self.get_values().iter().cloned().for_each(ejector);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> AsPort for DynPort<T> {
+    type Item = T;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> Sealed for DynPort<T> {}
+
 /// Trait that defines the methods that a DEVS event bag set must implement.
 ///
 /// # Safety
@@ -153,6 +267,24 @@ impl<T: AsPort, const N: usize> AsPort for [T; N] {
 }
 impl<T: AsPort, const N: usize> Sealed for [T; N] {}
 
+/// Bags of runtime-sized component collections.
+/// They are built empty, and [`AbstractSimulator`](crate::simulation::AbstractSimulator)
+/// implementations fill them with one bag per component.
+#[cfg(feature = "alloc")]
+unsafe impl<T: Bag> Bag for alloc::vec::Vec<T> {
+    fn build() -> Self {
+        Self::new()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.iter().all(|bag| bag.is_empty())
+    }
+
+    fn clear(&mut self) {
+        self.iter_mut().for_each(|bag| bag.clear());
+    }
+}
+
 unsafe impl Bag for () {
     fn build() -> Self {}
 
@@ -163,6 +295,19 @@ unsafe impl Bag for () {
     fn clear(&mut self) {}
 }
 
+/// Bag without ports. Coupled models use it in place of the bags of their `#[shared]` components.
+unsafe impl<T: ?Sized> Bag for core::marker::PhantomData<T> {
+    fn build() -> Self {
+        Self
+    }
+
+    fn is_empty(&self) -> bool {
+        true
+    }
+
+    fn clear(&mut self) {}
+}
+
 macro_rules! impl_bag_for_tuple {
     ($($idx:tt => $T:ident),+) => {
         unsafe impl<$($T: Bag),+> Bag for ($($T,)+) {
@@ -281,6 +426,34 @@ mod tests {
         assert_eq!(src.get_values(), &[1, 2, 3]);
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn dyn_port_has_no_capacity_limit() {
+        let mut src: DynPort<u32> = DynPort::new();
+        src.add_values(&[1, 2, 3]);
+        src.add_value(4);
+        let mut dst: DynPort<u32> = DynPort::new();
+        src.couple(&mut dst);
+        src.couple(&mut dst);
+        assert_eq!(dst.len(), 8);
+        assert_eq!(dst.get_values(), &[1, 2, 3, 4, 1, 2, 3, 4]);
+        dst.clear();
+        assert!(dst.is_empty());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_bag_is_built_empty() {
+        let mut bags = <alloc::vec::Vec<Port<u32, 1>> as Bag>::build();
+        assert!(bags.is_empty() && Bag::is_empty(&bags));
+        bags.push(Port::new());
+        bags.push(Port::new());
+        bags[1].add_value(7).unwrap();
+        assert!(!Bag::is_empty(&bags));
+        Bag::clear(&mut bags);
+        assert!(Bag::is_empty(&bags));
+    }
+
     #[test]
     fn port_is_full_len_cycle() {
         let mut port: Port<u32, 3> = Port::new();
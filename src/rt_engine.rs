@@ -1,13 +1,19 @@
 use core::future::Future;
 
+#[cfg(not(any(feature = "embassy", feature = "std")))]
+pub use crate::export::portable::{RecvError, SubscribeError};
+#[cfg(any(feature = "embassy", feature = "std"))]
 pub use crate::export::{RecvError, SubscribeError};
 use crate::{
     port::Bag,
-    simulation::{AbstractSimulator, AsyncInput, Simulable},
+    simulation::{AbstractSimulator, AsyncInput, Pacer, Simulable},
     Component, Duration, Instant,
 };
 use sealed::Sealed;
 
+/// Output channel of the model `M`.
+type OutputChannelOf<M> = <<M as Component>::Output as EjectOutput>::OutputChannel;
+
 /// Automated simulation engine for real-time execution of DEVS models.
 /// Its interfaces are created through the use of the `rt_engine` macro.
 pub struct RtEngine<K, M>
@@ -75,8 +81,8 @@ where
     pub fn receiver(
         &self,
     ) -> Result<
-        <<M::Output as EjectOutput>::OutputChannel as RtEngineOutputChannel>::Receiver,
-        crate::rt_engine::SubscribeError,
+        <OutputChannelOf<M> as RtEngineOutputChannel>::Receiver,
+        <OutputChannelOf<M> as RtEngineOutputChannel>::SubscribeError,
     > {
         self.output_channel.receiver()
     }
@@ -89,6 +95,7 @@ where
 {
     input_channel: &'a mut <M::Input as InjectInput>::InputChannel,
     last_rt: Option<crate::Instant>,
+    pacer: Option<Pacer>,
 }
 
 impl<'a, K, M> RtEngineInputHandler<'a, K, M>
@@ -100,6 +107,7 @@ where
         Self {
             input_channel,
             last_rt: None,
+            pacer: None,
         }
     }
 }
@@ -118,7 +126,11 @@ where
         t_until: f64,
         input: &mut Self::Input,
     ) -> f64 {
+        let pacer = self.pacer.get_or_insert_with(|| Pacer::new(config));
         let last_rt = self.last_rt.unwrap_or_else(Instant::now);
+        let Some((t_from, last_rt)) = pacer.schedule(t_from, t_until, last_rt, Instant::now) else {
+            return t_until;
+        };
         let time_duration = (t_until - t_from) * config.time_scale;
         let time_duration = (time_duration * 1_000_000_000.0) as u64;
         let next_rt = last_rt + Duration::from_nanos(time_duration);
@@ -129,7 +141,7 @@ where
 
         if embassy_time::with_deadline(next_rt, future).await.is_err() {
             // Deadline reached (timeout), check for jitter
-            if let Some(max_jitter) = config.max_jitter {
+            if let Some(max_jitter) = pacer.max_jitter() {
                 let jitter = Instant::now().duration_since(next_rt);
                 let max_jitter_ticks = Duration::from_micros(max_jitter.as_micros() as u64);
                 if jitter > max_jitter_ticks {
@@ -197,8 +209,11 @@ pub trait RtEngineOutputChannel: Sealed {
     /// Type of the receiver used to receive output events from the model.
     type Receiver;
 
+    /// Error returned when subscribing to the channel is not possible.
+    type SubscribeError;
+
     /// Returns a subscriber to the channel. The subscriber can be used to receive output events from the model.
-    fn receiver(&self) -> Result<Self::Receiver, crate::rt_engine::SubscribeError>;
+    fn receiver(&self) -> Result<Self::Receiver, Self::SubscribeError>;
 
     /// Publishes output events from the model to the channel, mapping the model's output ports to the channel's output events.
     fn publish(&self, output: Self::Output);
@@ -227,3 +242,80 @@ pub(crate) mod sealed {
     /// Trait used to prevent users from implementing certain traits manually.
     pub trait Sealed {}
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{simulation::Pacing, Atomic, AtomicKind, Config};
+
+    extern crate std;
+    use std::{thread, time::Instant as StdInstant};
+
+    /// Periodic model without input nor output ports.
+    struct Ticker {
+        calls: usize,
+    }
+
+    impl Component for Ticker {
+        type Kind = AtomicKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Atomic for Ticker {
+        fn delta_int(&mut self) {
+            self.calls += 1;
+        }
+        fn delta_ext(&mut self, _elapsed: f64, _input: &Self::Input) {}
+        fn lambda(&self, _output: &mut Self::Output) {}
+        fn ta(&self) -> f64 {
+            1.0
+        }
+    }
+
+    /// Simulates a [`Ticker`] with the input handler of [`RtEngine`].
+    /// The first output function takes 30 ms.
+    async fn simulate_ticker(config: &Config) -> usize {
+        let mut sim = Ticker { calls: 0 }.to_simulator();
+        let mut channel = ();
+        let input_handler = RtEngineInputHandler::<AtomicKind, Ticker>::new(&mut channel);
+        let mut late = true;
+        sim.simulate_rt_async(config, input_handler, |_| {
+            if core::mem::take(&mut late) {
+                thread::sleep(std::time::Duration::from_millis(30))
+            }
+        })
+        .await;
+        sim.calls
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "Jitter too high")]
+    async fn input_handler_strict_panics_when_late() {
+        let config = Config::new(0.0, 5.0, 0.01, Some(core::time::Duration::from_millis(5)));
+        simulate_ticker(&config).await;
+    }
+
+    #[tokio::test]
+    async fn input_handler_catch_up() {
+        let config = Config::new(0.0, 10.0, 0.01, Some(core::time::Duration::from_millis(5)))
+            .with_pacing(Pacing::CatchUp);
+        let start = StdInstant::now();
+        assert_eq!(simulate_ticker(&config).await, 10);
+        // Late transitions do not shift the schedule
+        assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn input_handler_fast_forward() {
+        // History of 90 time units: only the last 10 time units (100 ms) are paced
+        let config = Config::new(0.0, 100.0, 0.01, Some(core::time::Duration::from_millis(5)))
+            .with_pacing(Pacing::FastForward(core::time::Duration::from_millis(900)));
+        let start = StdInstant::now();
+        assert_eq!(simulate_ticker(&config).await, 100);
+        assert!(
+            start.elapsed() >= std::time::Duration::from_millis(100),
+            "live part is paced"
+        );
+    }
+}
@@ -1,14 +1,28 @@
 use crate::{component::Component, port::Bag, ComponentsKind};
 use core::{future::Future, time::Duration};
+use error::{ModelError, PathSegment};
 
+#[cfg(feature = "std")]
+pub mod conservative;
 pub mod coordinator;
 #[cfg(feature = "embassy")]
 pub mod embassy;
+pub mod error;
+#[cfg(feature = "alloc")]
+pub mod flat;
+pub mod idle;
+pub mod interrupt;
+#[cfg(feature = "std")]
+pub mod optimistic;
+pub mod profiling;
+pub mod reset;
 pub mod simulator;
 #[cfg(feature = "std")]
 pub mod std;
 
 /// Configuration for the DEVS simulator.
+///
+/// Build it with [`Config::new`]. The pacing mode is set with [`Config::with_pacing`].
 #[derive(Debug, Clone, Copy)]
 pub struct Config {
     /// The start time of the simulation.
@@ -28,19 +42,41 @@ pub struct Config {
     /// If `None`, jitter is not checked. If `Some(duration)`, the simulator will panic
     /// if the wall-clock time drift exceeds this duration.
     pub max_jitter: Option<Duration>,
+
+    /// The pacing mode of real-time simulations.
+    pacing: Pacing,
 }
 
 impl Config {
     /// Creates a new `SimulatorConfig` with the specified parameters.
     #[inline]
-    pub fn new(t_start: f64, t_stop: f64, time_scale: f64, max_jitter: Option<Duration>) -> Self {
+    pub const fn new(
+        t_start: f64,
+        t_stop: f64,
+        time_scale: f64,
+        max_jitter: Option<Duration>,
+    ) -> Self {
         Self {
             t_start,
             t_stop,
             time_scale,
             max_jitter,
+            pacing: Pacing::Strict,
         }
     }
+
+    /// Sets the pacing mode of real-time simulations. By default, it is [`Pacing::Strict`].
+    #[inline]
+    pub const fn with_pacing(mut self, pacing: Pacing) -> Self {
+        self.pacing = pacing;
+        self
+    }
+
+    /// Returns the pacing mode of real-time simulations.
+    #[inline]
+    pub const fn pacing(&self) -> Pacing {
+        self.pacing
+    }
 }
 
 impl Default for Config {
@@ -52,6 +88,142 @@ impl Default for Config {
     }
 }
 
+/// Pacing mode of real-time simulations.
+///
+/// It is honored by the wall-clock wait helpers of the crate (e.g., `wait_event` and `SleepAsync`),
+/// including the input handler of `RtEngine`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Pacing {
+    /// Every state transition is paced in real time.
+    /// The simulation panics if a transition is late by more than [`Config::max_jitter`].
+    #[default]
+    Strict,
+    /// Late state transitions run back-to-back, without waiting, until the schedule is met again.
+    /// [`Config::max_jitter`] is not enforced.
+    CatchUp,
+    /// The simulation started the given wall-clock duration ago. State transitions up to the
+    /// corresponding virtual time run as fast as possible (i.e., history is replayed in virtual time).
+    /// Then, the simulation runs live as in [`Pacing::Strict`].
+    FastForward(Duration),
+}
+
+/// How a wait helper must wait until the next state transition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Pace {
+    /// Return immediately, the transition belongs to the fast-forwarded history.
+    Skip,
+    /// Restart the wall-clock schedule now, which corresponds to the given virtual time.
+    Resync(f64),
+    /// Wait until the scheduled wall-clock time.
+    Paced,
+}
+
+/// Pacing state shared by the wall-clock wait helpers.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Pacer {
+    /// Virtual time at which live pacing starts (negative infinity if already live).
+    t_live: f64,
+    max_jitter: Option<Duration>,
+}
+
+impl Pacer {
+    pub(crate) fn new(config: &Config) -> Self {
+        let t_live = match config.pacing {
+            Pacing::FastForward(behind) => {
+                config.t_start + behind.as_secs_f64() / config.time_scale
+            }
+            _ => f64::NEG_INFINITY,
+        };
+        let max_jitter = match config.pacing {
+            Pacing::CatchUp => None,
+            _ => config.max_jitter,
+        };
+        Self { t_live, max_jitter }
+    }
+
+    /// Returns how to wait from `t_from` until `t_until`.
+    pub(crate) fn pace(&mut self, t_from: f64, t_until: f64) -> Pace {
+        if t_until <= self.t_live {
+            Pace::Skip
+        } else if self.t_live > f64::NEG_INFINITY {
+            let t_live = f64::max(t_from, self.t_live);
+            self.t_live = f64::NEG_INFINITY;
+            Pace::Resync(t_live)
+        } else {
+            Pace::Paced
+        }
+    }
+
+    /// Returns the `(t_from, last_rt)` pair to wait from until `t_until`, where `last_rt`
+    /// is the wall-clock time that corresponds to `t_from` (`now` is called if the schedule restarts).
+    /// It returns `None` if the wait must be skipped, as it belongs to the fast-forwarded history.
+    #[inline]
+    pub(crate) fn schedule<I>(
+        &mut self,
+        t_from: f64,
+        t_until: f64,
+        last_rt: I,
+        now: impl FnOnce() -> I,
+    ) -> Option<(f64, I)> {
+        match self.pace(t_from, t_until) {
+            Pace::Skip => None,
+            Pace::Resync(t_live) => Some((t_live, now())),
+            Pace::Paced => Some((t_from, last_rt)),
+        }
+    }
+
+    /// Returns the maximum jitter to enforce (if any).
+    #[inline]
+    pub(crate) fn max_jitter(&self) -> Option<Duration> {
+        self.max_jitter
+    }
+}
+
+/// Super-dense simulation time.
+///
+/// It orders the iterations of the simulation loops that happen at the same real time
+/// (e.g., cascades of zero-delay transitions). The micro-step is reset to 0 when the real time
+/// advances, and incremented in each subsequent iteration that runs transitions at the same
+/// real time. Wake-ups without transitions (e.g., spurious or empty input wake-ups) do not count.
+/// Initialization happens at `(t_start, 0)`.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct SuperDenseTime {
+    /// Real simulation time.
+    pub real: f64,
+    /// Number of previous iterations with transitions at the same real time.
+    pub micro_step: u32,
+}
+
+impl SuperDenseTime {
+    /// Creates a new super-dense time.
+    #[inline]
+    pub const fn new(real: f64, micro_step: u32) -> Self {
+        Self { real, micro_step }
+    }
+
+    /// Returns the super-dense time of the next iteration, which happens at the given real time.
+    #[inline]
+    pub fn advance(self, real: f64) -> Self {
+        if real == self.real {
+            Self::new(real, self.micro_step + 1)
+        } else {
+            Self::new(real, 0)
+        }
+    }
+
+    /// Returns the super-dense time of a wake-up at the given real time that runs no transitions.
+    ///
+    /// Unlike [`SuperDenseTime::advance`], the micro-step is not incremented at the same real time.
+    #[inline]
+    fn wake_up(self, real: f64) -> Self {
+        if real == self.real {
+            self
+        } else {
+            Self::new(real, 0)
+        }
+    }
+}
+
 /// Public simulation API for DEVS processors and processor collections.
 ///
 /// This trait provides transition-level methods (`start`, `stop`, `lambda`, `delta`)
@@ -74,32 +246,155 @@ pub unsafe trait AbstractSimulator {
 
     fn delta(&mut self, input: &mut Self::Input, output: &mut Self::Output, t: f64) -> f64;
 
+    /// Executes only the external transitions triggered by `input` at time `t`.
+    /// As in Classic DEVS, atomic models that receive input schedule their next internal
+    /// transition at `t + ta()`, even if they were imminent. Other components keep their schedule.
+    /// Classic DEVS coupled models use it for imminent components that were not selected.
+    ///
+    /// Only Classic DEVS coupled models (`#[coupled(classic)]`) call it, directly or through
+    /// their parents. The default implementation panics: simulators that may be part of
+    /// Classic DEVS coupled models must override it.
+    #[inline(always)]
+    fn delta_ext(&mut self, _input: &mut Self::Input, _t: f64) -> f64 {
+        unimplemented!("simulator does not support Classic DEVS coupled models (delta_ext)")
+    }
+
+    /// Returns the time of the next internal transition.
+    ///
+    /// As [`AbstractSimulator::delta_ext`], only Classic DEVS coupled models call it,
+    /// and the default implementation panics.
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        unimplemented!("simulator does not support Classic DEVS coupled models (t_next)")
+    }
+
+    /// Builds an empty input bag for this simulator.
+    /// Collections sized at runtime override it to match their number of components.
+    #[inline(always)]
+    fn build_input(&self) -> Self::Input {
+        Self::Input::build()
+    }
+
+    /// Builds an empty output bag for this simulator.
+    /// Collections sized at runtime override it to match their number of components.
+    #[inline(always)]
+    fn build_output(&self) -> Self::Output {
+        Self::Output::build()
+    }
+
+    /// Returns `true` if an inner component failed and its error has not been taken yet.
+    #[inline(always)]
+    fn has_error(&self) -> bool {
+        false
+    }
+
+    /// Takes the error of a failed inner component, with the path of the component attached.
+    #[inline(always)]
+    fn take_error(&mut self) -> Option<ModelError> {
+        None
+    }
+
+    /// Takes the error of an inner component isolated by a coupled model with the
+    /// [`ErrorStrategy::Isolate`](error::ErrorStrategy::Isolate) strategy, with the path of the component attached.
+    /// Isolated components keep their error until it is taken, so calling it repeatedly returns all of them.
+    #[inline(always)]
+    fn take_isolated_error(&mut self) -> Option<ModelError> {
+        None
+    }
+
     /// Executes simulation from `t_start` to `t_stop` using an external wait/input strategy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a failed component aborts the simulation.
+    /// Use [`AbstractSimulator::try_simulate_rt`] to handle the error instead.
     #[inline]
     fn simulate_rt(
+        &mut self,
+        config: &Config,
+        wait_until: impl FnMut(f64, f64, &mut Self::Input) -> f64,
+        propagate_output: impl FnMut(&Self::Output),
+    ) {
+        if let Err(error) = self.try_simulate_rt(config, wait_until, propagate_output) {
+            panic!("simulation aborted: {error}");
+        }
+    }
+
+    /// Fallible version of [`AbstractSimulator::simulate_rt`].
+    ///
+    /// If a failed component aborts the simulation, the simulation is stopped and the error is returned.
+    #[allow(clippy::result_large_err)] // no_std: errors cannot be boxed
+    #[inline]
+    fn try_simulate_rt(
         &mut self,
         config: &Config,
         mut wait_until: impl FnMut(f64, f64, &mut Self::Input) -> f64,
         mut propagate_output: impl FnMut(&Self::Output),
+    ) -> Result<(), ModelError> {
+        self.try_simulate_rt_dense(
+            config,
+            |t_from, t_until, input| wait_until(t_from.real, t_until, input),
+            |_, output| propagate_output(output),
+        )
+    }
+
+    /// Version of [`AbstractSimulator::simulate_rt`] with super-dense time.
+    ///
+    /// The wait/input strategy receives the super-dense time of the previous wake-up,
+    /// and the output propagation receives the super-dense time of the current iteration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a failed component aborts the simulation.
+    /// Use [`AbstractSimulator::try_simulate_rt_dense`] to handle the error instead.
+    #[inline]
+    fn simulate_rt_dense(
+        &mut self,
+        config: &Config,
+        wait_until: impl FnMut(SuperDenseTime, f64, &mut Self::Input) -> f64,
+        propagate_output: impl FnMut(SuperDenseTime, &Self::Output),
     ) {
-        let t_start = config.t_start;
+        if let Err(error) = self.try_simulate_rt_dense(config, wait_until, propagate_output) {
+            panic!("simulation aborted: {error}");
+        }
+    }
+
+    /// Fallible version of [`AbstractSimulator::simulate_rt_dense`].
+    #[allow(clippy::result_large_err)] // no_std: errors cannot be boxed
+    #[inline]
+    fn try_simulate_rt_dense(
+        &mut self,
+        config: &Config,
+        mut wait_until: impl FnMut(SuperDenseTime, f64, &mut Self::Input) -> f64,
+        mut propagate_output: impl FnMut(SuperDenseTime, &Self::Output),
+    ) -> Result<(), ModelError> {
         let t_stop = config.t_stop;
-        let mut t = t_start;
-        let mut t_next_internal = self.start(t);
-        let mut component_input = <Self::Input>::build();
-        let mut component_output = <Self::Output>::build();
-        while t < t_stop {
+        let mut t = SuperDenseTime::new(config.t_start, 0);
+        let mut t_from = t;
+        let mut t_next_internal = self.start(t.real);
+        let mut component_input = self.build_input();
+        let mut component_output = self.build_output();
+        while t_from.real < t_stop {
             let t_until = f64::min(t_next_internal, t_stop);
-            t = wait_until(t, t_until, &mut component_input);
-            if t >= t_next_internal {
-                self.lambda(&mut component_output, t);
-                propagate_output(&component_output);
-            } else if component_input.is_empty() {
+            let t_real = wait_until(t_from, t_until, &mut component_input);
+            if t_real < t_next_internal && component_input.is_empty() {
+                t_from = t.wake_up(t_real);
                 continue; // avoid spurious external transitions
             }
-            t_next_internal = self.delta(&mut component_input, &mut component_output, t);
+            t = t.advance(t_real);
+            t_from = t;
+            if t.real >= t_next_internal {
+                self.lambda(&mut component_output, t.real);
+                propagate_output(t, &component_output);
+            }
+            t_next_internal = self.delta(&mut component_input, &mut component_output, t.real);
+            if self.has_error() {
+                self.stop();
+                return Err(self.take_error().unwrap());
+            }
         }
         self.stop();
+        Ok(())
     }
 
     /// Executes simulation from `t_start` to `t_stop` with a virtual clock.
@@ -108,32 +403,133 @@ pub unsafe trait AbstractSimulator {
         self.simulate_rt(config, |_, t_until, _| t_until, |_| {});
     }
 
+    /// Fallible version of [`AbstractSimulator::simulate_vt`].
+    #[allow(clippy::result_large_err)] // no_std: errors cannot be boxed
+    #[inline]
+    fn try_simulate_vt(&mut self, config: &Config) -> Result<(), ModelError> {
+        self.try_simulate_rt(config, |_, t_until, _| t_until, |_| {})
+    }
+
+    /// Version of [`AbstractSimulator::simulate_vt`] with super-dense time.
+    ///
+    /// The output propagation receives the super-dense time of the current iteration
+    /// (e.g., to export traces of zero-delay cascades).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a failed component aborts the simulation.
+    /// Use [`AbstractSimulator::try_simulate_vt_dense`] to handle the error instead.
+    #[inline]
+    fn simulate_vt_dense(
+        &mut self,
+        config: &Config,
+        propagate_output: impl FnMut(SuperDenseTime, &Self::Output),
+    ) {
+        self.simulate_rt_dense(config, |_, t_until, _| t_until, propagate_output);
+    }
+
+    /// Fallible version of [`AbstractSimulator::simulate_vt_dense`].
+    #[allow(clippy::result_large_err)] // no_std: errors cannot be boxed
+    #[inline]
+    fn try_simulate_vt_dense(
+        &mut self,
+        config: &Config,
+        propagate_output: impl FnMut(SuperDenseTime, &Self::Output),
+    ) -> Result<(), ModelError> {
+        self.try_simulate_rt_dense(config, |_, t_until, _| t_until, propagate_output)
+    }
+
     /// Asynchronous version of [`AbstractSimulator::simulate_rt`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a failed component aborts the simulation.
+    /// Use [`AbstractSimulator::try_simulate_rt_async`] to handle the error instead.
     fn simulate_rt_async(
         &mut self,
         config: &Config,
-        mut input_handler: impl AsyncInput<Input = Self::Input>,
+        input_handler: impl AsyncInput<Input = Self::Input>,
         mut propagate_output: impl FnMut(&Self::Output),
     ) -> impl Future<Output = ()> {
+        self.simulate_rt_async_dense(config, input_handler, move |_, output| {
+            propagate_output(output)
+        })
+    }
+
+    /// Fallible version of [`AbstractSimulator::simulate_rt_async`].
+    ///
+    /// If a failed component aborts the simulation, the simulation is stopped and the error is returned.
+    #[allow(clippy::result_large_err)] // no_std: errors cannot be boxed
+    fn try_simulate_rt_async(
+        &mut self,
+        config: &Config,
+        input_handler: impl AsyncInput<Input = Self::Input>,
+        mut propagate_output: impl FnMut(&Self::Output),
+    ) -> impl Future<Output = Result<(), ModelError>> {
+        self.try_simulate_rt_async_dense(config, input_handler, move |_, output| {
+            propagate_output(output)
+        })
+    }
+
+    /// Asynchronous version of [`AbstractSimulator::simulate_rt_dense`].
+    ///
+    /// The input handler is called via [`AsyncInput::handle_dense`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a failed component aborts the simulation.
+    /// Use [`AbstractSimulator::try_simulate_rt_async_dense`] to handle the error instead.
+    fn simulate_rt_async_dense(
+        &mut self,
+        config: &Config,
+        input_handler: impl AsyncInput<Input = Self::Input>,
+        propagate_output: impl FnMut(SuperDenseTime, &Self::Output),
+    ) -> impl Future<Output = ()> {
+        let simulation = self.try_simulate_rt_async_dense(config, input_handler, propagate_output);
         async move {
-            let mut t = config.t_start;
-            let mut t_next_internal = self.start(t);
-            let mut component_input = <Self::Input>::build();
-            let mut component_output = <Self::Output>::build();
-            while t < config.t_stop {
+            if let Err(error) = simulation.await {
+                panic!("simulation aborted: {error}");
+            }
+        }
+    }
+
+    /// Fallible version of [`AbstractSimulator::simulate_rt_async_dense`].
+    #[allow(clippy::result_large_err)] // no_std: errors cannot be boxed
+    fn try_simulate_rt_async_dense(
+        &mut self,
+        config: &Config,
+        mut input_handler: impl AsyncInput<Input = Self::Input>,
+        mut propagate_output: impl FnMut(SuperDenseTime, &Self::Output),
+    ) -> impl Future<Output = Result<(), ModelError>> {
+        async move {
+            let mut t = SuperDenseTime::new(config.t_start, 0);
+            let mut t_from = t;
+            let mut t_next_internal = self.start(t.real);
+            let mut component_input = self.build_input();
+            let mut component_output = self.build_output();
+            while t_from.real < config.t_stop {
                 let t_until = f64::min(t_next_internal, config.t_stop);
-                t = input_handler
-                    .handle(config, t, t_until, &mut component_input)
+                let t_real = input_handler
+                    .handle_dense(config, t_from, t_until, &mut component_input)
                     .await;
-                if t >= t_next_internal {
-                    self.lambda(&mut component_output, t);
-                    propagate_output(&component_output);
-                } else if component_input.is_empty() {
+                if t_real < t_next_internal && component_input.is_empty() {
+                    t_from = t.wake_up(t_real);
                     continue; // avoid spurious external transitions
                 }
-                t_next_internal = self.delta(&mut component_input, &mut component_output, t);
+                t = t.advance(t_real);
+                t_from = t;
+                if t.real >= t_next_internal {
+                    self.lambda(&mut component_output, t.real);
+                    propagate_output(t, &component_output);
+                }
+                t_next_internal = self.delta(&mut component_input, &mut component_output, t.real);
+                if self.has_error() {
+                    self.stop();
+                    return Err(self.take_error().unwrap());
+                }
             }
             self.stop();
+            Ok(())
         }
     }
 }
@@ -186,6 +582,47 @@ pub trait AsyncInput {
         t_until: f64,
         input: &mut Self::Input,
     ) -> impl Future<Output = f64>;
+
+    /// Version of [`AsyncInput::handle`] with super-dense time.
+    ///
+    /// It receives the super-dense time of the previous iteration of the simulation loop.
+    /// By default, it calls [`AsyncInput::handle`] with the real time.
+    #[inline]
+    fn handle_dense(
+        &mut self,
+        config: &Config,
+        t_from: SuperDenseTime,
+        t_until: f64,
+        input: &mut Self::Input,
+    ) -> impl Future<Output = f64> {
+        self.handle(config, t_from.real, t_until, input)
+    }
+}
+
+impl<T: AsyncInput> AsyncInput for &mut T {
+    type Input = T::Input;
+
+    #[inline]
+    fn handle(
+        &mut self,
+        config: &Config,
+        t_from: f64,
+        t_until: f64,
+        input: &mut Self::Input,
+    ) -> impl Future<Output = f64> {
+        T::handle(self, config, t_from, t_until, input)
+    }
+
+    #[inline]
+    fn handle_dense(
+        &mut self,
+        config: &Config,
+        t_from: SuperDenseTime,
+        t_until: f64,
+        input: &mut Self::Input,
+    ) -> impl Future<Output = f64> {
+        T::handle_dense(self, config, t_from, t_until, input)
+    }
 }
 
 unsafe impl<T: AbstractSimulator> AbstractSimulator for &mut T {
@@ -211,6 +648,41 @@ unsafe impl<T: AbstractSimulator> AbstractSimulator for &mut T {
     fn delta(&mut self, input: &mut Self::Input, output: &mut Self::Output, t: f64) -> f64 {
         T::delta(self, input, output, t)
     }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        T::delta_ext(self, input, t)
+    }
+
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        T::t_next(self)
+    }
+
+    #[inline(always)]
+    fn build_input(&self) -> Self::Input {
+        T::build_input(self)
+    }
+
+    #[inline(always)]
+    fn build_output(&self) -> Self::Output {
+        T::build_output(self)
+    }
+
+    #[inline(always)]
+    fn has_error(&self) -> bool {
+        T::has_error(self)
+    }
+
+    #[inline(always)]
+    fn take_error(&mut self) -> Option<ModelError> {
+        T::take_error(self)
+    }
+
+    #[inline(always)]
+    fn take_isolated_error(&mut self) -> Option<ModelError> {
+        T::take_isolated_error(self)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -237,6 +709,41 @@ unsafe impl<T: AbstractSimulator> AbstractSimulator for alloc::boxed::Box<T> {
     fn delta(&mut self, input: &mut Self::Input, output: &mut Self::Output, t: f64) -> f64 {
         T::delta(self, input, output, t)
     }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        T::delta_ext(self, input, t)
+    }
+
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        T::t_next(self)
+    }
+
+    #[inline(always)]
+    fn build_input(&self) -> Self::Input {
+        T::build_input(self)
+    }
+
+    #[inline(always)]
+    fn build_output(&self) -> Self::Output {
+        T::build_output(self)
+    }
+
+    #[inline(always)]
+    fn has_error(&self) -> bool {
+        T::has_error(self)
+    }
+
+    #[inline(always)]
+    fn take_error(&mut self) -> Option<ModelError> {
+        T::take_error(self)
+    }
+
+    #[inline(always)]
+    fn take_isolated_error(&mut self) -> Option<ModelError> {
+        T::take_isolated_error(self)
+    }
 }
 
 unsafe impl<T: AbstractSimulator, const N: usize> AbstractSimulator for [T; N] {
@@ -270,6 +777,52 @@ unsafe impl<T: AbstractSimulator, const N: usize> AbstractSimulator for [T; N] {
             .map(|((processor, input), output)| T::delta(processor, input, output, t))
             .fold(f64::INFINITY, f64::min)
     }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        self.iter_mut()
+            .zip(input.iter_mut())
+            .map(|(processor, input)| T::delta_ext(processor, input, t))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        self.iter().map(T::t_next).fold(f64::INFINITY, f64::min)
+    }
+
+    #[inline(always)]
+    fn build_input(&self) -> Self::Input {
+        core::array::from_fn(|i| self[i].build_input())
+    }
+
+    #[inline(always)]
+    fn build_output(&self) -> Self::Output {
+        core::array::from_fn(|i| self[i].build_output())
+    }
+
+    #[inline(always)]
+    fn has_error(&self) -> bool {
+        self.iter().any(T::has_error)
+    }
+
+    #[inline(always)]
+    fn take_error(&mut self) -> Option<ModelError> {
+        self.iter_mut().enumerate().find_map(|(i, processor)| {
+            let mut error = T::take_error(processor)?;
+            error.push_parent(PathSegment::Index(i));
+            Some(error)
+        })
+    }
+
+    #[inline(always)]
+    fn take_isolated_error(&mut self) -> Option<ModelError> {
+        self.iter_mut().enumerate().find_map(|(i, processor)| {
+            let mut error = T::take_isolated_error(processor)?;
+            error.push_parent(PathSegment::Index(i));
+            Some(error)
+        })
+    }
 }
 
 unsafe impl<T: AbstractSimulator> AbstractSimulator for Option<T> {
@@ -308,6 +861,136 @@ unsafe impl<T: AbstractSimulator> AbstractSimulator for Option<T> {
             }
         }
     }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        match self {
+            Some(processor) => T::delta_ext(processor, input, t),
+            None => {
+                input.clear();
+                f64::INFINITY
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        match self {
+            Some(processor) => T::t_next(processor),
+            None => f64::INFINITY,
+        }
+    }
+
+    #[inline(always)]
+    fn build_input(&self) -> Self::Input {
+        match self {
+            Some(processor) => T::build_input(processor),
+            None => Bag::build(),
+        }
+    }
+
+    #[inline(always)]
+    fn build_output(&self) -> Self::Output {
+        match self {
+            Some(processor) => T::build_output(processor),
+            None => Bag::build(),
+        }
+    }
+
+    #[inline(always)]
+    fn has_error(&self) -> bool {
+        self.as_ref().is_some_and(T::has_error)
+    }
+
+    #[inline(always)]
+    fn take_error(&mut self) -> Option<ModelError> {
+        self.as_mut().and_then(T::take_error)
+    }
+
+    #[inline(always)]
+    fn take_isolated_error(&mut self) -> Option<ModelError> {
+        self.as_mut().and_then(T::take_isolated_error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl<T: AbstractSimulator> AbstractSimulator for alloc::vec::Vec<T> {
+    type Input = alloc::vec::Vec<T::Input>;
+    type Output = alloc::vec::Vec<T::Output>;
+
+    #[inline(always)]
+    fn start(&mut self, t_start: f64) -> f64 {
+        self.iter_mut()
+            .map(|processor| T::start(processor, t_start))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[inline(always)]
+    fn stop(&mut self) {
+        self.iter_mut().for_each(|processor| T::stop(processor));
+    }
+
+    #[inline(always)]
+    fn lambda(&mut self, output: &mut Self::Output, t: f64) {
+        for (processor, output) in self.iter_mut().zip(output.iter_mut()) {
+            T::lambda(processor, output, t);
+        }
+    }
+
+    #[inline(always)]
+    fn delta(&mut self, input: &mut Self::Input, output: &mut Self::Output, t: f64) -> f64 {
+        self.iter_mut()
+            .zip(input.iter_mut())
+            .zip(output.iter_mut())
+            .map(|((processor, input), output)| T::delta(processor, input, output, t))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        self.iter_mut()
+            .zip(input.iter_mut())
+            .map(|(processor, input)| T::delta_ext(processor, input, t))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        self.iter().map(T::t_next).fold(f64::INFINITY, f64::min)
+    }
+
+    #[inline(always)]
+    fn build_input(&self) -> Self::Input {
+        self.iter().map(T::build_input).collect()
+    }
+
+    #[inline(always)]
+    fn build_output(&self) -> Self::Output {
+        self.iter().map(T::build_output).collect()
+    }
+
+    #[inline(always)]
+    fn has_error(&self) -> bool {
+        self.iter().any(T::has_error)
+    }
+
+    #[inline(always)]
+    fn take_error(&mut self) -> Option<ModelError> {
+        self.iter_mut().enumerate().find_map(|(i, processor)| {
+            let mut error = T::take_error(processor)?;
+            error.push_parent(PathSegment::Index(i));
+            Some(error)
+        })
+    }
+
+    #[inline(always)]
+    fn take_isolated_error(&mut self) -> Option<ModelError> {
+        self.iter_mut().enumerate().find_map(|(i, processor)| {
+            let mut error = T::take_isolated_error(processor)?;
+            error.push_parent(PathSegment::Index(i));
+            Some(error)
+        })
+    }
 }
 
 macro_rules! impl_abstract_simulator_for_tuple {
@@ -339,6 +1022,53 @@ macro_rules! impl_abstract_simulator_for_tuple {
                 $(min_t = min_t.min(self.$idx.delta(&mut input.$idx, &mut output.$idx, t));)+
                 min_t
             }
+
+            #[inline(always)]
+            fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+                let mut min_t = f64::INFINITY;
+                $(min_t = min_t.min(self.$idx.delta_ext(&mut input.$idx, t));)+
+                min_t
+            }
+
+            #[inline(always)]
+            fn t_next(&self) -> f64 {
+                let mut min_t = f64::INFINITY;
+                $(min_t = min_t.min(self.$idx.t_next());)+
+                min_t
+            }
+
+            #[inline(always)]
+            fn build_input(&self) -> Self::Input {
+                ($(self.$idx.build_input(),)+)
+            }
+
+            #[inline(always)]
+            fn build_output(&self) -> Self::Output {
+                ($(self.$idx.build_output(),)+)
+            }
+
+            #[inline(always)]
+            fn has_error(&self) -> bool {
+                false $(|| self.$idx.has_error())+
+            }
+
+            #[inline(always)]
+            fn take_error(&mut self) -> Option<ModelError> {
+                $(if let Some(mut error) = self.$idx.take_error() {
+                    error.push_parent(PathSegment::Index($idx));
+                    return Some(error);
+                })+
+                None
+            }
+
+            #[inline(always)]
+            fn take_isolated_error(&mut self) -> Option<ModelError> {
+                $(if let Some(mut error) = self.$idx.take_isolated_error() {
+                    error.push_parent(PathSegment::Index($idx));
+                    return Some(error);
+                })+
+                None
+            }
         }
     }
 }
@@ -427,6 +1157,23 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<T, K> Simulable<alloc::vec::Vec<K>> for alloc::vec::Vec<T>
+where
+    T: Component<Kind = K>,
+    T: Simulable<K>,
+    K: crate::component::sealed::Sealed,
+{
+    type Simulator = alloc::vec::Vec<T::Simulator>;
+
+    #[inline(always)]
+    fn to_simulator(self) -> Self::Simulator {
+        self.into_iter()
+            .map(|component| component.to_simulator())
+            .collect()
+    }
+}
+
 // Module with models for simulation, simulator and coordinator testing
 #[cfg(test)]
 pub(crate) mod test_utils {
@@ -434,7 +1181,7 @@ pub(crate) mod test_utils {
         component::coupled::{ComponentsInput, ComponentsOutput, Coupled},
         component::CoupledKind,
         port::Port,
-        Atomic, AtomicKind, Component,
+        Atomic, AtomicKind, Component, TryAtomic, TryAtomicKind,
     };
 
     pub(crate) struct TestAtomic {
@@ -444,6 +1191,8 @@ pub(crate) mod test_utils {
         pub ext_calls: usize,
         pub last_elapsed: f64,
         pub out_val: usize,
+        /// Time advance after an external transition.
+        pub ext_sigma: f64,
     }
 
     impl Component for TestAtomic {
@@ -460,7 +1209,7 @@ pub(crate) mod test_utils {
         fn delta_ext(&mut self, elapsed: f64, _input: &Self::Input) {
             self.ext_calls += 1;
             self.last_elapsed = elapsed;
-            self.sigma = 0.0;
+            self.sigma = self.ext_sigma;
         }
         fn lambda(&self, output: &mut Self::Output) {
             let _ = output.add_value(self.out_val);
@@ -479,6 +1228,7 @@ pub(crate) mod test_utils {
                 ext_calls: 0,
                 last_elapsed: 0.0,
                 out_val: 99,
+                ext_sigma: 0.0,
             }
         }
         pub(crate) fn oneshot(sigma: f64) -> Self {
@@ -486,6 +1236,68 @@ pub(crate) mod test_utils {
         }
     }
 
+    #[derive(Debug)]
+    pub(crate) enum TestError {
+        #[allow(dead_code)]
+        Overflow(usize),
+    }
+
+    /// Periodic atomic model that fails in its `fail_at`-th internal transition.
+    /// It fails in its output function if `fail_at` is 0.
+    pub(crate) struct TestTryAtomic {
+        pub sigma: f64,
+        pub fail_at: usize,
+        pub int_calls: usize,
+        pub ext_calls: usize,
+    }
+
+    impl TestTryAtomic {
+        pub(crate) fn new(sigma: f64, fail_at: usize) -> Self {
+            Self {
+                sigma,
+                fail_at,
+                int_calls: 0,
+                ext_calls: 0,
+            }
+        }
+    }
+
+    impl Component for TestTryAtomic {
+        type Kind = TryAtomicKind;
+        type Input = Port<usize, 1>;
+        type Output = Port<usize, 1>;
+    }
+
+    impl TryAtomic for TestTryAtomic {
+        type Error = TestError;
+
+        fn try_delta_int(&mut self) -> Result<(), Self::Error> {
+            self.int_calls += 1;
+            match self.int_calls == self.fail_at {
+                true => Err(TestError::Overflow(self.int_calls)),
+                false => Ok(()),
+            }
+        }
+        fn try_delta_ext(
+            &mut self,
+            _elapsed: f64,
+            _input: &Self::Input,
+        ) -> Result<(), Self::Error> {
+            self.ext_calls += 1;
+            Ok(())
+        }
+        fn try_lambda(&self, output: &mut Self::Output) -> Result<(), Self::Error> {
+            if self.fail_at == 0 {
+                return Err(TestError::Overflow(0));
+            }
+            let _ = output.add_value(self.int_calls + 1);
+            Ok(())
+        }
+        fn ta(&self) -> f64 {
+            self.sigma
+        }
+    }
+
     #[crate::coupled]
     pub(crate) struct TestCoupled {
         pub a0: TestAtomic,
@@ -531,13 +1343,17 @@ pub(crate) mod test_utils {
 
 #[cfg(test)]
 mod tests {
-    use super::test_utils::{TestAtomic, TestCoupled, TestCoupledWithOption};
+    use super::test_utils::{TestAtomic, TestCoupled, TestCoupledWithOption, TestTryAtomic};
     use crate::{
         component::coupled::PartialCoupled,
         port::Port,
-        simulation::{simulator::Simulator, AbstractSimulator, Config, Simulable},
+        simulation::{
+            simulator::Simulator, AbstractSimulator, Config, Pace, Pacer, Pacing, Simulable,
+            SuperDenseTime,
+        },
         Component,
     };
+    use core::time::Duration;
     #[test]
     fn simulate_vt_single_event() {
         let mut sim = TestAtomic::oneshot(5.0).to_simulator();
@@ -651,6 +1467,29 @@ mod tests {
         assert_eq!(sim.ext_calls, 0, "no external transitions");
     }
 
+    #[tokio::test]
+    async fn try_simulate_rt_async_returns_error() {
+        let mut sim = TestTryAtomic::new(1.0, 2).to_simulator();
+        let config = Config::new(0.0, 10.0, 1.0, None);
+        let mut steps = 0;
+        let error = sim
+            .try_simulate_rt_async(&config, IdentityAsyncInput, |_| steps += 1)
+            .await
+            .unwrap_err();
+        assert_eq!(error.message(), "Overflow(2)");
+        assert_eq!(steps, 2, "simulation aborted at t = 2");
+        assert!(sim.is_failed());
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "simulation aborted")]
+    async fn simulate_rt_async_panics_on_error() {
+        let mut sim = TestTryAtomic::new(1.0, 0).to_simulator();
+        let config = Config::new(0.0, 10.0, 1.0, None);
+        sim.simulate_rt_async(&config, IdentityAsyncInput, |_| {})
+            .await;
+    }
+
     #[tokio::test]
     async fn simulate_rt_async_external_input() {
         let mut sim = TestAtomic::oneshot(5.0).to_simulator();
@@ -699,6 +1538,186 @@ mod tests {
         assert_eq!(captured.get_values(), &[99], "async propagate_output");
     }
 
+    #[test]
+    fn super_dense_time_advance() {
+        let t = SuperDenseTime::new(1.0, 0);
+        assert_eq!(t.advance(1.0), SuperDenseTime::new(1.0, 1));
+        assert_eq!(t.advance(1.0).advance(2.0), SuperDenseTime::new(2.0, 0));
+        assert!(t < t.advance(1.0), "lexicographic order");
+        assert!(t.advance(1.0) < SuperDenseTime::new(1.5, 0));
+    }
+
+    #[test]
+    fn pacer_modes() {
+        let jitter = Some(Duration::from_millis(1));
+        let config = Config::new(0.0, 10.0, 0.5, jitter);
+        let mut pacer = Pacer::new(&config);
+        assert_eq!(pacer.pace(0.0, 1.0), Pace::Paced);
+        assert_eq!(pacer.max_jitter(), jitter);
+
+        let pacer = Pacer::new(&config.with_pacing(Pacing::CatchUp));
+        assert_eq!(pacer.max_jitter(), None);
+
+        // 2 seconds of history with time scale 0.5 are 4 units of virtual time
+        let config = config.with_pacing(Pacing::FastForward(Duration::from_secs(2)));
+        let mut pacer = Pacer::new(&config);
+        assert_eq!(pacer.pace(0.0, 3.0), Pace::Skip);
+        assert_eq!(pacer.pace(3.0, 4.0), Pace::Skip);
+        assert_eq!(pacer.pace(4.0, 6.0), Pace::Resync(4.0));
+        assert_eq!(pacer.pace(6.0, 7.0), Pace::Paced);
+        assert_eq!(pacer.max_jitter(), jitter);
+    }
+
+    #[test]
+    fn pacer_schedule() {
+        let config = Config::new(0.0, 10.0, 0.5, None)
+            .with_pacing(Pacing::FastForward(Duration::from_secs(2)));
+        assert_eq!(config.pacing(), Pacing::FastForward(Duration::from_secs(2)));
+        let mut pacer = Pacer::new(&config);
+        let now = || 100;
+        assert_eq!(pacer.schedule(0.0, 3.0, 10, now), None, "history");
+        assert_eq!(
+            pacer.schedule(3.5, 6.0, 10, now),
+            Some((4.0, 100)),
+            "schedule restarts when going live"
+        );
+        assert_eq!(pacer.schedule(6.0, 7.0, 10, now), Some((6.0, 10)));
+    }
+
+    /// a0 fires every second, a1 reacts to it with a zero-delay transition
+    fn zero_delay_cascade(
+    ) -> impl AbstractSimulator<Input = Port<usize, 1>, Output = Port<usize, 1>> {
+        TestCoupled::build(
+            TestAtomic::periodic(0.0, 1.0),
+            TestAtomic::oneshot(f64::INFINITY),
+        )
+        .to_simulator()
+    }
+
+    #[test]
+    fn simulate_rt_dense_zero_delay() {
+        let mut sim = zero_delay_cascade();
+        let config = Config::new(0.0, 1.5, 1.0, None);
+        let mut steps = heapless::Vec::<(f64, u32, usize), 8>::new();
+
+        sim.simulate_rt_dense(
+            &config,
+            |_, t_until, _| t_until,
+            |t, output| steps.push((t.real, t.micro_step, output.len())).unwrap(),
+        );
+
+        assert_eq!(
+            steps.as_slice(),
+            &[(0.0, 1, 0), (0.0, 2, 1), (1.0, 0, 0), (1.0, 1, 1)],
+            "a1 outputs one micro-step after a0"
+        );
+    }
+
+    #[test]
+    fn simulate_vt_dense_zero_delay() {
+        let mut sim = zero_delay_cascade();
+        let config = Config::new(0.0, 1.5, 1.0, None);
+        let mut steps = heapless::Vec::<(f64, u32, usize), 8>::new();
+
+        sim.simulate_vt_dense(&config, |t, output| {
+            steps.push((t.real, t.micro_step, output.len())).unwrap()
+        });
+
+        assert_eq!(
+            steps.as_slice(),
+            &[(0.0, 1, 0), (0.0, 2, 1), (1.0, 0, 0), (1.0, 1, 1)]
+        );
+    }
+
+    #[test]
+    fn simulate_rt_dense_empty_wake_ups() {
+        let mut sim = zero_delay_cascade();
+        let config = Config::new(0.0, 1.5, 1.0, None);
+        let mut t_from = heapless::Vec::<(f64, u32), 16>::new();
+        let mut wake_ups = 0;
+
+        sim.simulate_rt_dense(
+            &config,
+            |t, t_until, input| {
+                t_from.push((t.real, t.micro_step)).unwrap();
+                wake_ups += 1;
+                match wake_ups {
+                    // wake up twice at 0.5 without input, then with input
+                    3 | 4 => 0.5,
+                    5 => {
+                        input.add_value(7).unwrap();
+                        0.5
+                    }
+                    _ => t_until,
+                }
+            },
+            |_, _| {},
+        );
+
+        assert_eq!(
+            &t_from[..7],
+            &[
+                (0.0, 0),
+                (0.0, 1),
+                (0.0, 2),
+                (0.5, 0), // after a spurious wake-up
+                (0.5, 0), // after a spurious wake-up
+                (0.5, 0), // after the external transition
+                (0.5, 1), // after a0 reacts with a zero-delay transition
+            ],
+            "empty wake-ups do not advance the micro-step"
+        );
+    }
+
+    #[tokio::test]
+    async fn simulate_rt_async_dense_handler() {
+        struct DenseInput<'a>(&'a mut heapless::Vec<SuperDenseTime, 8>);
+
+        impl crate::simulation::AsyncInput for DenseInput<'_> {
+            type Input = Port<usize, 1>;
+            async fn handle(
+                &mut self,
+                _config: &Config,
+                _t_from: f64,
+                _t_until: f64,
+                _input: &mut Self::Input,
+            ) -> f64 {
+                unreachable!("handle_dense is overridden")
+            }
+            async fn handle_dense(
+                &mut self,
+                _config: &Config,
+                t_from: SuperDenseTime,
+                t_until: f64,
+                _input: &mut Self::Input,
+            ) -> f64 {
+                self.0.push(t_from).unwrap();
+                t_until
+            }
+        }
+
+        let mut sim = zero_delay_cascade();
+        let config = Config::new(0.0, 1.5, 1.0, None);
+        let mut t_from = heapless::Vec::new();
+        let mut outputs = heapless::Vec::<_, 8>::new();
+        sim.simulate_rt_async_dense(&config, DenseInput(&mut t_from), |t, output| {
+            if !output.is_empty() {
+                outputs.push(t).unwrap();
+            }
+        })
+        .await;
+
+        assert_eq!(
+            outputs.as_slice(),
+            &[SuperDenseTime::new(0.0, 2), SuperDenseTime::new(1.0, 1)]
+        );
+        let t_from: heapless::Vec<_, 8> = t_from.iter().map(|t| (t.real, t.micro_step)).collect();
+        assert_eq!(
+            t_from.as_slice(),
+            &[(0.0, 0), (0.0, 1), (0.0, 2), (1.0, 0), (1.0, 1)]
+        );
+    }
+
     #[test]
     fn array_start_returns_min() {
         let a0 = TestAtomic::oneshot(3.0);
@@ -939,6 +1958,10 @@ mod tests {
 
         impl crate::component::coupled::PartialCoupled for ArrayCoupled {
             type Components = ArrayCoupledComponents;
+            type Buffers = crate::simulation::coordinator::OwnedBuffers<
+                ComponentsInput<Self>,
+                ComponentsOutput<Self>,
+            >;
             fn get_components(&self) -> &crate::component::coupled::Components<Self> {
                 &self.components
             }
@@ -0,0 +1,995 @@
+//! Conservative distributed simulation on targets with `std`.
+//!
+//! A model is split into several partitions, and every partition is simulated by its own
+//! [`Partition`] in a different thread or process. From the point of view of a partition,
+//! the rest of the model is a remote proxy: output events are sent through output links,
+//! and input events are received from input links. Links carry [`Message`]s and are
+//! synchronized with the Chandy-Misra-Bryant null-message protocol.
+//!
+//! # Lookahead
+//!
+//! Every output link has a lookahead `L > 0` declared by the user. It is a promise of the
+//! sending partition: every event sent through the link is scheduled at least `L` in advance.
+//! In other words, an event sent at time `t` was scheduled by a transition at time `t - L` or
+//! before (events sent in the first step at `t_start` are exempt). For example, a link fed by
+//! a generator with a period of `1.0` may have a lookahead of `1.0`.
+//! If every link honors its lookahead, the results are exactly the same as those of
+//! [`simulate_vt`](crate::simulation::AbstractSimulator::simulate_vt) with the whole model in a single process.
+//! Links that do not honor their lookahead are detected by the receiving partition, which fails with an
+//! [`InvalidData`](io::ErrorKind::InvalidData) error.
+//!
+//! # Remote proxies
+//!
+//! A sub-model of a `#[coupled]` hierarchy can be moved to a remote [`Partition`] and replaced by a
+//! [`Remote`] proxy with the same input and output bags. The rest of the hierarchy does not change,
+//! and it is simulated as usual (e.g., with `simulate_vt`). Links leaving the remote partition
+//! declare their lookahead as usual, while the proxy announces exact bounds, as it is simulated
+//! together with the models that feed it.
+
+use crate::{
+    port::{Bag, BagMux},
+    simulation::{error::ModelError, AbstractSimulator, Config},
+    transport::{Codec, WireFormat},
+    wire::{WireCodec, WireError},
+    Component, ComponentsKind,
+};
+use std::{
+    boxed::Box,
+    collections::VecDeque,
+    format,
+    io::{self, Read, Write},
+    marker::PhantomData,
+    net::TcpStream,
+    sync::mpsc,
+    vec::Vec,
+};
+
+/// Maximum length (in bytes) of an encoded message sent through a [`TcpStream`] link.
+pub const MAX_MESSAGE_LEN: usize = 512;
+
+/// Message exchanged by partitions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Message<T> {
+    /// Event sent at the given simulation time.
+    Event(f64, T),
+    /// Promise that no event will be sent through the link before the given simulation time.
+    Null(f64),
+}
+
+impl<T: WireCodec> WireCodec for Message<T> {
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        match self {
+            Self::Event(t, event) => {
+                let mut n = 0u8.encode(buf)?;
+                n += t.encode(&mut buf[n..])?;
+                n += event.encode(&mut buf[n..])?;
+                Ok(n)
+            }
+            Self::Null(t) => {
+                let n = 1u8.encode(buf)?;
+                Ok(n + t.encode(&mut buf[n..])?)
+            }
+        }
+    }
+
+    fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+        let (tag, mut n) = u8::decode(buf)?;
+        let (t, m) = f64::decode(&buf[n..])?;
+        n += m;
+        match tag {
+            0 => {
+                let (event, m) = T::decode(&buf[n..])?;
+                Ok((Self::Event(t, event), n + m))
+            }
+            1 => Ok((Self::Null(t), n)),
+            _ => Err(WireError::InvalidValue),
+        }
+    }
+}
+
+/// Sending half of a link between two partitions.
+pub trait LinkSender<T> {
+    /// Sends a message to the remote partition.
+    fn send(&mut self, message: Message<T>) -> io::Result<()>;
+}
+
+/// Receiving half of a link between two partitions.
+pub trait LinkReceiver<T> {
+    /// Blocks until the next message from the remote partition is received.
+    fn recv(&mut self) -> io::Result<Message<T>>;
+}
+
+impl<T> LinkSender<T> for mpsc::Sender<Message<T>> {
+    fn send(&mut self, message: Message<T>) -> io::Result<()> {
+        mpsc::Sender::send(self, message)
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "link receiver is gone"))
+    }
+}
+
+impl<T> LinkReceiver<T> for mpsc::Receiver<Message<T>> {
+    fn recv(&mut self) -> io::Result<Message<T>> {
+        mpsc::Receiver::recv(self)
+            .map_err(|_| io::Error::new(io::ErrorKind::UnexpectedEof, "link sender is gone"))
+    }
+}
+
+/// Messages are sent as a little-endian `u16` length followed by the [`wire`](crate::wire) encoding of the message.
+impl<T: WireCodec> LinkSender<T> for TcpStream {
+    fn send(&mut self, message: Message<T>) -> io::Result<()> {
+        let mut buf = [0; MAX_MESSAGE_LEN + 2];
+        let n = WireFormat
+            .encode(&message, &mut buf[2..])
+            .map_err(wire_error)?;
+        buf[..2].copy_from_slice(&(n as u16).to_le_bytes());
+        self.write_all(&buf[..n + 2])
+    }
+}
+
+/// Messages are received as a little-endian `u16` length followed by the [`wire`](crate::wire) encoding of the message.
+impl<T: WireCodec> LinkReceiver<T> for TcpStream {
+    fn recv(&mut self) -> io::Result<Message<T>> {
+        let mut len = [0; 2];
+        self.read_exact(&mut len)?;
+        let len = u16::from_le_bytes(len) as usize;
+        if len > MAX_MESSAGE_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "message is too long",
+            ));
+        }
+        let mut buf = [0; MAX_MESSAGE_LEN];
+        self.read_exact(&mut buf[..len])?;
+        WireFormat.decode(&buf[..len]).map_err(wire_error)
+    }
+}
+
+fn wire_error(err: WireError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{err:?}"))
+}
+
+type InputMux<S> = <<S as AbstractSimulator>::Input as BagMux>::Mux;
+type OutputMux<S> = <<S as AbstractSimulator>::Output as BagMux>::Mux;
+
+/// Input link of a partition.
+struct InputLink<'a, I> {
+    receiver: Box<dyn LinkReceiver<I> + Send + 'a>,
+    /// Earliest input time: no event with a lower time will be received from this link.
+    eit: f64,
+    /// Events received but not injected yet.
+    pending: VecDeque<(f64, I)>,
+}
+
+impl<'a, I> InputLink<'a, I> {
+    fn new(link: impl LinkReceiver<I> + Send + 'a) -> Self {
+        Self {
+            receiver: Box::new(link),
+            eit: f64::NEG_INFINITY,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Blocks until the next message is received from the link.
+    fn recv(&mut self) -> io::Result<()> {
+        match self.receiver.recv()? {
+            Message::Event(t, event) => {
+                if t < self.eit {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "event received before the announced bound (is the lookahead too large?)",
+                    ));
+                }
+                self.eit = t;
+                self.pending.push_back((t, event));
+            }
+            Message::Null(t) => self.eit = self.eit.max(t),
+        }
+        Ok(())
+    }
+}
+
+/// Earliest input time of a set of input links.
+fn eit<I>(inputs: &[InputLink<'_, I>]) -> f64 {
+    inputs
+        .iter()
+        .map(|link| link.eit)
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Time of the earliest input event received from a set of input links but not injected yet.
+fn earliest_pending<I>(inputs: &[InputLink<'_, I>]) -> f64 {
+    inputs
+        .iter()
+        .filter_map(|link| link.pending.front().map(|(t, _)| *t))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Blocks until a message is received from the input link with the lowest earliest input time.
+fn recv<I>(inputs: &mut [InputLink<'_, I>]) -> io::Result<()> {
+    match inputs.iter_mut().min_by(|a, b| a.eit.total_cmp(&b.eit)) {
+        Some(link) => link.recv(),
+        None => Ok(()),
+    }
+}
+
+/// Type-erased output link of a partition.
+trait OutputPort<O> {
+    fn send_event(&mut self, t: f64, event: &O) -> io::Result<()>;
+
+    fn send_null(&mut self, t: f64) -> io::Result<()>;
+}
+
+struct Coupling<L, F, T> {
+    link: L,
+    coupling: F,
+    _event: PhantomData<fn() -> T>,
+}
+
+impl<O, T, L, F> OutputPort<O> for Coupling<L, F, T>
+where
+    L: LinkSender<T>,
+    F: FnMut(&O) -> Option<T>,
+{
+    fn send_event(&mut self, t: f64, event: &O) -> io::Result<()> {
+        match (self.coupling)(event) {
+            Some(event) => self.link.send(Message::Event(t, event)),
+            None => Ok(()),
+        }
+    }
+
+    fn send_null(&mut self, t: f64) -> io::Result<()> {
+        self.link.send(Message::Null(t))
+    }
+}
+
+/// Output link of a partition.
+struct OutputLink<'a, O> {
+    port: Box<dyn OutputPort<O> + Send + 'a>,
+    lookahead: f64,
+    /// Last lower bound announced to the remote partition.
+    bound: f64,
+}
+
+/// Part of a distributed model, simulated with the conservative null-message protocol.
+///
+/// The input events of the partition are received from input links as
+/// [`BagMux::Mux`] values of its input bag, and its output events are sent
+/// to output links after being mapped by a coupling function.
+pub struct Partition<'a, S: AbstractSimulator>
+where
+    S::Input: BagMux,
+    S::Output: BagMux,
+{
+    simulator: S,
+    inputs: Vec<InputLink<'a, InputMux<S>>>,
+    outputs: Vec<OutputLink<'a, OutputMux<S>>>,
+}
+
+impl<'a, S: AbstractSimulator> Partition<'a, S>
+where
+    S::Input: BagMux,
+    S::Output: BagMux,
+{
+    /// Creates a new partition without links.
+    pub fn new(simulator: S) -> Self {
+        Self {
+            simulator,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Adds an input link. Received events are injected to the input bag of the partition.
+    pub fn with_input(mut self, link: impl LinkReceiver<InputMux<S>> + Send + 'a) -> Self {
+        self.inputs.push(InputLink::new(link));
+        self
+    }
+
+    /// Adds an output link with the given lookahead.
+    ///
+    /// Every output event of the partition is mapped by `coupling`. If it returns `Some`,
+    /// the resulting event is sent through the link. Several output links may lead to the same
+    /// remote partition (e.g., to feed more than one of its input ports with the same event).
+    ///
+    /// # Panics
+    ///
+    /// It panics if `lookahead` is not greater than zero.
+    pub fn with_output<T: 'a>(
+        mut self,
+        link: impl LinkSender<T> + Send + 'a,
+        lookahead: f64,
+        coupling: impl FnMut(&OutputMux<S>) -> Option<T> + Send + 'a,
+    ) -> Self {
+        assert!(lookahead > 0.0, "lookahead must be greater than zero");
+        self.outputs.push(OutputLink {
+            port: Box::new(Coupling {
+                link,
+                coupling,
+                _event: PhantomData,
+            }),
+            lookahead,
+            bound: f64::NEG_INFINITY,
+        });
+        self
+    }
+
+    /// Returns a reference to the simulator of the partition.
+    pub fn simulator(&self) -> &S {
+        &self.simulator
+    }
+
+    /// Consumes the partition and returns its simulator.
+    pub fn into_inner(self) -> S {
+        self.simulator
+    }
+
+    /// Executes the simulation of the partition from `t_start` to `t_stop` with a virtual clock.
+    ///
+    /// All the partitions of the model must be simulated with the same configuration.
+    /// Before returning, it announces the end of the simulation to the remote partitions and
+    /// waits until they announce it too, so links can be safely dropped afterwards.
+    pub fn simulate(&mut self, config: &Config) -> io::Result<()> {
+        let (t_start, t_stop) = (config.t_start, config.t_stop);
+        for link in self.inputs.iter_mut() {
+            link.eit = t_start;
+            link.pending.clear();
+        }
+        for link in self.outputs.iter_mut() {
+            link.bound = t_start;
+        }
+
+        let mut t_next = self.simulator.start(t_start);
+        let mut input = self.simulator.build_input();
+        let mut output = self.simulator.build_output();
+        // Time of the output function computed ahead of its state transition (if any)
+        let mut lambda_at = None;
+        let mut done = t_start >= t_stop;
+        while !done {
+            let eit = self.eit();
+            let t = f64::min(t_next, self.earliest_pending());
+            let horizon = f64::min(t, eit);
+            if horizon > t_stop || horizon == f64::INFINITY {
+                break;
+            }
+            if t < eit {
+                // All the inputs up to t are known: we can safely execute the next step
+                if t >= t_next && lambda_at != Some(t) {
+                    self.lambda(&mut output, t)?;
+                }
+                for link in self.inputs.iter_mut() {
+                    while link.pending.front().is_some_and(|(te, _)| *te == t) {
+                        let (_, event) = link.pending.pop_front().unwrap();
+                        input.inject_event(event).map_err(|_| {
+                            io::Error::new(io::ErrorKind::InvalidData, "input port is full")
+                        })?;
+                    }
+                }
+                t_next = self.simulator.delta(&mut input, &mut output, t);
+                lambda_at = None;
+                done = t >= t_stop;
+            } else {
+                // Inputs at t may still arrive. However, no input can arrive before t = eit,
+                // so the output function of an imminent step at t does not depend on them.
+                if t == t_next && t == eit && t <= t_stop && lambda_at.is_none() {
+                    self.lambda(&mut output, t)?;
+                    lambda_at = Some(t);
+                }
+                self.send_nulls(t_next, lambda_at)?;
+                self.recv()?;
+            }
+        }
+        self.simulator.stop();
+
+        for link in self.outputs.iter_mut() {
+            if link.bound < f64::INFINITY {
+                link.bound = f64::INFINITY;
+                link.port.send_null(f64::INFINITY)?;
+            }
+        }
+        // Remote partitions may still send messages: drain the links until they are done
+        while self.eit() < f64::INFINITY {
+            self.recv()?;
+        }
+        for link in self.inputs.iter_mut() {
+            link.pending.clear();
+        }
+        Ok(())
+    }
+
+    /// Earliest input time of the partition.
+    fn eit(&self) -> f64 {
+        eit(&self.inputs)
+    }
+
+    /// Time of the earliest input event received but not injected yet.
+    fn earliest_pending(&self) -> f64 {
+        earliest_pending(&self.inputs)
+    }
+
+    /// Computes the output function at time `t` and sends the output events through the output links.
+    fn lambda(&mut self, output: &mut S::Output, t: f64) -> io::Result<()> {
+        self.simulator.lambda(output, t);
+        let mut res = Ok(());
+        output.eject_events(|event| {
+            for link in self.outputs.iter_mut() {
+                if res.is_ok() {
+                    res = link.port.send_event(t, &event);
+                }
+            }
+        });
+        res
+    }
+
+    /// Announces new lower bounds on the time of the next output event of every output link.
+    fn send_nulls(&mut self, t_next: f64, lambda_at: Option<f64>) -> io::Result<()> {
+        let eit = self.eit();
+        let t_pending = self.earliest_pending();
+        for link in self.outputs.iter_mut() {
+            let bound = match lambda_at {
+                // The output at t has already been sent, the next one must come later
+                Some(t) => t.next_up(),
+                None => f64::min(t_next, f64::min(t_pending, eit) + link.lookahead),
+            };
+            if bound > link.bound {
+                link.bound = bound;
+                link.port.send_null(bound)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Blocks until a message is received from the input link with the lowest earliest input time.
+    fn recv(&mut self) -> io::Result<()> {
+        recv(&mut self.inputs)
+    }
+}
+
+/// Output link of a [`Remote`] proxy.
+struct ProxyLink<I> {
+    port: Box<dyn OutputPort<I> + Send>,
+    /// Last lower bound announced to the remote partition.
+    bound: f64,
+}
+
+impl<I> ProxyLink<I> {
+    /// Announces a new lower bound on the time of the next event sent through the link.
+    fn announce(&mut self, bound: f64) -> io::Result<()> {
+        if bound > self.bound {
+            self.bound = bound;
+            self.port.send_null(bound)?;
+        }
+        Ok(())
+    }
+}
+
+/// Proxy of a sub-model that is simulated by a remote [`Partition`] (see the [module documentation](self)).
+///
+/// It has the input and output bags of the sub-model, so it replaces the sub-model in a `#[coupled]`
+/// hierarchy. Its input events are sent through its output links, and the events received from its
+/// input links are emitted as its output events at the time they were sent by the remote partition.
+///
+/// Before emitting its output at time `t`, the proxy blocks until all the events sent by the remote
+/// partition up to `t` are known. Meanwhile, it announces to the remote partition that no event will
+/// be sent before `t`. If a link fails, the proxy fails with the I/O error and becomes passive.
+pub struct Remote<I: BagMux, O: BagMux> {
+    inputs: Vec<InputLink<'static, O::Mux>>,
+    outputs: Vec<ProxyLink<I::Mux>>,
+    t_next: f64,
+    failed: bool,
+    error: Option<io::Error>,
+}
+
+impl<I: BagMux, O: BagMux> Remote<I, O> {
+    /// Creates a new proxy without links.
+    pub fn new() -> Self {
+        Self {
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            t_next: f64::INFINITY,
+            failed: false,
+            error: None,
+        }
+    }
+
+    /// Adds an input link. Received events are emitted through the output bag of the proxy.
+    pub fn with_input(mut self, link: impl LinkReceiver<O::Mux> + Send + 'static) -> Self {
+        self.inputs.push(InputLink::new(link));
+        self
+    }
+
+    /// Adds an output link. Every input event of the proxy is mapped by `coupling`.
+    /// If it returns `Some`, the resulting event is sent through the link.
+    pub fn with_output<T: 'static>(
+        mut self,
+        link: impl LinkSender<T> + Send + 'static,
+        coupling: impl FnMut(&I::Mux) -> Option<T> + Send + 'static,
+    ) -> Self {
+        self.outputs.push(ProxyLink {
+            port: Box::new(Coupling {
+                link,
+                coupling,
+                _event: PhantomData,
+            }),
+            bound: f64::NEG_INFINITY,
+        });
+        self
+    }
+
+    /// Announces `bound` to all the output links.
+    fn announce(&mut self, bound: f64) -> io::Result<()> {
+        self.outputs
+            .iter_mut()
+            .try_for_each(|link| link.announce(bound))
+    }
+
+    /// Blocks until all the remote events up to `t` are known.
+    fn sync(&mut self, t: f64) -> io::Result<()> {
+        while eit(&self.inputs) <= t {
+            self.announce(t)?;
+            recv(&mut self.inputs)?;
+        }
+        Ok(())
+    }
+
+    /// Sends the input events received at time `t` through the output links.
+    fn send(&mut self, input: &I, t: f64) -> io::Result<()> {
+        let mut res = Ok(());
+        input.eject_events(|event| {
+            for link in self.outputs.iter_mut() {
+                if res.is_ok() {
+                    res = link.port.send_event(t, &event);
+                }
+            }
+        });
+        res?;
+        // All the events sent at t are known
+        self.announce(t.next_up())
+    }
+
+    /// Computes the time of the next output of the proxy.
+    fn schedule(&mut self) -> f64 {
+        self.t_next = match self.failed {
+            true => f64::INFINITY,
+            false => f64::min(earliest_pending(&self.inputs), eit(&self.inputs)),
+        };
+        self.t_next
+    }
+
+    /// Keeps the first error and makes the proxy passive.
+    fn fail(&mut self, error: io::Error) {
+        if !self.failed {
+            self.failed = true;
+            self.error = Some(error);
+        }
+        self.t_next = f64::INFINITY;
+    }
+}
+
+impl<I: BagMux, O: BagMux> Default for Remote<I, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: Bag + BagMux, O: Bag + BagMux> Component for Remote<I, O> {
+    type Kind = ComponentsKind;
+    type Input = I;
+    type Output = O;
+}
+
+unsafe impl<I: Bag + BagMux, O: Bag + BagMux> AbstractSimulator for Remote<I, O> {
+    type Input = I;
+    type Output = O;
+
+    fn start(&mut self, t_start: f64) -> f64 {
+        self.failed = false;
+        self.error = None;
+        for link in self.inputs.iter_mut() {
+            link.eit = t_start;
+            link.pending.clear();
+        }
+        for link in self.outputs.iter_mut() {
+            link.bound = t_start;
+        }
+        self.schedule()
+    }
+
+    fn stop(&mut self) {
+        // Remote partitions may still send messages: drain the links until they are done
+        let res = self.announce(f64::INFINITY).and_then(|_| {
+            while eit(&self.inputs) < f64::INFINITY {
+                recv(&mut self.inputs)?;
+            }
+            Ok(())
+        });
+        if let Err(error) = res {
+            self.fail(error);
+        }
+        for link in self.inputs.iter_mut() {
+            link.pending.clear();
+        }
+    }
+
+    fn lambda(&mut self, output: &mut Self::Output, t: f64) {
+        if t < self.t_next {
+            return;
+        }
+        if let Err(error) = self.sync(t) {
+            return self.fail(error);
+        }
+        for link in self.inputs.iter_mut() {
+            while link.pending.front().is_some_and(|(te, _)| *te == t) {
+                let (_, event) = link.pending.pop_front().unwrap();
+                if output.inject_event(event).is_err() {
+                    let error = io::Error::new(io::ErrorKind::InvalidData, "output port is full");
+                    return self.fail(error);
+                }
+            }
+        }
+    }
+
+    fn delta(&mut self, input: &mut Self::Input, output: &mut Self::Output, t: f64) -> f64 {
+        output.clear();
+        if self.failed {
+            input.clear();
+            return f64::INFINITY;
+        }
+        if !input.is_empty() {
+            let res = self.send(input, t);
+            input.clear();
+            if let Err(error) = res {
+                self.fail(error);
+            }
+        }
+        self.schedule()
+    }
+
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        if !self.failed && !input.is_empty() {
+            if let Err(error) = self.send(input, t) {
+                self.fail(error);
+            }
+        }
+        input.clear();
+        self.schedule()
+    }
+
+    fn t_next(&self) -> f64 {
+        self.t_next
+    }
+
+    fn has_error(&self) -> bool {
+        self.error.is_some()
+    }
+
+    fn take_error(&mut self) -> Option<ModelError> {
+        self.error.take().map(|error| ModelError::new(&error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpt::{Generator, Processor, Transducer};
+    use crate::simulation::{error::PathSegment, Simulable};
+    use crate::{Component, ComponentsInput, ComponentsOutput, Coupled, CoupledKind, Port};
+    use std::{net::TcpListener, thread};
+
+    #[derive(crate::Bag, crate::BagMux, crate::WireMux)]
+    pub struct Jobs {
+        job: Port<usize, 1>,
+    }
+
+    type JobsMux = <Jobs as BagMux>::Mux;
+
+    /// Experimental frame: generator and transducer.
+    #[crate::coupled]
+    pub struct Front {
+        generator: Generator,
+        transducer: Transducer,
+    }
+
+    impl Component for Front {
+        type Kind = CoupledKind;
+        type Input = Jobs;
+        type Output = Jobs;
+    }
+
+    impl Coupled for Front {
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            from.generator
+                .couple(&mut to.transducer.in_generator)
+                .unwrap();
+            from.transducer.couple(&mut to.generator).unwrap();
+        }
+        fn eic(from: &Self::Input, to: &mut ComponentsInput<Self>) {
+            from.job.couple(&mut to.transducer.in_processor).unwrap();
+        }
+        fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
+            from.generator.couple(&mut to.job).unwrap();
+        }
+    }
+
+    #[crate::coupled]
+    pub struct Back {
+        processor: Processor,
+    }
+
+    impl Component for Back {
+        type Kind = CoupledKind;
+        type Input = Jobs;
+        type Output = Jobs;
+    }
+
+    impl Coupled for Back {
+        fn eic(from: &Self::Input, to: &mut ComponentsInput<Self>) {
+            from.job.couple(&mut to.processor).unwrap();
+        }
+        fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
+            from.processor.couple(&mut to.job).unwrap();
+        }
+    }
+
+    #[crate::coupled]
+    pub struct Whole {
+        front: Front,
+        back: Back,
+    }
+
+    impl Component for Whole {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for Whole {
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            from.front.job.couple(&mut to.back.job).unwrap();
+            from.back.job.couple(&mut to.front.job).unwrap();
+        }
+    }
+
+    /// Whole model with the back sub-model replaced by a remote proxy.
+    #[crate::coupled]
+    pub struct RemoteBack {
+        front: Front,
+        back: Remote<Jobs, Jobs>,
+    }
+
+    impl Component for RemoteBack {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for RemoteBack {
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            from.front.job.couple(&mut to.back.job).unwrap();
+            from.back.job.couple(&mut to.front.job).unwrap();
+        }
+    }
+
+    /// Whole model with the front sub-model replaced by a remote proxy.
+    #[crate::coupled]
+    pub struct RemoteFront {
+        front: Remote<Jobs, Jobs>,
+        back: Back,
+    }
+
+    impl Component for RemoteFront {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for RemoteFront {
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            from.front.job.couple(&mut to.back.job).unwrap();
+            from.back.job.couple(&mut to.front.job).unwrap();
+        }
+    }
+
+    fn front(period: f64, obs_time: f64) -> Front {
+        Front::build(Generator::new(period), Transducer::new(obs_time))
+    }
+
+    fn back(processing_time: f64) -> Back {
+        Back::build(Processor::new(processing_time))
+    }
+
+    /// Acceptance and throughput of the model simulated in a single process.
+    fn single(period: f64, processing_time: f64, config: &Config) -> (f64, f64) {
+        let whole = Whole::build(front(period, 10.0), back(processing_time));
+        let mut sim = whole.to_simulator();
+        sim.simulate_vt(config);
+        let trans = &*sim.components.front.components.transducer;
+        (trans.acceptance(), trans.throughput())
+    }
+
+    /// Acceptance and throughput of the model simulated in two partitions.
+    fn partitioned<L>(
+        period: f64,
+        processing_time: f64,
+        config: &Config,
+        front_links: (impl LinkReceiver<JobsMux> + Send + 'static, L),
+        back_links: (impl LinkReceiver<JobsMux> + Send + 'static, L),
+    ) -> (f64, f64)
+    where
+        L: LinkSender<JobsMux> + Send + 'static,
+    {
+        let config = *config;
+        let handle = thread::spawn(move || {
+            let mut partition = Partition::new(back(processing_time).to_simulator())
+                .with_input(back_links.0)
+                .with_output(back_links.1, processing_time, |e| Some(e.clone()));
+            partition.simulate(&config).unwrap();
+        });
+        let mut partition = Partition::new(front(period, 10.0).to_simulator())
+            .with_input(front_links.0)
+            .with_output(front_links.1, period, |e| Some(e.clone()));
+        partition.simulate(&config).unwrap();
+        handle.join().unwrap();
+
+        let sim = partition.into_inner();
+        let trans = &*sim.components.transducer;
+        (trans.acceptance(), trans.throughput())
+    }
+
+    /// Acceptance and throughput of the model simulated with `simulate_vt`, with the back sub-model in a remote partition.
+    fn remote_back(period: f64, processing_time: f64, config: &Config) -> (f64, f64) {
+        let ((front_rx, front_tx), (back_rx, back_tx)) = mpsc_links();
+        let config_remote = *config;
+        let handle = thread::spawn(move || {
+            Partition::new(back(processing_time).to_simulator())
+                .with_input(back_rx)
+                .with_output(back_tx, processing_time, |e| Some(e.clone()))
+                .simulate(&config_remote)
+                .unwrap();
+        });
+        let proxy = Remote::new()
+            .with_input(front_rx)
+            .with_output(front_tx, |e: &JobsMux| Some(e.clone()));
+        let mut sim = RemoteBack::build(front(period, 10.0), proxy).to_simulator();
+        sim.simulate_vt(config);
+        handle.join().unwrap();
+
+        let trans = &*sim.components.front.components.transducer;
+        (trans.acceptance(), trans.throughput())
+    }
+
+    /// Acceptance and throughput of the model simulated with `simulate_vt`, with the front sub-model in a remote partition.
+    fn remote_front(period: f64, processing_time: f64, config: &Config) -> (f64, f64) {
+        let ((front_rx, front_tx), (back_rx, back_tx)) = mpsc_links();
+        let config_remote = *config;
+        let handle = thread::spawn(move || {
+            let mut partition = Partition::new(front(period, 10.0).to_simulator())
+                .with_input(front_rx)
+                .with_output(front_tx, period, |e| Some(e.clone()));
+            partition.simulate(&config_remote).unwrap();
+            let trans = &*partition.simulator().components.transducer;
+            (trans.acceptance(), trans.throughput())
+        });
+        let proxy = Remote::new()
+            .with_input(back_rx)
+            .with_output(back_tx, |e: &JobsMux| Some(e.clone()));
+        let mut sim = RemoteFront::build(proxy, back(processing_time)).to_simulator();
+        sim.simulate_vt(config);
+        handle.join().unwrap()
+    }
+
+    type MpscLinks = (
+        mpsc::Receiver<Message<JobsMux>>,
+        mpsc::Sender<Message<JobsMux>>,
+    );
+
+    fn mpsc_links() -> (MpscLinks, MpscLinks) {
+        let (front_tx, back_rx) = mpsc::channel();
+        let (back_tx, front_rx) = mpsc::channel();
+        ((front_rx, front_tx), (back_rx, back_tx))
+    }
+
+    fn tcp_links() -> ((TcpStream, TcpStream), (TcpStream, TcpStream)) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let front = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (back, _) = listener.accept().unwrap();
+        front.set_nodelay(true).unwrap();
+        back.set_nodelay(true).unwrap();
+        (
+            (front.try_clone().unwrap(), front),
+            (back.try_clone().unwrap(), back),
+        )
+    }
+
+    #[test]
+    fn message_wire_roundtrip() {
+        let mut buf = [0; 32];
+        for message in [Message::Event(1.5, 42u32), Message::Null(f64::INFINITY)] {
+            let n = message.encode(&mut buf).unwrap();
+            assert_eq!(Message::decode(&buf[..n]).unwrap(), (message, n));
+        }
+        assert_eq!(
+            Message::<u32>::decode(&[2, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(WireError::InvalidValue)
+        );
+    }
+
+    #[test]
+    fn mpsc_partitions_match_single_process() {
+        // Simultaneous events across partitions (period == processing time) and events at t_stop
+        for (period, processing_time) in [(1.0, 2.5), (1.0, 1.0), (2.0, 0.5)] {
+            for t_stop in [20.0, 10.0, 5.0] {
+                let config = Config::new(0.0, t_stop, 1.0, None);
+                let (front_links, back_links) = mpsc_links();
+                let expected = single(period, processing_time, &config);
+                let got = partitioned(period, processing_time, &config, front_links, back_links);
+                assert_eq!(
+                    got, expected,
+                    "period {period}, processing time {processing_time}, t_stop {t_stop}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn remote_proxies_match_single_process() {
+        for (period, processing_time) in [(1.0, 2.5), (1.0, 1.0), (2.0, 0.5)] {
+            for t_stop in [20.0, 10.0, 5.0] {
+                let config = Config::new(0.0, t_stop, 1.0, None);
+                let expected = single(period, processing_time, &config);
+                let msg =
+                    format!("period {period}, processing time {processing_time}, t_stop {t_stop}");
+                assert_eq!(
+                    remote_back(period, processing_time, &config),
+                    expected,
+                    "remote back, {msg}"
+                );
+                assert_eq!(
+                    remote_front(period, processing_time, &config),
+                    expected,
+                    "remote front, {msg}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn remote_proxy_fails_when_link_is_gone() {
+        let (tx, rx) = mpsc::channel::<Message<JobsMux>>();
+        drop(tx);
+        let proxy = Remote::new().with_input(rx);
+        let mut sim = RemoteFront::build(proxy, back(1.0)).to_simulator();
+        let err = sim
+            .try_simulate_vt(&Config::new(0.0, 10.0, 1.0, None))
+            .unwrap_err();
+        assert_eq!(err.path(), &[PathSegment::Field("front")]);
+    }
+
+    #[test]
+    fn tcp_partitions_match_single_process() {
+        let config = Config::new(0.0, 20.0, 1.0, None);
+        let (front_links, back_links) = tcp_links();
+        let expected = single(1.0, 2.5, &config);
+        let got = partitioned(1.0, 2.5, &config, front_links, back_links);
+        assert_eq!(got, expected);
+    }
+
+    #[test]
+    fn lookahead_violation_is_detected() {
+        // The processor answers 0.5 after receiving a job, but the link promises 1.0
+        let config = Config::new(0.0, 20.0, 1.0, None);
+        let ((front_rx, front_tx), (back_rx, back_tx)) = mpsc_links();
+        let handle = thread::spawn(move || {
+            Partition::new(back(0.5).to_simulator())
+                .with_input(back_rx)
+                .with_output(back_tx, 1.0, |e| Some(e.clone()))
+                .simulate(&config)
+        });
+        let res = Partition::new(front(2.0, 10.0).to_simulator())
+            .with_input(front_rx)
+            .with_output(front_tx, 2.0, |e| Some(e.clone()))
+            .simulate(&config);
+        let err = res.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        // The front partition is gone, so the back partition fails too
+        assert!(handle.join().unwrap().is_err());
+    }
+}
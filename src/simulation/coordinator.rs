@@ -1,32 +1,309 @@
 use crate::{
     component::{
-        coupled::{ComponentsInput, ComponentsOutput, Coupled},
-        CoupledKind,
+        coupled::{Components, ComponentsInput, ComponentsOutput, Coupled},
+        Component, CoupledKind,
     },
     port::Bag,
-    simulation::{AbstractSimulator, Simulable},
+    simulation::{
+        error::{ErrorStrategy, ModelError},
+        reset::Reset,
+        AbstractSimulator, Simulable,
+    },
 };
 use core::ops::{Deref, DerefMut};
 
 /// Coordinator that encapsulates coupled-model simulation state.
 pub struct Coordinator<T: Coupled> {
     component: T,
-    components_input: ComponentsInput<T>,
-    components_output: ComponentsOutput<T>,
+    buffers: T::Buffers,
     t_next: f64,
 }
 
-impl<T: Coupled> Coordinator<T> {
+impl<T: Coupled> Coordinator<T>
+where
+    T::Buffers: Buffers<T>,
+{
     /// Creates a new coordinator for the given coupled model.
     #[inline(always)]
     pub fn new(component: T) -> Self {
         Self {
+            buffers: T::Buffers::build(component.get_components()),
             component,
-            components_input: ComponentsInput::<T>::build(),
-            components_output: ComponentsOutput::<T>::build(),
             t_next: f64::INFINITY,
         }
     }
+
+    /// Returns the coupled model of the coordinator, discarding its buffers.
+    #[inline]
+    pub fn into_component(self) -> T {
+        self.component
+    }
+}
+
+/// Buffers are not cloned, as they are empty between simulation steps.
+impl<T: Coupled + Clone> Clone for Coordinator<T>
+where
+    T::Buffers: Buffers<T>,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            component: self.component.clone(),
+            buffers: T::Buffers::build(self.component.get_components()),
+            t_next: self.t_next,
+        }
+    }
+}
+
+/// Storage of the input and output bags that a [`Coordinator`] keeps for the inner components
+/// of its coupled model between simulation steps.
+///
+/// `#[coupled]` models use [`OwnedBuffers`]. With `#[coupled(shared)]`, they use [`SharedBuffers`],
+/// and models with `#[shared]` components use [`MixedBuffers`].
+pub trait Buffers<T: Coupled>: Sized {
+    /// Creates the buffers for the given inner components.
+    fn build(components: &Components<T>) -> Self;
+
+    /// Removes all the events from the buffers.
+    fn clear(&mut self);
+
+    /// Executes the output function of the inner components.
+    fn lambda(&mut self, components: &mut Components<T>, output: &mut T::Output, t: f64);
+
+    /// Propagates the outputs of the inner components to the output of the coupled model.
+    fn eoc(&self, output: &mut T::Output);
+
+    /// Propagates the input of the coupled model and the outputs of the inner components
+    /// to the inputs of the inner components, and executes their transition functions.
+    fn delta(
+        &mut self,
+        components: &mut Components<T>,
+        input: &mut T::Input,
+        output: &mut T::Output,
+        t: f64,
+    ) -> f64;
+
+    /// Propagates the input of the coupled model to the inputs of the inner components,
+    /// and executes only their external transitions.
+    fn delta_ext(&mut self, components: &mut Components<T>, input: &mut T::Input, t: f64) -> f64;
+}
+
+/// Buffers with dedicated input and output bags for the inner components.
+/// Events are propagated through the couplings of the coupled model.
+pub struct OwnedBuffers<I, O> {
+    input: I,
+    output: O,
+}
+
+impl<T: Coupled> Buffers<T> for OwnedBuffers<ComponentsInput<T>, ComponentsOutput<T>> {
+    #[inline(always)]
+    fn build(components: &Components<T>) -> Self {
+        Self {
+            input: components.build_input(),
+            output: components.build_output(),
+        }
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        self.input.clear();
+        self.output.clear();
+    }
+
+    #[inline(always)]
+    fn lambda(&mut self, components: &mut Components<T>, _output: &mut T::Output, t: f64) {
+        components.lambda(&mut self.output, t);
+    }
+
+    #[inline(always)]
+    fn eoc(&self, output: &mut T::Output) {
+        T::eoc(&self.output, output);
+    }
+
+    #[inline(always)]
+    fn delta(
+        &mut self,
+        components: &mut Components<T>,
+        input: &mut T::Input,
+        _output: &mut T::Output,
+        t: f64,
+    ) -> f64 {
+        T::eic(input, &mut self.input);
+        T::ic(&self.output, &mut self.input);
+        components.delta(&mut self.input, &mut self.output, t)
+    }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, components: &mut Components<T>, input: &mut T::Input, t: f64) -> f64 {
+        T::eic(input, &mut self.input);
+        components.delta_ext(&mut self.input, t)
+    }
+}
+
+/// Buffers for coupled models whose only component has the same input and output as the model.
+/// There are no bags: the component reads the input of the coupled model in place,
+/// and writes directly into its output. Thus, `#[coupled(shared)]` implements [`Coupled`] without
+/// couplings, and models cannot define their own:
+///
+/// ```compile_fail
+/// #[xdevs::coupled(shared)]
+/// struct Swap {
+///     inner: Inner,
+/// }
+///
+/// impl xdevs::Component for Swap {
+///     type Kind = xdevs::CoupledKind;
+///     type Input = xdevs::Port<usize, 1>;
+///     type Output = xdevs::Port<usize, 1>;
+/// }
+///
+/// impl xdevs::Coupled for Swap {
+///     fn eoc(from: &xdevs::ComponentsOutput<Self>, to: &mut Self::Output) {
+///         to.add_values(from.get_values()).unwrap();
+///     }
+/// }
+/// # struct Inner;
+/// # impl xdevs::Component for Inner {
+/// #     type Kind = xdevs::AtomicKind;
+/// #     type Input = xdevs::Port<usize, 1>;
+/// #     type Output = xdevs::Port<usize, 1>;
+/// # }
+/// # impl xdevs::Atomic for Inner {
+/// #     fn delta_int(&mut self) {}
+/// #     fn delta_ext(&mut self, _elapsed: f64, _input: &Self::Input) {}
+/// #     fn lambda(&self, _output: &mut Self::Output) {}
+/// #     fn ta(&self) -> f64 {
+/// #         f64::INFINITY
+/// #     }
+/// # }
+/// ```
+///
+/// Coupled models with several components (e.g., HI and HO DEVStone levels) can mark the
+/// components that only forward the input or output of the model with `#[shared]`
+/// (see [`MixedBuffers`]).
+pub struct SharedBuffers;
+
+impl<T> Buffers<T> for SharedBuffers
+where
+    T: Coupled,
+    T::Components: Component<Input = T::Input, Output = T::Output>,
+{
+    #[inline(always)]
+    fn build(_components: &Components<T>) -> Self {
+        Self
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {}
+
+    #[inline(always)]
+    fn lambda(&mut self, components: &mut Components<T>, output: &mut T::Output, t: f64) {
+        components.lambda(output, t);
+    }
+
+    #[inline(always)]
+    fn eoc(&self, _output: &mut T::Output) {}
+
+    #[inline(always)]
+    fn delta(
+        &mut self,
+        components: &mut Components<T>,
+        input: &mut T::Input,
+        output: &mut T::Output,
+        t: f64,
+    ) -> f64 {
+        components.delta(input, output, t)
+    }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, components: &mut Components<T>, input: &mut T::Input, t: f64) -> f64 {
+        components.delta_ext(input, t)
+    }
+}
+
+/// Inner components of a coupled model in which some components use the bags of the model.
+///
+/// Components marked with `#[shared(input)]` read the input `I` of the coupled model in place,
+/// and components marked with `#[shared(output)]` write directly into its output `O`
+/// (`#[shared]` marks both). The input and output bags of the inner components
+/// (i.e., [`ComponentsInput`] and [`ComponentsOutput`]) only have room for the other components.
+pub trait SharedComponents<I, O>: AbstractSimulator {
+    /// Executes the output function of the inner components.
+    fn lambda_shared(&mut self, output: &mut Self::Output, shared_output: &mut O, t: f64);
+
+    /// Executes the transition functions of the inner components.
+    fn delta_shared(
+        &mut self,
+        input: &mut Self::Input,
+        output: &mut Self::Output,
+        shared_input: &mut I,
+        shared_output: &mut O,
+        t: f64,
+    ) -> f64;
+
+    /// Executes only the external transitions of the inner components.
+    fn delta_ext_shared(&mut self, input: &mut Self::Input, shared_input: &mut I, t: f64) -> f64;
+}
+
+/// Buffers for coupled models in which some components use the bags of the model
+/// (see [`SharedComponents`]). There are dedicated bags only for the other components.
+///
+/// The couplings of the model only deal with the other components: shared components are fed by
+/// the whole input of the model, and their whole output is the output of the model.
+/// Thus, components can only share the input if they do not receive events from other
+/// components, and the output if it is not coupled to other components.
+pub struct MixedBuffers<I, O> {
+    input: I,
+    output: O,
+}
+
+impl<T: Coupled> Buffers<T> for MixedBuffers<ComponentsInput<T>, ComponentsOutput<T>>
+where
+    T::Components: SharedComponents<T::Input, T::Output>,
+{
+    #[inline(always)]
+    fn build(components: &Components<T>) -> Self {
+        Self {
+            input: components.build_input(),
+            output: components.build_output(),
+        }
+    }
+
+    #[inline(always)]
+    fn clear(&mut self) {
+        self.input.clear();
+        self.output.clear();
+    }
+
+    #[inline(always)]
+    fn lambda(&mut self, components: &mut Components<T>, output: &mut T::Output, t: f64) {
+        components.lambda_shared(&mut self.output, output, t);
+    }
+
+    #[inline(always)]
+    fn eoc(&self, output: &mut T::Output) {
+        T::eoc(&self.output, output);
+    }
+
+    #[inline(always)]
+    fn delta(
+        &mut self,
+        components: &mut Components<T>,
+        input: &mut T::Input,
+        output: &mut T::Output,
+        t: f64,
+    ) -> f64 {
+        // The shared input may be cleared by its component, so it is propagated first
+        T::eic(input, &mut self.input);
+        T::ic(&self.output, &mut self.input);
+        components.delta_shared(&mut self.input, &mut self.output, input, output, t)
+    }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, components: &mut Components<T>, input: &mut T::Input, t: f64) -> f64 {
+        T::eic(input, &mut self.input);
+        components.delta_ext_shared(&mut self.input, input, t)
+    }
 }
 
 impl<T: Coupled> Deref for Coordinator<T> {
@@ -46,7 +323,10 @@ impl<T: Coupled> DerefMut for Coordinator<T> {
 }
 
 // Coupled models can be simulated using a `Coupled` struct
-impl<T: Coupled> Simulable<CoupledKind> for T {
+impl<T: Coupled> Simulable<CoupledKind> for T
+where
+    T::Buffers: Buffers<T>,
+{
     type Simulator = Coordinator<T>;
 
     fn to_simulator(self) -> Self::Simulator {
@@ -54,7 +334,23 @@ impl<T: Coupled> Simulable<CoupledKind> for T {
     }
 }
 
-unsafe impl<T: Coupled> AbstractSimulator for Coordinator<T> {
+impl<T: Coupled> Reset for Coordinator<T>
+where
+    T::Components: Reset,
+    T::Buffers: Buffers<T>,
+{
+    #[inline]
+    fn reset(&mut self) {
+        self.component.get_components_mut().reset();
+        self.buffers.clear();
+        self.t_next = f64::INFINITY;
+    }
+}
+
+unsafe impl<T: Coupled> AbstractSimulator for Coordinator<T>
+where
+    T::Buffers: Buffers<T>,
+{
     type Input = T::Input;
     type Output = T::Output;
 
@@ -73,10 +369,14 @@ unsafe impl<T: Coupled> AbstractSimulator for Coordinator<T> {
     #[inline(always)]
     fn lambda(&mut self, output: &mut Self::Output, t: f64) {
         if t >= self.t_next {
-            self.component
-                .get_components_mut()
-                .lambda(&mut self.components_output, t);
-            T::eoc(&self.components_output, output);
+            let components = self.component.get_components_mut();
+            self.buffers.lambda(components, output, t);
+            if T::ERROR_STRATEGY == ErrorStrategy::Forward {
+                while let Some(error) = components.take_error() {
+                    T::forward_error(&error, output);
+                }
+            }
+            self.buffers.eoc(output);
         }
     }
 
@@ -87,13 +387,12 @@ unsafe impl<T: Coupled> AbstractSimulator for Coordinator<T> {
             return t_next;
         }
 
-        T::eic(input, &mut self.components_input);
-        T::ic(&self.components_output, &mut self.components_input);
-        let t_next = self.component.get_components_mut().delta(
-            &mut self.components_input,
-            &mut self.components_output,
-            t,
-        );
+        let components = self.component.get_components_mut();
+        let mut t_next = self.buffers.delta(components, input, output, t);
+        // Pending errors are forwarded in an additional step at the same time
+        if T::ERROR_STRATEGY == ErrorStrategy::Forward && components.has_error() {
+            t_next = t;
+        }
 
         self.t_next = t_next;
 
@@ -102,16 +401,363 @@ unsafe impl<T: Coupled> AbstractSimulator for Coordinator<T> {
 
         t_next
     }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        if input.is_empty() {
+            return self.t_next;
+        }
+
+        let components = self.component.get_components_mut();
+        let mut t_next = self.buffers.delta_ext(components, input, t);
+        if T::ERROR_STRATEGY == ErrorStrategy::Forward && components.has_error() {
+            t_next = t;
+        }
+
+        self.t_next = t_next;
+
+        input.clear();
+
+        t_next
+    }
+
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        self.t_next
+    }
+
+    #[inline(always)]
+    fn has_error(&self) -> bool {
+        T::ERROR_STRATEGY == ErrorStrategy::Abort && self.component.get_components().has_error()
+    }
+
+    #[inline(always)]
+    fn take_error(&mut self) -> Option<ModelError> {
+        match T::ERROR_STRATEGY {
+            ErrorStrategy::Abort => self.component.get_components_mut().take_error(),
+            _ => None,
+        }
+    }
+
+    #[inline(always)]
+    fn take_isolated_error(&mut self) -> Option<ModelError> {
+        let components = self.component.get_components_mut();
+        match T::ERROR_STRATEGY {
+            ErrorStrategy::Isolate => components.take_error(),
+            _ => None,
+        }
+        .or_else(|| components.take_isolated_error())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        component::coupled::PartialCoupled,
+        component::coupled::{ComponentsInput, ComponentsOutput, PartialCoupled},
         port::Port,
-        simulation::test_utils::{TestAtomic, TestCoupled},
+        simulation::{
+            error::PathSegment,
+            test_utils::{TestAtomic, TestCoupled, TestTryAtomic},
+            Config,
+        },
+        Atomic, AtomicKind, Component, CoupledKind,
     };
+    use core::{
+        mem::size_of,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static SEQ: AtomicUsize = AtomicUsize::new(0);
+
+    /// Atomic model that records the (global) order of its internal transitions.
+    struct Ordered {
+        sigma: f64,
+        seq: usize,
+    }
+
+    impl Ordered {
+        fn new(sigma: f64) -> Self {
+            Self { sigma, seq: 0 }
+        }
+    }
+
+    impl Component for Ordered {
+        type Kind = AtomicKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Atomic for Ordered {
+        fn delta_int(&mut self) {
+            self.seq = SEQ.fetch_add(1, Ordering::Relaxed);
+            self.sigma = f64::INFINITY;
+        }
+        fn delta_ext(&mut self, _elapsed: f64, _input: &Self::Input) {}
+        fn lambda(&self, _output: &mut Self::Output) {}
+        fn ta(&self) -> f64 {
+            self.sigma
+        }
+    }
+
+    #[crate::coupled]
+    struct Prioritized {
+        low: Ordered,
+        #[priority = 2]
+        high: Ordered,
+        #[priority = -1]
+        lowest: Ordered,
+        #[priority = 2]
+        high_tie: Ordered,
+    }
+
+    impl Component for Prioritized {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for Prioritized {}
+
+    #[crate::coupled(classic)]
+    struct Classic {
+        a0: TestAtomic,
+        #[priority = 1]
+        a1: TestAtomic,
+    }
+
+    impl Component for Classic {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = Port<usize, 2>;
+    }
+
+    impl Coupled for Classic {
+        fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
+            let _ = from.a0.couple(to);
+            let _ = from.a1.couple(to);
+        }
+    }
+
+    #[crate::coupled(classic)]
+    struct ClassicRelay {
+        #[priority = 1]
+        sender: TestAtomic,
+        receiver: TestAtomic,
+    }
+
+    impl Component for ClassicRelay {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = Port<usize, 1>;
+    }
+
+    impl Coupled for ClassicRelay {
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            let _ = from.sender.couple(&mut to.receiver);
+        }
+        fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
+            let _ = from.receiver.couple(to);
+        }
+    }
+
+    #[crate::coupled(classic)]
+    struct ClassicArray {
+        atomics: [TestAtomic; 2],
+        #[priority = -1]
+        last: TestAtomic,
+    }
+
+    impl Component for ClassicArray {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for ClassicArray {}
+
+    #[crate::coupled]
+    struct Faulty {
+        ok: TestAtomic,
+        faulty: TestTryAtomic,
+    }
+
+    impl Component for Faulty {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for Faulty {}
+
+    #[crate::coupled]
+    struct Isolating {
+        inner: Faulty,
+    }
+
+    impl Component for Isolating {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for Isolating {
+        const ERROR_STRATEGY: ErrorStrategy = ErrorStrategy::Isolate;
+    }
+
+    #[crate::coupled]
+    struct Forwarding {
+        inner: Faulty,
+    }
+
+    impl Component for Forwarding {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = Port<ModelError, 1>;
+    }
+
+    impl Coupled for Forwarding {
+        const ERROR_STRATEGY: ErrorStrategy = ErrorStrategy::Forward;
+
+        fn forward_error(error: &ModelError, to: &mut Self::Output) {
+            let _ = to.add_value(error.clone());
+        }
+    }
+
+    /// Coupled model that forwards its input and output to and from its only component.
+    #[crate::coupled]
+    struct Wrapper {
+        inner: TestCoupled,
+    }
+
+    impl Component for Wrapper {
+        type Kind = CoupledKind;
+        type Input = Port<usize, 1>;
+        type Output = Port<usize, 1>;
+    }
+
+    impl Coupled for Wrapper {
+        fn eic(from: &Self::Input, to: &mut ComponentsInput<Self>) {
+            let _ = from.couple(&mut to.inner);
+        }
+        fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
+            let _ = from.inner.couple(to);
+        }
+    }
+
+    /// Same as [`Wrapper`], but its component uses the bags of the wrapper.
+    #[crate::coupled(shared)]
+    struct SharedWrapper {
+        inner: TestCoupled,
+    }
+
+    impl Component for SharedWrapper {
+        type Kind = CoupledKind;
+        type Input = Port<usize, 1>;
+        type Output = Port<usize, 1>;
+    }
+
+    fn wrapped() -> TestCoupled {
+        TestCoupled::build(
+            TestAtomic::oneshot(f64::INFINITY),
+            TestAtomic::periodic(1.0, 1.5),
+        )
+    }
+
+    /// HI DEVStone-like level with a chain of atomic models, and an inner model that receives
+    /// the input of the level and sends the output of the level.
+    #[crate::coupled]
+    struct Level {
+        atomics: [TestAtomic; 2],
+        inner: TestCoupled,
+    }
+
+    impl Component for Level {
+        type Kind = CoupledKind;
+        type Input = Port<usize, 1>;
+        type Output = Port<usize, 1>;
+    }
+
+    impl Coupled for Level {
+        fn eic(from: &Self::Input, to: &mut ComponentsInput<Self>) {
+            for atomic in to.atomics.iter_mut() {
+                let _ = from.couple(atomic);
+            }
+            let _ = from.couple(&mut to.inner);
+        }
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            let _ = from.atomics[0].couple(&mut to.atomics[1]);
+        }
+        fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
+            let _ = from.inner.couple(to);
+        }
+    }
+
+    /// Same as [`Level`], but its inner model uses the bags of the level.
+    #[crate::coupled]
+    struct SharedLevel {
+        atomics: [TestAtomic; 2],
+        #[shared]
+        inner: TestCoupled,
+    }
+
+    impl Component for SharedLevel {
+        type Kind = CoupledKind;
+        type Input = Port<usize, 1>;
+        type Output = Port<usize, 1>;
+    }
+
+    impl Coupled for SharedLevel {
+        fn eic(from: &Self::Input, to: &mut ComponentsInput<Self>) {
+            for atomic in to.atomics.iter_mut() {
+                let _ = from.couple(atomic);
+            }
+        }
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            let _ = from.atomics[0].couple(&mut to.atomics[1]);
+        }
+    }
+
+    fn atomics() -> [TestAtomic; 2] {
+        [
+            TestAtomic::periodic(0.5, 2.0),
+            TestAtomic::oneshot(f64::INFINITY),
+        ]
+    }
+
+    /// Injects events at times 2.5 and 4, and records the output events until time 6.
+    fn trace<S>(sim: &mut S) -> heapless::Vec<(f64, usize), 16>
+    where
+        S: AbstractSimulator<Input = Port<usize, 1>, Output = Port<usize, 1>>,
+    {
+        let mut trace = heapless::Vec::new();
+        let (mut input, mut output) = (Port::new(), Port::new());
+        let mut injections = [2.5, 4.0].into_iter().peekable();
+        sim.start(0.0);
+        loop {
+            let t_injection = injections.peek().copied().unwrap_or(f64::INFINITY);
+            let t = f64::min(sim.t_next(), t_injection);
+            if t > 6.0 {
+                return trace;
+            }
+            if t == t_injection {
+                input.add_value(7).unwrap();
+                injections.next();
+            }
+            sim.lambda(&mut output, t);
+            for &value in output.get_values() {
+                trace.push((t, value)).unwrap();
+            }
+            sim.delta(&mut input, &mut output, t);
+        }
+    }
+
+    fn faulty(fail_at: usize) -> Faulty {
+        Faulty::build(
+            TestAtomic::periodic(1.0, 1.0),
+            TestTryAtomic::new(1.0, fail_at),
+        )
+    }
 
     #[test]
     fn start_delegates() {
@@ -208,4 +854,264 @@ mod tests {
         let comps = <TestCoupled as PartialCoupled>::get_components(&coord);
         assert_eq!(comps.a1.ext_calls, 1, "ic routes a0's output to a1's input");
     }
+
+    #[test]
+    fn delta_priority_order() {
+        let model = Prioritized::build(
+            Ordered::new(1.0),
+            Ordered::new(1.0),
+            Ordered::new(1.0),
+            Ordered::new(1.0),
+        );
+        let mut coord = Coordinator::new(model);
+        assert_eq!(coord.start(0.0), 1.0);
+        assert_eq!(coord.delta(&mut (), &mut (), 1.0), f64::INFINITY);
+
+        let comps = <Prioritized as PartialCoupled>::get_components(&coord);
+        assert!(comps.high.seq < comps.high_tie.seq, "ties keep field order");
+        assert!(comps.high_tie.seq < comps.low.seq, "higher priority first");
+        assert!(comps.low.seq < comps.lowest.seq, "negative priority last");
+    }
+
+    #[test]
+    fn classic_selects_one_imminent() {
+        let mut a0 = TestAtomic::periodic(1.0, 1.0);
+        a0.out_val = 0;
+        let mut a1 = TestAtomic::periodic(1.0, 1.0);
+        a1.out_val = 1;
+        let mut coord = Coordinator::new(Classic::build(a0, a1));
+        assert_eq!(coord.start(0.0), 1.0);
+        assert_eq!(coord.t_next(), 1.0);
+
+        // First step: only a1 (higher priority) is executed
+        let mut output = Port::<usize, 2>::new();
+        coord.lambda(&mut output, 1.0);
+        assert_eq!(output.get_values(), &[1]);
+        assert_eq!(
+            coord.delta(&mut (), &mut output, 1.0),
+            1.0,
+            "a0 still imminent"
+        );
+        let comps = <Classic as PartialCoupled>::get_components(&coord);
+        assert_eq!((comps.a0.int_calls, comps.a1.int_calls), (0, 1));
+
+        // Second step at the same time: a0 is executed
+        coord.lambda(&mut output, 1.0);
+        assert_eq!(output.get_values(), &[0]);
+        assert_eq!(coord.delta(&mut (), &mut output, 1.0), 2.0);
+        let comps = <Classic as PartialCoupled>::get_components(&coord);
+        assert_eq!((comps.a0.int_calls, comps.a1.int_calls), (1, 1));
+    }
+
+    #[test]
+    fn classic_selects_whole_fields() {
+        let atomics = [
+            TestAtomic::periodic(1.0, 1.0),
+            TestAtomic::periodic(1.0, 1.0),
+        ];
+        let last = TestAtomic::periodic(1.0, 1.0);
+        let mut coord = Coordinator::new(ClassicArray::build(atomics, last));
+        assert_eq!(coord.start(0.0), 1.0);
+
+        // The array field is selected: both its imminent atomics are executed in one step
+        coord.lambda(&mut (), 1.0);
+        assert_eq!(
+            coord.delta(&mut (), &mut (), 1.0),
+            1.0,
+            "last still imminent"
+        );
+        let comps = <ClassicArray as PartialCoupled>::get_components(&coord);
+        let int_calls = comps.atomics.each_ref().map(|a| a.int_calls);
+        assert_eq!((int_calls, comps.last.int_calls), ([1, 1], 0));
+
+        coord.lambda(&mut (), 1.0);
+        assert_eq!(coord.delta(&mut (), &mut (), 1.0), 2.0);
+        let comps = <ClassicArray as PartialCoupled>::get_components(&coord);
+        assert_eq!(comps.last.int_calls, 1);
+    }
+
+    #[test]
+    fn classic_influencees_reschedule_internal_transition() {
+        let mut sender = TestAtomic::periodic(1.0, f64::INFINITY);
+        sender.out_val = 1;
+        let mut receiver = TestAtomic::periodic(1.0, f64::INFINITY);
+        receiver.out_val = 2;
+        receiver.ext_sigma = 0.5;
+        let mut coord = Coordinator::new(ClassicRelay::build(sender, receiver));
+        assert_eq!(coord.start(0.0), 1.0);
+
+        // The sender is selected, and the imminent receiver only executes its external transition
+        let mut output = Port::<usize, 1>::new();
+        coord.lambda(&mut output, 1.0);
+        assert!(output.is_empty());
+        assert_eq!(
+            coord.delta(&mut (), &mut output, 1.0),
+            1.5,
+            "receiver scheduled from the event"
+        );
+        let comps = <ClassicRelay as PartialCoupled>::get_components(&coord);
+        assert_eq!((comps.sender.int_calls, comps.receiver.int_calls), (1, 0));
+        assert_eq!(comps.receiver.ext_calls, 1);
+        assert_eq!(comps.receiver.last_elapsed, 1.0);
+
+        // The receiver emits its output after the time advance of its new state
+        coord.lambda(&mut output, 1.5);
+        assert_eq!(output.get_values(), &[2]);
+        assert_eq!(coord.delta(&mut (), &mut output, 1.5), f64::INFINITY);
+        let comps = <ClassicRelay as PartialCoupled>::get_components(&coord);
+        assert_eq!((comps.sender.int_calls, comps.receiver.int_calls), (1, 1));
+    }
+
+    #[test]
+    fn shared_buffers_match_owned() {
+        let expected = trace(&mut Coordinator::new(wrapped()));
+        assert!(expected.len() > 4);
+        assert_eq!(
+            trace(&mut Coordinator::new(Wrapper::build(wrapped()))),
+            expected
+        );
+        assert_eq!(
+            trace(&mut Coordinator::new(SharedWrapper::build(wrapped()))),
+            expected
+        );
+    }
+
+    #[test]
+    fn shared_fields_match_owned() {
+        let mut owned = Coordinator::new(Level::build(atomics(), wrapped()));
+        let mut shared = Coordinator::new(SharedLevel::build(atomics(), wrapped()));
+        let expected = trace(&mut owned);
+        assert!(expected.len() > 4);
+        assert_eq!(trace(&mut shared), expected);
+
+        let owned = <Level as PartialCoupled>::get_components(&owned);
+        let shared = <SharedLevel as PartialCoupled>::get_components(&shared);
+        for (owned, shared) in owned.atomics.iter().zip(&shared.atomics) {
+            assert_eq!(
+                (owned.int_calls, owned.ext_calls),
+                (shared.int_calls, shared.ext_calls)
+            );
+        }
+        assert!(
+            shared.atomics[1].ext_calls > 2,
+            "injections and chained events"
+        );
+    }
+
+    #[test]
+    fn shared_fields_have_no_bags() {
+        // Only the atomic models have bags
+        assert_eq!(
+            size_of::<<SharedLevel as PartialCoupled>::Buffers>(),
+            2 * size_of::<[Port<usize, 1>; 2]>()
+        );
+        assert_eq!(
+            size_of::<Coordinator<SharedLevel>>(),
+            size_of::<Coordinator<Level>>() - 2 * size_of::<Port<usize, 1>>()
+        );
+    }
+
+    #[test]
+    fn shared_buffers_have_no_bags() {
+        assert_eq!(size_of::<<SharedWrapper as PartialCoupled>::Buffers>(), 0);
+        assert_eq!(
+            size_of::<Coordinator<SharedWrapper>>(),
+            size_of::<Coordinator<Wrapper>>() - size_of::<<Wrapper as PartialCoupled>::Buffers>()
+        );
+    }
+
+    #[test]
+    fn error_abort() {
+        let mut coord = Coordinator::new(faulty(2));
+        let error = coord
+            .try_simulate_vt(&Config::new(0.0, 10.0, 1.0, None))
+            .unwrap_err();
+        assert_eq!(error.path(), &[PathSegment::Field("faulty")]);
+        assert_eq!(error.message(), "Overflow(2)");
+
+        let comps = <Faulty as PartialCoupled>::get_components(&coord);
+        assert_eq!(comps.ok.int_calls, 2, "simulation aborted at t = 2");
+        assert!(comps.faulty.is_failed());
+    }
+
+    #[test]
+    #[should_panic(expected = "simulation aborted: faulty: Overflow(0)")]
+    fn error_abort_panics() {
+        Coordinator::new(faulty(0)).simulate_vt(&Config::new(0.0, 10.0, 1.0, None));
+    }
+
+    #[test]
+    fn error_isolate() {
+        let mut coord = Coordinator::new(Isolating::build(faulty(2)));
+        assert!(coord
+            .try_simulate_vt(&Config::new(0.0, 10.0, 1.0, None))
+            .is_ok());
+
+        let inner = &<Isolating as PartialCoupled>::get_components(&coord).inner;
+        let comps = <Faulty as PartialCoupled>::get_components(inner);
+        assert_eq!(comps.ok.int_calls, 10, "other components keep running");
+        assert!(comps.faulty.is_failed());
+        assert_eq!(comps.faulty.int_calls, 2, "failed component is passive");
+
+        assert!(!coord.has_error(), "isolated errors do not abort");
+        let error = coord.take_isolated_error().unwrap();
+        assert_eq!(
+            error.path(),
+            &[PathSegment::Field("inner"), PathSegment::Field("faulty")]
+        );
+        assert_eq!(error.message(), "Overflow(2)");
+        assert!(
+            coord.take_isolated_error().is_none(),
+            "error taken only once"
+        );
+    }
+
+    #[test]
+    fn error_isolate_reaches_parents() {
+        let mut sims = (
+            TestAtomic::periodic(1.0, 1.0).to_simulator(),
+            Coordinator::new(Isolating::build(faulty(2))),
+        );
+        let config = Config::new(0.0, 10.0, 1.0, None);
+        assert!(sims.try_simulate_vt(&config).is_ok());
+
+        let error = sims.take_isolated_error().unwrap();
+        assert_eq!(
+            error.path(),
+            &[
+                PathSegment::Index(1),
+                PathSegment::Field("inner"),
+                PathSegment::Field("faulty")
+            ]
+        );
+        assert!(sims.take_isolated_error().is_none());
+    }
+
+    #[test]
+    fn error_forward() {
+        for fail_at in [0, 2] {
+            let mut coord = Coordinator::new(Forwarding::build(faulty(fail_at)));
+            let t = core::cell::Cell::new(0.0);
+            let mut forwarded = heapless::Vec::<(f64, ModelError), 2>::new();
+            coord.simulate_rt(
+                &Config::new(0.0, 10.0, 1.0, None),
+                |_, t_until, _| {
+                    t.set(t_until);
+                    t_until
+                },
+                |output| {
+                    for error in output.get_values() {
+                        forwarded.push((t.get(), error.clone())).unwrap();
+                    }
+                },
+            );
+            assert_eq!(forwarded.len(), 1);
+            let (t, error) = &forwarded[0];
+            assert_eq!(*t, f64::max(fail_at as f64, 1.0));
+            assert_eq!(
+                error.path(),
+                &[PathSegment::Field("inner"), PathSegment::Field("faulty")]
+            );
+        }
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{port::Bag, simulation::AsyncInput, Config};
+use crate::{
+    port::Bag,
+    simulation::{AsyncInput, Pacer},
+    Config,
+};
 use core::time::Duration;
 use embassy_time::{Instant, Timer};
 
@@ -7,6 +11,8 @@ use embassy_time::{Instant, Timer};
 pub struct SleepAsync<T: Bag> {
     /// The last recorded real time instant.
     last_rt: Option<Instant>,
+    /// The pacing state, created on the first call.
+    pacer: Option<Pacer>,
     /// Phantom data to associate with the input bag type.
     input: core::marker::PhantomData<T>,
 }
@@ -16,6 +22,7 @@ impl<T: Bag> SleepAsync<T> {
     pub fn new() -> Self {
         Self {
             last_rt: None,
+            pacer: None,
             input: core::marker::PhantomData,
         }
     }
@@ -31,7 +38,11 @@ impl<T: Bag> AsyncInput for SleepAsync<T> {
         t_until: f64,
         _input: &mut Self::Input,
     ) -> f64 {
+        let pacer = self.pacer.get_or_insert_with(|| Pacer::new(config));
         let last_rt = self.last_rt.unwrap_or_else(Instant::now);
+        let Some((t_from, last_rt)) = pacer.schedule(t_from, t_until, last_rt, Instant::now) else {
+            return t_until;
+        };
         let duration = Duration::from_secs_f64((t_until - t_from) * config.time_scale);
         let next_rt = last_rt + duration.try_into().unwrap();
         Timer::at(next_rt).await;
@@ -0,0 +1,159 @@
+use core::fmt::{self, Debug, Display, Write};
+
+/// Maximum number of segments stored in the path of a [`ModelError`].
+pub const MAX_PATH_DEPTH: usize = 8;
+
+/// Maximum length (in bytes) of the message stored in a [`ModelError`].
+pub const MAX_MESSAGE_LEN: usize = 64;
+
+/// Strategy followed by a coupled model when one of its inner components fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorStrategy {
+    /// The error is propagated to the parent model.
+    /// At the top level, it aborts the simulation (see [`AbstractSimulator::try_simulate_rt`]).
+    ///
+    /// [`AbstractSimulator::try_simulate_rt`]: crate::simulation::AbstractSimulator::try_simulate_rt
+    #[default]
+    Abort,
+    /// The failing component is isolated (i.e., it becomes passive) and the simulation goes on.
+    /// The error is kept by the component until it is taken with
+    /// [`AbstractSimulator::take_isolated_error`](crate::simulation::AbstractSimulator::take_isolated_error)
+    /// (e.g., after the simulation) from this model or any of its parents.
+    Isolate,
+    /// The failing component is isolated and the error is forwarded to the output of the coupled
+    /// model via [`Coupled::forward_error`](crate::Coupled::forward_error).
+    Forward,
+}
+
+/// Segment of the path of a model within the model hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSegment {
+    /// Named field of a coupled model.
+    Field(&'static str),
+    /// Position in an array or tuple of components.
+    Index(usize),
+}
+
+/// Error of a failed component, with the path of the component attached.
+///
+/// The original error is stored as its [`Debug`] representation, truncated to [`MAX_MESSAGE_LEN`] bytes,
+/// so its type is lost. Components that need the typed error (e.g., to recover from it) must handle it
+/// themselves, or send it through their output ports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelError {
+    path: heapless::Vec<PathSegment, MAX_PATH_DEPTH>,
+    truncated: bool,
+    message: heapless::String<MAX_MESSAGE_LEN>,
+}
+
+impl ModelError {
+    /// Creates a new model error from the error returned by a component.
+    pub fn new(error: &impl Debug) -> Self {
+        // Write as many characters as possible, silently truncating the message
+        struct Truncate<'a>(&'a mut heapless::String<MAX_MESSAGE_LEN>);
+
+        impl Write for Truncate<'_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                for c in s.chars() {
+                    self.0.push(c).map_err(|_| fmt::Error)?;
+                }
+                Ok(())
+            }
+        }
+
+        let mut message = heapless::String::new();
+        let _ = write!(Truncate(&mut message), "{error:?}");
+        Self {
+            path: heapless::Vec::new(),
+            truncated: false,
+            message,
+        }
+    }
+
+    /// Returns the path of the failed component, from the outermost to the innermost model.
+    ///
+    /// If the path is deeper than [`MAX_PATH_DEPTH`], only the innermost segments are kept.
+    #[inline]
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// Returns the [`Debug`] representation of the original error.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Prepends a segment to the path. Used when propagating the error to the parent model.
+    #[doc(hidden)]
+    #[inline]
+    pub fn push_parent(&mut self, segment: PathSegment) {
+        if self.path.insert(0, segment).is_err() {
+            self.truncated = true;
+        }
+    }
+}
+
+impl Display for ModelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        for (i, segment) in self.path.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) if i == 0 && !self.truncated => f.write_str(name)?,
+                PathSegment::Field(name) => write!(f, ".{name}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_string(error: &ModelError) -> heapless::String<128> {
+        let mut s = heapless::String::new();
+        write!(s, "{error}").unwrap();
+        s
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    enum SensorError {
+        Timeout(u8),
+    }
+
+    #[test]
+    fn display_path() {
+        let mut error = ModelError::new(&SensorError::Timeout(3));
+        error.push_parent(PathSegment::Field("sensor"));
+        error.push_parent(PathSegment::Index(2));
+        error.push_parent(PathSegment::Field("boards"));
+        assert_eq!(error.message(), "Timeout(3)");
+        assert_eq!(
+            error.path(),
+            &[
+                PathSegment::Field("boards"),
+                PathSegment::Index(2),
+                PathSegment::Field("sensor")
+            ]
+        );
+        assert_eq!(to_string(&error), "boards[2].sensor: Timeout(3)");
+    }
+
+    #[test]
+    fn truncation() {
+        let long = [0u8; 64];
+        let mut error = ModelError::new(&long);
+        assert_eq!(error.message().len(), MAX_MESSAGE_LEN);
+        for i in 0..=MAX_PATH_DEPTH {
+            error.push_parent(PathSegment::Index(i));
+        }
+        assert_eq!(error.path().len(), MAX_PATH_DEPTH);
+        assert_eq!(error.path()[0], PathSegment::Index(MAX_PATH_DEPTH - 1));
+        assert!(to_string(&error).starts_with("...[7]"));
+    }
+}
@@ -0,0 +1,481 @@
+//! Flattening of coupled hierarchies whose couplings only forward events.
+//!
+//! Coordinators run the couplings of their coupled model at every level of a hierarchy, so events
+//! are copied through the bags of every level. [`Flat`] simulates all the atomic models of a
+//! hierarchy with a single coordinator instead, and events are routed directly from the output
+//! of an atomic model to the inputs of the atomic models that receive them.
+//!
+//! Couplings are plain Rust functions, so they cannot be composed automatically. Coupled models
+//! opt in by implementing [`Flatten`], which describes their couplings as forwarding links between
+//! the ports of their components. Chains of external input, internal and external output couplings
+//! across levels are then composed into direct routes between atomic models.
+//!
+//! Only couplings that forward all the events of a port to another port can be flattened. Atomic
+//! models have a single input port and a single output port (i.e., their bags are forwarded as a
+//! whole). As in the couplings of coupled models, events that do not fit in the bag they are sent
+//! to are dropped. There are no intermediate bags, but the external ports of a [`Flat`] model
+//! receive the events of all the atomic models coupled to them, so their bag type is independent
+//! of the bags of the atomic models (e.g., a [`DynPort`](crate::DynPort) for ports with a fan-in
+//! only known at runtime).
+//!
+//! # Example
+//!
+//! ```ignore
+//! impl Flatten<Simulator<AtomicModel>> for HIModel {
+//!     fn flatten(self, flat: &mut FlatBuilder<Simulator<AtomicModel>>) -> FlatPorts {
+//!         let atomics: Vec<_> = self.components.atomics.into_iter().map(|a| a.flatten(flat)).collect();
+//!         let inner = self.components.inner.flatten(flat);
+//!
+//!         let mut ports = FlatPorts::new(1, 1);
+//!         for atomic in atomics.iter().chain([&inner]) {
+//!             ports.eic(0, atomic, 0);
+//!         }
+//!         for (from, to) in atomics.iter().zip(atomics.iter().skip(1)) {
+//!             flat.ic(from, 0, to, 0);
+//!         }
+//!         ports.eoc(&inner, 0, 0);
+//!         ports
+//!     }
+//! }
+//!
+//! // The flat model replaces the HI model in the hierarchy
+//! let flat: Flat<Simulator<AtomicModel>> = Flat::new(hi_model);
+//! ```
+
+use crate::{
+    component::{atomic::Atomic, coupled::Coupled, Component},
+    port::{Bag, BagMux},
+    simulation::{
+        coordinator::{Buffers, Coordinator},
+        error::{ModelError, PathSegment},
+        reset::Reset,
+        simulator::Simulator,
+        AbstractSimulator,
+    },
+    ComponentsKind,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::marker::PhantomData;
+
+/// Ports of a flattened component. Every port is given by the atomic models behind it:
+/// the atomic models that receive the events of an input port,
+/// and the atomic models whose events are sent through an output port.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlatPorts {
+    inputs: Vec<Vec<usize>>,
+    outputs: Vec<Vec<usize>>,
+}
+
+impl FlatPorts {
+    /// Creates the ports of a component with the given number of input and output ports,
+    /// which are not coupled to any atomic model yet.
+    pub fn new(n_inputs: usize, n_outputs: usize) -> Self {
+        Self {
+            inputs: vec![Vec::new(); n_inputs],
+            outputs: vec![Vec::new(); n_outputs],
+        }
+    }
+
+    /// Ports of an atomic model, with the given index in the flat model.
+    fn atomic(index: usize) -> Self {
+        Self {
+            inputs: vec![vec![index]],
+            outputs: vec![vec![index]],
+        }
+    }
+
+    /// Returns the number of input ports.
+    pub fn n_inputs(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Returns the number of output ports.
+    pub fn n_outputs(&self) -> usize {
+        self.outputs.len()
+    }
+
+    /// External input coupling from the input port `port` to the input port `to_port` of `to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the ports does not exist.
+    pub fn eic(&mut self, port: usize, to: &FlatPorts, to_port: usize) {
+        self.inputs[port].extend_from_slice(&to.inputs[to_port]);
+    }
+
+    /// External output coupling from the output port `from_port` of `from` to the output port `port`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the ports does not exist.
+    pub fn eoc(&mut self, from: &FlatPorts, from_port: usize, port: usize) {
+        self.outputs[port].extend_from_slice(&from.outputs[from_port]);
+    }
+}
+
+/// Builder of [`Flat`] models. It keeps the atomic models of the hierarchy,
+/// and the routes between them composed so far.
+pub struct FlatBuilder<S> {
+    atomics: Vec<S>,
+    routes: Vec<Vec<usize>>,
+}
+
+impl<S: AbstractSimulator> FlatBuilder<S> {
+    /// Adds an atomic model to the flat model, and returns its ports.
+    pub fn atomic(&mut self, atomic: S) -> FlatPorts {
+        self.atomics.push(atomic);
+        self.routes.push(Vec::new());
+        FlatPorts::atomic(self.atomics.len() - 1)
+    }
+
+    /// Internal coupling from the output port `from_port` of `from` to the input port `to_port` of `to`.
+    /// Every atomic model behind the output port gets a route to every atomic model behind the input port.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the ports does not exist.
+    pub fn ic(&mut self, from: &FlatPorts, from_port: usize, to: &FlatPorts, to_port: usize) {
+        for &source in &from.outputs[from_port] {
+            self.routes[source].extend_from_slice(&to.inputs[to_port]);
+        }
+    }
+}
+
+/// Interface for components that can be flattened into a [`Flat`] model whose atomic models are of type `S`.
+pub trait Flatten<S> {
+    /// Moves the atomic models of the component into `flat`, adds the routes of its internal
+    /// couplings, and returns its ports.
+    fn flatten(self, flat: &mut FlatBuilder<S>) -> FlatPorts;
+}
+
+impl<T: Atomic> Flatten<Simulator<T>> for Simulator<T> {
+    #[inline]
+    fn flatten(self, flat: &mut FlatBuilder<Simulator<T>>) -> FlatPorts {
+        flat.atomic(self)
+    }
+}
+
+impl<S, T> Flatten<S> for Coordinator<T>
+where
+    T: Coupled + Flatten<S>,
+    T::Buffers: Buffers<T>,
+{
+    #[inline]
+    fn flatten(self, flat: &mut FlatBuilder<S>) -> FlatPorts {
+        self.into_component().flatten(flat)
+    }
+}
+
+impl<S, T: Flatten<S>> Flatten<S> for Box<T> {
+    #[inline]
+    fn flatten(self, flat: &mut FlatBuilder<S>) -> FlatPorts {
+        T::flatten(*self, flat)
+    }
+}
+
+/// Flat simulator of the atomic models of a hierarchy (see the [module documentation](self)).
+///
+/// It is a component with `I` input ports of type `In` and `O` output ports of type `Out`.
+/// By default, they have the same bags as the atomic models. Thus, it can replace the flattened
+/// component in the hierarchy. Failed atomic models always abort the simulation.
+pub struct Flat<
+    S: AbstractSimulator,
+    const I: usize = 1,
+    const O: usize = 1,
+    In = <S as AbstractSimulator>::Input,
+    Out = <S as AbstractSimulator>::Output,
+> {
+    atomics: Vec<S>,
+    inputs: Vec<S::Input>,
+    outputs: Vec<S::Output>,
+    /// Atomic models that receive the events of every atomic model.
+    routes: Vec<Vec<usize>>,
+    /// Atomic models that receive the events of every input port.
+    eic: [Vec<usize>; I],
+    /// Atomic models whose events are sent through every output port.
+    eoc: [Vec<usize>; O],
+    t_next: f64,
+    ports: PhantomData<([In; I], [Out; O])>,
+}
+
+impl<S, const I: usize, const O: usize, In, Out> Flat<S, I, O, In, Out>
+where
+    S: AbstractSimulator,
+    S::Output: BagMux,
+    S::Input: BagMux<Mux = <S::Output as BagMux>::Mux>,
+    In: BagMux<Mux = <S::Output as BagMux>::Mux>,
+    Out: BagMux<Mux = <S::Output as BagMux>::Mux>,
+{
+    /// Flattens a component into a flat simulator of its atomic models.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component does not have `I` input ports and `O` output ports.
+    pub fn new<T: Flatten<S>>(component: T) -> Self {
+        let mut flat = FlatBuilder {
+            atomics: Vec::new(),
+            routes: Vec::new(),
+        };
+        let ports = component.flatten(&mut flat);
+        assert_eq!(ports.n_inputs(), I, "unexpected number of input ports");
+        assert_eq!(ports.n_outputs(), O, "unexpected number of output ports");
+
+        let mut inputs = ports.inputs.into_iter();
+        let mut outputs = ports.outputs.into_iter();
+        Self {
+            inputs: flat.atomics.iter().map(S::build_input).collect(),
+            outputs: flat.atomics.iter().map(S::build_output).collect(),
+            atomics: flat.atomics,
+            routes: flat.routes,
+            eic: core::array::from_fn(|_| inputs.next().unwrap()),
+            eoc: core::array::from_fn(|_| outputs.next().unwrap()),
+            t_next: f64::INFINITY,
+            ports: PhantomData,
+        }
+    }
+
+    /// Forwards the events of the input ports to the atomic models that receive them.
+    fn route_input(&mut self, input: &[In; I]) {
+        for (port, destinations) in input.iter().zip(&self.eic) {
+            for &to in destinations {
+                port.eject_events(|event| {
+                    let _ = self.inputs[to].inject_event(event);
+                });
+            }
+        }
+    }
+}
+
+impl<S: AbstractSimulator, const I: usize, const O: usize, In, Out> Flat<S, I, O, In, Out> {
+    /// Returns the atomic models of the flat model.
+    pub fn atomics(&self) -> &[S] {
+        &self.atomics
+    }
+
+    /// Returns the number of routes between atomic models.
+    pub fn n_routes(&self) -> usize {
+        self.routes.iter().map(Vec::len).sum()
+    }
+}
+
+impl<S, const I: usize, const O: usize, In: Bag, Out: Bag> Component for Flat<S, I, O, In, Out>
+where
+    S: AbstractSimulator,
+{
+    type Kind = ComponentsKind;
+    type Input = [In; I];
+    type Output = [Out; O];
+}
+
+impl<S, const I: usize, const O: usize, In, Out> Reset for Flat<S, I, O, In, Out>
+where
+    S: AbstractSimulator + Reset,
+{
+    fn reset(&mut self) {
+        self.atomics.iter_mut().for_each(Reset::reset);
+        self.inputs.iter_mut().for_each(Bag::clear);
+        self.outputs.iter_mut().for_each(Bag::clear);
+        self.t_next = f64::INFINITY;
+    }
+}
+
+unsafe impl<S, const I: usize, const O: usize, In, Out> AbstractSimulator for Flat<S, I, O, In, Out>
+where
+    S: AbstractSimulator,
+    S::Output: BagMux,
+    S::Input: BagMux<Mux = <S::Output as BagMux>::Mux>,
+    In: BagMux<Mux = <S::Output as BagMux>::Mux>,
+    Out: BagMux<Mux = <S::Output as BagMux>::Mux>,
+{
+    type Input = [In; I];
+    type Output = [Out; O];
+
+    #[inline]
+    fn start(&mut self, t_start: f64) -> f64 {
+        self.t_next = (self.atomics.iter_mut())
+            .map(|atomic| atomic.start(t_start))
+            .fold(f64::INFINITY, f64::min);
+        self.t_next
+    }
+
+    #[inline]
+    fn stop(&mut self) {
+        self.atomics.iter_mut().for_each(S::stop);
+    }
+
+    #[inline]
+    fn lambda(&mut self, output: &mut Self::Output, t: f64) {
+        if t < self.t_next {
+            return;
+        }
+        for (atomic, atomic_output) in self.atomics.iter_mut().zip(&mut self.outputs) {
+            atomic.lambda(atomic_output, t);
+        }
+        for (port, sources) in output.iter_mut().zip(&self.eoc) {
+            for &from in sources {
+                self.outputs[from].eject_events(|event| {
+                    let _ = port.inject_event(event);
+                });
+            }
+        }
+    }
+
+    #[inline]
+    fn delta(&mut self, input: &mut Self::Input, output: &mut Self::Output, t: f64) -> f64 {
+        if t < self.t_next && input.is_empty() {
+            return self.t_next;
+        }
+
+        self.route_input(input);
+        for (from, destinations) in self.routes.iter().enumerate() {
+            if self.outputs[from].is_empty() {
+                continue;
+            }
+            for &to in destinations {
+                self.outputs[from].eject_events(|event| {
+                    let _ = self.inputs[to].inject_event(event);
+                });
+            }
+        }
+        self.t_next = (self.atomics.iter_mut())
+            .zip(self.inputs.iter_mut().zip(&mut self.outputs))
+            .map(|(atomic, (input, output))| atomic.delta(input, output, t))
+            .fold(f64::INFINITY, f64::min);
+
+        input.clear();
+        output.clear();
+
+        self.t_next
+    }
+
+    #[inline]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        if input.is_empty() {
+            return self.t_next;
+        }
+
+        self.route_input(input);
+        self.t_next = (self.atomics.iter_mut())
+            .zip(&mut self.inputs)
+            .map(|(atomic, input)| atomic.delta_ext(input, t))
+            .fold(f64::INFINITY, f64::min);
+
+        input.clear();
+
+        self.t_next
+    }
+
+    #[inline]
+    fn t_next(&self) -> f64 {
+        self.t_next
+    }
+
+    #[inline]
+    fn build_input(&self) -> Self::Input {
+        core::array::from_fn(|_| Bag::build())
+    }
+
+    #[inline]
+    fn build_output(&self) -> Self::Output {
+        core::array::from_fn(|_| Bag::build())
+    }
+
+    #[inline]
+    fn has_error(&self) -> bool {
+        self.atomics.iter().any(S::has_error)
+    }
+
+    #[inline]
+    fn take_error(&mut self) -> Option<ModelError> {
+        self.atomics.iter_mut().enumerate().find_map(|(i, atomic)| {
+            let mut error = atomic.take_error()?;
+            error.push_parent(PathSegment::Index(i));
+            Some(error)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{port::Port, simulation::test_utils::TestAtomic};
+
+    /// Coupled model that forwards its input to `first`, `first` to `second`, and `second` to its output.
+    struct Chain {
+        first: Simulator<TestAtomic>,
+        second: Box<Simulator<TestAtomic>>,
+    }
+
+    impl Chain {
+        fn new(first: TestAtomic, second: TestAtomic) -> Self {
+            Self {
+                first: Simulator::new(first),
+                second: Box::new(Simulator::new(second)),
+            }
+        }
+    }
+
+    impl Flatten<Simulator<TestAtomic>> for Chain {
+        fn flatten(self, flat: &mut FlatBuilder<Simulator<TestAtomic>>) -> FlatPorts {
+            let first = self.first.flatten(flat);
+            let second = self.second.flatten(flat);
+
+            let mut ports = FlatPorts::new(1, 1);
+            ports.eic(0, &first, 0);
+            flat.ic(&first, 0, &second, 0);
+            ports.eoc(&second, 0, 0);
+            ports
+        }
+    }
+
+    #[test]
+    fn routes_compose_across_levels() {
+        let mut flat = FlatBuilder {
+            atomics: Vec::new(),
+            routes: Vec::new(),
+        };
+        let inner =
+            Chain::new(TestAtomic::oneshot(1.0), TestAtomic::oneshot(1.0)).flatten(&mut flat);
+        let last = flat.atomic(Simulator::new(TestAtomic::oneshot(1.0)));
+        flat.ic(&inner, 0, &last, 0);
+
+        let mut ports = FlatPorts::new(1, 1);
+        ports.eic(0, &inner, 0);
+        ports.eoc(&last, 0, 0);
+
+        assert_eq!(flat.routes, vec![vec![1], vec![2], vec![]]);
+        assert_eq!(ports.inputs, vec![vec![0]]);
+        assert_eq!(ports.outputs, vec![vec![2]]);
+    }
+
+    #[test]
+    fn events_are_routed_between_atomics() {
+        let mut second = TestAtomic::oneshot(f64::INFINITY);
+        second.out_val = 7;
+        second.ext_sigma = 1.0;
+        let mut flat: Flat<Simulator<TestAtomic>> =
+            Flat::new(Chain::new(TestAtomic::oneshot(1.0), second));
+        assert_eq!(flat.n_routes(), 1);
+
+        let mut input = [Port::<usize, 1>::new()];
+        let mut output = [Port::<usize, 1>::new()];
+        assert_eq!(flat.start(0.0), 1.0);
+
+        flat.lambda(&mut output, 1.0);
+        assert!(
+            output[0].is_empty(),
+            "first atomic is not coupled to the output"
+        );
+        assert_eq!(flat.delta(&mut input, &mut output, 1.0), 2.0);
+        assert_eq!(
+            flat.atomics()[1].ext_calls,
+            1,
+            "second atomic received the event"
+        );
+
+        flat.lambda(&mut output, 2.0);
+        assert_eq!(output[0].get_values(), &[7]);
+        flat.delta(&mut input, &mut output, 2.0);
+        assert!(output[0].is_empty(), "output cleared after delta");
+    }
+}
@@ -0,0 +1,363 @@
+//! Idle strategies for waiting between events on bare-metal targets.
+//!
+//! The [`IdleWaiter`] computes the wall-clock deadline of the next state transition, and idles
+//! until then using an [`IdleStrategy`] (e.g., spinning, yielding, or putting the MCU to sleep).
+//! If the strategy reports that an external event woke it up, the input handler is called to
+//! inject the event into the input bag of the model.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let strategy = Sleep::new(|duration| {
+//!     rtc.set_alarm(duration);
+//!     cortex_m::asm::wfi();
+//!     BUTTON.is_pending()
+//! });
+//! let mut idle = IdleWaiter::new(&config, strategy);
+//! simulator.simulate_rt(&config, wait_until(&mut idle, |input| read_button(input)), |_| {});
+//! ```
+
+use crate::{
+    port::Bag,
+    simulation::{Config, Pacer},
+};
+use embassy_time::{Duration, Instant};
+
+/// Interface for idling the simulator until the next event.
+pub trait IdleStrategy {
+    /// Idles for (at most) `duration`.
+    /// It may return earlier, for example, if an interrupt wakes up the MCU.
+    fn sleep_for(&mut self, duration: Duration);
+
+    /// Returns `true` if the last wake-up was caused by an external event.
+    /// By default, it returns `false`.
+    #[inline]
+    fn wake_on_event(&mut self) -> bool {
+        false
+    }
+}
+
+/// Busy-waiting strategy. It is the most accurate, but also the most power-hungry.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Spin;
+
+impl IdleStrategy for Spin {
+    #[inline]
+    fn sleep_for(&mut self, duration: Duration) {
+        let deadline = Instant::now().saturating_add(duration);
+        while Instant::now() < deadline {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Strategy that repeatedly calls a yield function (e.g., to let an RTOS run other tasks).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Yield<F>(pub F);
+
+impl<F: FnMut()> IdleStrategy for Yield<F> {
+    #[inline]
+    fn sleep_for(&mut self, duration: Duration) {
+        let deadline = Instant::now().saturating_add(duration);
+        while Instant::now() < deadline {
+            (self.0)();
+        }
+    }
+}
+
+/// User-provided sleep strategy.
+///
+/// The sleep function receives the maximum duration to sleep, and returns `true` if the MCU was
+/// woken up by an external event.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Sleep<F> {
+    sleep: F,
+    event: bool,
+}
+
+impl<F: FnMut(Duration) -> bool> Sleep<F> {
+    /// Creates a new sleep strategy with the given sleep function.
+    #[inline]
+    pub const fn new(sleep: F) -> Self {
+        Self {
+            sleep,
+            event: false,
+        }
+    }
+}
+
+impl<F: FnMut(Duration) -> bool> IdleStrategy for Sleep<F> {
+    #[inline]
+    fn sleep_for(&mut self, duration: Duration) {
+        self.event = (self.sleep)(duration);
+    }
+
+    #[inline]
+    fn wake_on_event(&mut self) -> bool {
+        self.event
+    }
+}
+
+/// Wake-up statistics of an [`IdleWaiter`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WakeStats {
+    /// Number of calls to [`IdleStrategy::sleep_for`].
+    pub sleeps: u64,
+    /// Number of wake-ups caused by external events.
+    pub events: u64,
+    /// Number of wake-ups that arrived before the deadline without an external event.
+    pub early: u64,
+}
+
+/// Wait helper that idles until the next state transition using an [`IdleStrategy`].
+pub struct IdleWaiter<S> {
+    strategy: S,
+    time_scale: f64,
+    pacer: Pacer,
+    last_rt: Option<Instant>,
+    stats: WakeStats,
+}
+
+impl<S: IdleStrategy> IdleWaiter<S> {
+    /// Creates a new idle waiter for the given configuration and strategy.
+    #[inline]
+    pub fn new(config: &Config, strategy: S) -> Self {
+        Self {
+            strategy,
+            time_scale: config.time_scale,
+            pacer: Pacer::new(config),
+            last_rt: None,
+            stats: WakeStats::default(),
+        }
+    }
+
+    /// Returns the wake-up statistics.
+    #[inline]
+    pub fn stats(&self) -> &WakeStats {
+        &self.stats
+    }
+
+    /// Returns a reference to the idle strategy.
+    #[inline]
+    pub fn strategy(&self) -> &S {
+        &self.strategy
+    }
+
+    /// Returns the idle strategy.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.strategy
+    }
+
+    /// Idles until `t_until` or until an external event arrives.
+    ///
+    /// On external events, `input_handler` is called to inject them into `input`.
+    /// It returns the virtual time at which the wait finished.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the wall-clock drift exceeds the maximum jitter of the configuration (if any).
+    pub fn wait<T: Bag>(
+        &mut self,
+        t_from: f64,
+        t_until: f64,
+        input: &mut T,
+        mut input_handler: impl FnMut(&mut T),
+    ) -> f64 {
+        let last = *self.last_rt.get_or_insert_with(Instant::now);
+        let Some((t_from, last)) = self.pacer.schedule(t_from, t_until, last, Instant::now) else {
+            return t_until;
+        };
+        let next_rt = core::time::Duration::try_from_secs_f64((t_until - t_from) * self.time_scale)
+            .ok()
+            .and_then(|duration| Duration::try_from(duration).ok())
+            .and_then(|duration| last.checked_add(duration))
+            .unwrap_or(Instant::MAX);
+
+        loop {
+            let now = Instant::now();
+            if now >= next_rt {
+                if let Some(max_jitter) = self.pacer.max_jitter() {
+                    let jitter = now - next_rt;
+                    if jitter.as_micros() > max_jitter.as_micros() as u64 {
+                        panic!("[IW]>> Jitter too high: {:?}", jitter);
+                    }
+                }
+                self.last_rt = Some(next_rt);
+                return t_until;
+            }
+
+            self.strategy.sleep_for(next_rt - now);
+            self.stats.sleeps += 1;
+            if self.strategy.wake_on_event() {
+                self.stats.events += 1;
+                input_handler(input);
+                if !input.is_empty() {
+                    let now = Instant::now();
+                    self.last_rt = Some(now);
+                    let elapsed = now.saturating_duration_since(last).as_micros() as f64 / 1e6;
+                    return f64::min(t_from + elapsed / self.time_scale, t_until);
+                }
+            } else if Instant::now() < next_rt {
+                self.stats.early += 1;
+            }
+        }
+    }
+}
+
+/// Closure for RT simulation that idles with the given [`IdleWaiter`].
+///
+/// See [`IdleWaiter::wait`] for more details.
+pub fn wait_until<'a, T: Bag, S: IdleStrategy>(
+    idle: &'a mut IdleWaiter<S>,
+    mut input_handler: impl FnMut(&mut T) + 'a,
+) -> impl FnMut(f64, f64, &mut T) -> f64 + 'a {
+    move |t_from, t_until, input: &mut T| idle.wait(t_from, t_until, input, &mut input_handler)
+}
+
+#[cfg(feature = "std")]
+pub use mock::MockIdle;
+
+#[cfg(feature = "std")]
+mod mock {
+    use super::IdleStrategy;
+    use embassy_time::{Duration, Instant};
+    use std::{collections::VecDeque, thread, vec::Vec};
+
+    /// Mock idle strategy for testing on hosted targets.
+    ///
+    /// It sleeps the current thread and records the requested durations.
+    /// Interrupts can be scheduled to wake it up before the deadline.
+    /// Interrupt times are relative to the first call to [`IdleStrategy::sleep_for`].
+    #[derive(Debug, Default)]
+    pub struct MockIdle {
+        start: Option<Instant>,
+        interrupts: VecDeque<(Duration, bool)>,
+        requested: Vec<Duration>,
+        event: bool,
+    }
+
+    impl MockIdle {
+        /// Creates a new mock strategy without interrupts.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Schedules an interrupt caused by an external event.
+        pub fn event_at(mut self, at: Duration) -> Self {
+            self.schedule(at, true);
+            self
+        }
+
+        /// Schedules a spurious interrupt (i.e., not caused by an external event).
+        pub fn spurious_at(mut self, at: Duration) -> Self {
+            self.schedule(at, false);
+            self
+        }
+
+        fn schedule(&mut self, at: Duration, event: bool) {
+            let i = self.interrupts.partition_point(|(t, _)| *t <= at);
+            self.interrupts.insert(i, (at, event));
+        }
+
+        /// Returns the durations requested to [`IdleStrategy::sleep_for`].
+        pub fn requested(&self) -> &[Duration] {
+            &self.requested
+        }
+    }
+
+    impl IdleStrategy for MockIdle {
+        fn sleep_for(&mut self, duration: Duration) {
+            self.requested.push(duration);
+            let now = Instant::now();
+            let start = *self.start.get_or_insert(now);
+            let deadline = now.saturating_add(duration);
+            self.event = false;
+            let wake = match self.interrupts.front() {
+                Some(&(at, event)) if start + at <= deadline => {
+                    self.interrupts.pop_front();
+                    self.event = event;
+                    start + at
+                }
+                _ => deadline,
+            };
+            let remaining = wake.saturating_duration_since(Instant::now());
+            thread::sleep(std::time::Duration::from_micros(remaining.as_micros()));
+        }
+
+        fn wake_on_event(&mut self) -> bool {
+            self.event
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        port::Port,
+        simulation::{test_utils::TestAtomic, AbstractSimulator, Simulable},
+    };
+
+    const CONFIG: Config = Config::new(0.0, 1.0, 0.01, None);
+
+    #[test]
+    fn spin_and_yield_reach_deadline() {
+        let yields = core::cell::Cell::new(0);
+        let mut idle = IdleWaiter::new(&CONFIG, Yield(|| yields.set(yields.get() + 1)));
+        let start = Instant::now();
+        assert_eq!(idle.wait(0.0, 0.5, &mut (), |_| {}), 0.5);
+        assert!(start.elapsed() >= Duration::from_millis(5));
+        assert_eq!(idle.stats().early, 0);
+        assert!(yields.get() > 0);
+
+        let mut idle = IdleWaiter::new(&CONFIG, Spin);
+        let start = Instant::now();
+        assert_eq!(idle.wait(0.0, 0.5, &mut (), |_| {}), 0.5);
+        assert!(start.elapsed() >= Duration::from_millis(5));
+        assert_eq!(idle.stats().sleeps, 1);
+    }
+
+    #[test]
+    fn sleep_reports_early_wakeups() {
+        // A sleep function that always wakes up halfway
+        let strategy = Sleep::new(|duration: Duration| {
+            std::thread::sleep(std::time::Duration::from_micros(duration.as_micros() / 2));
+            false
+        });
+        let mut idle = IdleWaiter::new(&CONFIG, strategy);
+        assert_eq!(idle.wait(0.0, 1.0, &mut (), |_| {}), 1.0);
+        let stats = idle.stats();
+        assert!(stats.early >= 1, "early wake-ups are reported");
+        assert_eq!(stats.events, 0);
+        // The last sleep is not early, unless the deadline passed right after checking it
+        assert!((stats.early..=stats.early + 1).contains(&stats.sleeps));
+    }
+
+    #[test]
+    fn mock_event_injection() {
+        let strategy = MockIdle::new()
+            .spurious_at(Duration::from_millis(10))
+            .event_at(Duration::from_millis(30));
+        let mut idle = IdleWaiter::new(&CONFIG, strategy);
+        let mut sim = TestAtomic::oneshot(f64::INFINITY).to_simulator();
+        let config = Config::new(0.0, 5.0, 0.01, None);
+
+        sim.simulate_rt(
+            &config,
+            wait_until(&mut idle, |input: &mut Port<usize, 1>| {
+                input.add_value(1).unwrap();
+            }),
+            |_| {},
+        );
+
+        assert_eq!(sim.ext_calls, 1, "event injected");
+        assert!((2.5..5.0).contains(&sim.last_elapsed));
+        assert_eq!(idle.stats().events, 1);
+        assert_eq!(idle.stats().early, 1);
+        // Spurious interrupt, event, and final deadline
+        let mock = idle.into_inner();
+        assert_eq!(mock.requested().len(), 3);
+        assert!(mock.requested()[0] > Duration::from_millis(40));
+    }
+}
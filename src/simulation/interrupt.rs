@@ -0,0 +1,314 @@
+//! Interrupt-safe input injection for [`AbstractSimulator::simulate_rt`](crate::simulation::AbstractSimulator::simulate_rt).
+//!
+//! On bare-metal targets without an async executor, interrupt handlers push input events to a
+//! statically allocated [`EventQueue`]. The [`wait_until`] closure sleeps until the next state
+//! transition or until an event is queued, and injects queued events into the input bag of the model.
+//!
+//! The queue is protected with [`critical_section`], so the final binary must provide a critical
+//! section implementation. This crate does not select one (not even with the `std` feature), and linking
+//! fails with undefined `_critical_section_1_0_*` symbols if none is provided. For instance:
+//!
+//! - On `std` targets, enable the `std` feature of `critical-section` in your `Cargo.toml`:
+//!   `critical-section = { version = "1.2", features = ["std"] }`.
+//! - On Cortex-M targets, enable the `critical-section-single-core` feature of `cortex-m`.
+//!
+//! See the [`critical_section`] documentation for other targets.
+//!
+//! # Example
+//!
+//! ```ignore
+//! static QUEUE: EventQueue<<MyInput as BagMux>::Mux, 8> = EventQueue::new();
+//!
+//! #[interrupt]
+//! fn EXTI0() {
+//!     let _ = QUEUE.push(MyInputMux::Button(()));
+//! }
+//!
+//! let wait = wait_until(&config, &QUEUE, |_deadline| cortex_m::asm::wfi());
+//! simulator.simulate_rt(&config, wait, |_| {});
+//! ```
+
+use crate::{
+    port::BagMux,
+    simulation::{Config, Pacer},
+};
+use core::cell::RefCell;
+use critical_section::Mutex;
+use embassy_time::{Duration, Instant};
+
+/// Fixed-capacity event queue that can be shared between interrupt handlers and the simulator.
+pub struct EventQueue<M, const N: usize> {
+    events: Mutex<RefCell<heapless::Deque<M, N>>>,
+}
+
+impl<M, const N: usize> EventQueue<M, N> {
+    /// Creates a new, empty event queue. It can be used to initialize a `static` queue.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            events: Mutex::new(RefCell::new(heapless::Deque::new())),
+        }
+    }
+
+    /// Pushes an event to the queue. It can be called from interrupt handlers.
+    ///
+    /// It returns the event back if the queue is full.
+    #[inline]
+    pub fn push(&self, event: M) -> Result<(), M> {
+        critical_section::with(|cs| self.events.borrow_ref_mut(cs).push_back(event))
+    }
+
+    /// Removes the oldest event from the queue.
+    #[inline]
+    pub fn pop(&self) -> Option<M> {
+        critical_section::with(|cs| self.events.borrow_ref_mut(cs).pop_front())
+    }
+
+    /// Returns the number of queued events.
+    #[inline]
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.events.borrow_ref(cs).len())
+    }
+
+    /// Returns `true` if there are no queued events.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        critical_section::with(|cs| self.events.borrow_ref(cs).is_empty())
+    }
+
+    /// Injects queued events into `bag`, in arrival order. It returns `true` if any event was injected.
+    ///
+    /// If a port of the bag is full, the event (and subsequent events) remain in the queue.
+    pub fn inject_into<T: BagMux<Mux = M>>(&self, bag: &mut T) -> bool {
+        critical_section::with(|cs| {
+            let mut events = self.events.borrow_ref_mut(cs);
+            let mut injected = false;
+            while let Some(event) = events.pop_front() {
+                if let Err(event) = bag.inject_event(event) {
+                    // There is room in the queue, as we just popped this event
+                    let _ = events.push_front(event);
+                    break;
+                }
+                injected = true;
+            }
+            injected
+        })
+    }
+}
+
+impl<M, const N: usize> Default for EventQueue<M, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Closure for RT simulation on bare-metal targets with interrupt-driven inputs.
+///
+/// It waits until the next state transition of the model or until an event is pushed to `queue`.
+/// Then, it injects the queued events into the input bag of the model.
+///
+/// While waiting, it repeatedly calls `wait_for_interrupt` with the wall-clock deadline
+/// ([`Instant::MAX`] if the model is passive). The hook is called outside any critical section, so
+/// interrupt handlers (and other threads) can push events while it runs. After every call, the queue
+/// and the deadline are checked again. The hook must return at least when an interrupt occurs
+/// (including the one that marks the deadline), and it may return spuriously.
+///
+/// An event pushed between the last check of the queue and the call to the hook is only noticed once the
+/// hook returns. Instructions such as `wfe` (which wakes up on events signaled before going to sleep)
+/// avoid this race. With `wfi`, the hook may miss the event until the next interrupt or the deadline.
+///
+/// # Panics
+///
+/// It panics if the wall-clock drift exceeds the maximum jitter of `config` (if any).
+pub fn wait_until<'q, T: BagMux, const N: usize>(
+    config: &Config,
+    queue: &'q EventQueue<T::Mux, N>,
+    mut wait_for_interrupt: impl FnMut(Instant) + 'q,
+) -> impl FnMut(f64, f64, &mut T) -> f64 + 'q {
+    let time_scale = config.time_scale;
+    let mut pacer = Pacer::new(config);
+    let mut last_rt: Option<Instant> = None;
+
+    move |t_from, t_until, input: &mut T| -> f64 {
+        let last = *last_rt.get_or_insert_with(Instant::now);
+        let Some((t_from, last)) = pacer.schedule(t_from, t_until, last, Instant::now) else {
+            return t_until;
+        };
+        let next_rt = core::time::Duration::try_from_secs_f64((t_until - t_from) * time_scale)
+            .ok()
+            .and_then(|duration| Duration::try_from(duration).ok())
+            .and_then(|duration| last.checked_add(duration))
+            .unwrap_or(Instant::MAX);
+
+        loop {
+            if queue.inject_into(input) {
+                let now = Instant::now();
+                last_rt = Some(now);
+                let elapsed = now.saturating_duration_since(last).as_micros() as f64 / 1e6;
+                return f64::min(t_from + elapsed / time_scale, t_until);
+            }
+            let now = Instant::now();
+            if now >= next_rt {
+                if let Some(max_jitter) = pacer.max_jitter() {
+                    let jitter = now - next_rt;
+                    if jitter.as_micros() > max_jitter.as_micros() as u64 {
+                        panic!("[WU]>> Jitter too high: {:?}", jitter);
+                    }
+                }
+                last_rt = Some(next_rt);
+                return t_until;
+            }
+            if queue.is_empty() {
+                wait_for_interrupt(next_rt);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        simulation::{AbstractSimulator, Simulable},
+        Port,
+    };
+
+    extern crate std;
+    use std::thread;
+
+    #[derive(crate::Bag, crate::BagMux)]
+    pub struct Jobs {
+        job: Port<usize, 1>,
+    }
+
+    type JobsMux = <Jobs as BagMux>::Mux;
+
+    #[test]
+    fn queue_fifo() {
+        static QUEUE: EventQueue<usize, 2> = EventQueue::new();
+        assert!(QUEUE.is_empty());
+        assert_eq!(QUEUE.push(1), Ok(()));
+        assert_eq!(QUEUE.push(2), Ok(()));
+        assert_eq!(QUEUE.push(3), Err(3), "queue is full");
+        assert_eq!(QUEUE.len(), 2);
+        assert_eq!(QUEUE.pop(), Some(1));
+        assert_eq!(QUEUE.pop(), Some(2));
+        assert_eq!(QUEUE.pop(), None);
+    }
+
+    #[test]
+    fn inject_keeps_overflowing_events() {
+        let queue = EventQueue::<JobsMux, 4>::new();
+        assert!(queue.push(JobsMux::Job(1)).is_ok());
+        assert!(queue.push(JobsMux::Job(2)).is_ok());
+
+        let mut bag = <Jobs as crate::port::Bag>::build();
+        assert!(queue.inject_into(&mut bag));
+        assert_eq!(bag.job.get_values(), &[1]);
+        assert_eq!(queue.len(), 1, "second event does not fit in the port");
+
+        bag.job.clear();
+        assert!(queue.inject_into(&mut bag));
+        assert_eq!(bag.job.get_values(), &[2]);
+        assert!(!queue.inject_into(&mut bag));
+    }
+
+    #[test]
+    fn wait_until_deadline() {
+        let queue = EventQueue::<JobsMux, 4>::new();
+        let config = Config::new(0.0, 1.0, 0.01, None);
+        let start = Instant::now();
+        let mut wakeups = 0;
+        let mut wait = wait_until(&config, &queue, |deadline| {
+            assert!(deadline >= start + Duration::from_millis(5));
+            assert!(deadline < Instant::MAX, "deadline of an active model");
+            wakeups += 1;
+        });
+        let mut bag = <Jobs as crate::port::Bag>::build();
+
+        assert_eq!(wait(0.0, 0.5, &mut bag), 0.5);
+        assert!(start.elapsed() >= Duration::from_millis(5));
+        assert!(crate::port::Bag::is_empty(&bag));
+        drop(wait);
+        assert!(wakeups > 0);
+    }
+
+    #[test]
+    fn wait_until_hook_outside_critical_section() {
+        let queue = EventQueue::<JobsMux, 4>::new();
+        let config = Config::new(0.0, 1.0, 1.0, None);
+        let mut wait = wait_until(&config, &queue, |deadline| {
+            assert_eq!(deadline, Instant::MAX, "deadline of a passive model");
+            // The interrupt handler pushes from another thread while the hook is running.
+            // Joining it would deadlock if the hook ran within a critical section.
+            thread::scope(|s| {
+                s.spawn(|| assert!(queue.push(JobsMux::Job(3)).is_ok()));
+            });
+        });
+        let mut bag = <Jobs as crate::port::Bag>::build();
+
+        let t = wait(0.0, f64::INFINITY, &mut bag);
+        assert!(t < 1.0, "woken up by the event at {t}");
+        assert_eq!(
+            bag.job.get_values(),
+            &[3],
+            "queue checked again after the hook"
+        );
+    }
+
+    /// Passive model that accumulates the received jobs.
+    struct Sink {
+        jobs: usize,
+        elapsed: f64,
+    }
+
+    impl crate::Component for Sink {
+        type Kind = crate::AtomicKind;
+        type Input = Jobs;
+        type Output = ();
+    }
+
+    impl crate::Atomic for Sink {
+        fn delta_int(&mut self) {}
+        fn delta_ext(&mut self, elapsed: f64, input: &Self::Input) {
+            self.jobs += input.job.get_values().iter().sum::<usize>();
+            self.elapsed = elapsed;
+        }
+        fn lambda(&self, _output: &mut Self::Output) {}
+        fn ta(&self) -> f64 {
+            f64::INFINITY
+        }
+    }
+
+    #[test]
+    fn simulate_rt_with_interrupts() {
+        static QUEUE: EventQueue<JobsMux, 4> = EventQueue::new();
+
+        let mut sim = Sink {
+            jobs: 0,
+            elapsed: f64::NAN,
+        }
+        .to_simulator();
+        let config = Config::new(0.0, 0.2, 1.0, None);
+
+        // Emulated interrupt handler
+        let isr = thread::spawn(|| {
+            thread::sleep(std::time::Duration::from_millis(50));
+            assert!(QUEUE.push(JobsMux::Job(7)).is_ok());
+        });
+        sim.simulate_rt(
+            &config,
+            wait_until(&config, &QUEUE, |_| thread::yield_now()),
+            |_| {},
+        );
+        isr.join().unwrap();
+
+        assert_eq!(sim.jobs, 7, "event injected from the interrupt");
+        assert!(
+            (0.04..0.2).contains(&sim.elapsed),
+            "event at {}",
+            sim.elapsed
+        );
+    }
+}
@@ -0,0 +1,894 @@
+//! Optimistic (Time Warp) parallel simulation on targets with `std`.
+//!
+//! A model is split into several [`LogicalProcess`]es, usually one per top-level component of a
+//! `#[coupled]` model (or per group of them). `#[coupled(optimistic)]` generates an `into_processes`
+//! method that does this split, with the internal couplings of the model as output links.
+//! Every logical process runs in its own thread and advances speculatively, without waiting for
+//! the others. It saves a snapshot of its simulator
+//! before every step, and rolls back when it receives a straggler (i.e., an event in its past).
+//! Events sent by rolled-back steps are canceled with anti-messages.
+//!
+//! Every few steps, the logical processes agree on the global virtual time (GVT): the lowest time
+//! to which any of them may still roll back. Snapshots and events older than the GVT are discarded
+//! (fossil collection), and the simulation ends when the GVT goes beyond `t_stop`.
+//!
+//! Simultaneous events are ordered with a micro-step counter, so the results are the same as those of
+//! [`simulate_vt`](crate::simulation::AbstractSimulator::simulate_vt) with the whole model in a single process.
+//! Events received at the same time from different output links are injected in the order in which the links were created.
+//!
+//! # Rollback
+//!
+//! Simulators must implement [`Rollback`]. It is implemented for every [`Clone`] type, so it is enough
+//! to derive [`Clone`] for every atomic model and to add `#[derive(Clone)]` below the
+//! [`coupled`](crate::coupled) attribute of every coupled model. Note that side effects of the
+//! model functions (e.g., printing to the console) may happen more than once.
+
+use crate::{
+    port::BagMux,
+    simulation::{AbstractSimulator, Config},
+};
+use std::{
+    boxed::Box,
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicIsize, AtomicU64, Ordering},
+        mpsc, Condvar, Mutex, PoisonError,
+    },
+    thread,
+    vec::Vec,
+};
+
+/// Interface for saving and restoring the state of a simulator.
+///
+/// It is implemented for every [`Clone`] type, using clones as snapshots.
+pub trait Rollback {
+    /// Saved state.
+    type Snapshot;
+
+    /// Saves the current state.
+    fn save(&self) -> Self::Snapshot;
+
+    /// Restores a previously saved state.
+    fn restore(&mut self, snapshot: Self::Snapshot);
+}
+
+impl<T: Clone> Rollback for T {
+    type Snapshot = T;
+
+    #[inline]
+    fn save(&self) -> Self::Snapshot {
+        self.clone()
+    }
+
+    #[inline]
+    fn restore(&mut self, snapshot: Self::Snapshot) {
+        *self = snapshot;
+    }
+}
+
+/// Statistics of a logical process.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessStats {
+    /// Number of executed steps, including the rolled-back ones.
+    pub steps: u64,
+    /// Number of rollbacks.
+    pub rollbacks: u64,
+    /// Number of steps undone by rollbacks.
+    pub rolled_back_steps: u64,
+    /// Number of anti-messages sent.
+    pub anti_messages: u64,
+}
+
+/// Simulation time with a micro-step counter to order simultaneous steps.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Stamp {
+    t: f64,
+    micro: u32,
+}
+
+impl Stamp {
+    const MIN: Self = Self::new(f64::NEG_INFINITY, 0);
+    const MAX: Self = Self::new(f64::INFINITY, u32::MAX);
+
+    #[inline]
+    const fn new(t: f64, micro: u32) -> Self {
+        Self { t, micro }
+    }
+
+    #[inline]
+    fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// Message exchanged by logical processes.
+enum Message<T> {
+    /// Event sent through the given output link.
+    Event {
+        id: u64,
+        link: u64,
+        stamp: Stamp,
+        event: T,
+    },
+    /// Cancels a previously sent event.
+    Anti { id: u64 },
+}
+
+/// Source of unique message identifiers.
+static NEXT_MESSAGE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Source of unique output link identifiers.
+static NEXT_LINK_ID: AtomicU64 = AtomicU64::new(0);
+
+mod private {
+    use super::*;
+
+    /// Barrier that fails if any of the participants panicked.
+    pub(super) struct Barrier {
+        n: usize,
+        state: Mutex<(usize, usize, bool)>, // (count, generation, broken)
+        cvar: Condvar,
+    }
+
+    impl Barrier {
+        pub(super) fn new(n: usize) -> Self {
+            Self {
+                n,
+                state: Mutex::new((0, 0, false)),
+                cvar: Condvar::new(),
+            }
+        }
+
+        /// Blocks until all the participants have reached the barrier.
+        /// It returns `true` for the last participant to arrive.
+        pub(super) fn wait(&self) -> bool {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            let generation = state.1;
+            state.0 += 1;
+            if state.0 == self.n {
+                state.0 = 0;
+                state.1 += 1;
+                self.cvar.notify_all();
+                return true;
+            }
+            while state.1 == generation && !state.2 {
+                state = self
+                    .cvar
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner);
+            }
+            if state.2 {
+                drop(state);
+                panic!("another logical process panicked");
+            }
+            false
+        }
+
+        /// Breaks the barrier, so all the waiting participants panic.
+        fn abort(&self) {
+            self.state.lock().unwrap_or_else(PoisonError::into_inner).2 = true;
+            self.cvar.notify_all();
+        }
+    }
+
+    /// Breaks the barrier if the current thread panics.
+    pub(super) struct AbortOnPanic<'a>(pub(super) &'a Barrier);
+
+    impl Drop for AbortOnPanic<'_> {
+        fn drop(&mut self) {
+            if thread::panicking() {
+                self.0.abort();
+            }
+        }
+    }
+
+    /// Shared state of the logical processes of a simulation.
+    pub struct Control {
+        pub(super) barrier: Barrier,
+        /// Number of messages sent but not received yet.
+        pub(super) in_transit: AtomicIsize,
+        /// Accumulator for computing the GVT.
+        pub(super) gvt: Mutex<Stamp>,
+    }
+}
+
+use private::{AbortOnPanic, Barrier, Control};
+
+/// Type-erased [`LogicalProcess`], as expected by [`simulate`].
+pub trait Process: Send {
+    #[doc(hidden)]
+    fn run(&mut self, config: &Config, gvt_interval: usize, control: &Control);
+}
+
+type InputMux<S> = <<S as AbstractSimulator>::Input as BagMux>::Mux;
+type OutputMux<S> = <<S as AbstractSimulator>::Output as BagMux>::Mux;
+
+/// Type-erased output link of a logical process.
+trait OutputLink<O> {
+    /// Sends the events that the coupling function produces for `event`.
+    /// The identifier of every sent message is passed to `sent`.
+    fn send_event(&mut self, stamp: Stamp, event: &O, sent: &mut dyn FnMut(u64));
+
+    fn send_anti(&mut self, id: u64);
+}
+
+struct Coupling<T, F> {
+    id: u64,
+    sender: mpsc::Sender<Message<T>>,
+    coupling: F,
+}
+
+impl<O, T, F: FnMut(&O, &mut dyn FnMut(T))> OutputLink<O> for Coupling<T, F> {
+    fn send_event(&mut self, stamp: Stamp, event: &O, sent: &mut dyn FnMut(u64)) {
+        let Self {
+            id: link,
+            sender,
+            coupling,
+        } = self;
+        coupling(event, &mut |event| {
+            let id = NEXT_MESSAGE_ID.fetch_add(1, Ordering::Relaxed);
+            // The receiver is gone only if its thread panicked, and then the whole simulation fails
+            let _ = sender.send(Message::Event {
+                id,
+                link: *link,
+                stamp,
+                event,
+            });
+            sent(id);
+        });
+    }
+
+    fn send_anti(&mut self, id: u64) {
+        let _ = self.sender.send(Message::Anti { id });
+    }
+}
+
+/// Destination of the events of an output link. See [`LogicalProcess::inbox`].
+pub struct Inbox<T>(mpsc::Sender<Message<T>>);
+
+impl<T> Clone for Inbox<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Event received by a logical process.
+struct Input<T> {
+    stamp: Stamp,
+    id: u64,
+    link: u64,
+    event: T,
+}
+
+/// Step executed by a logical process that may still be rolled back.
+struct Step<S: Rollback> {
+    stamp: Stamp,
+    /// State of the simulator before the step.
+    snapshot: S::Snapshot,
+    /// Next internal step before the step.
+    next: Stamp,
+    /// Messages sent during the step (output link index and message identifier).
+    sent: Vec<(usize, u64)>,
+}
+
+/// Part of a model that runs in its own thread during an optimistic simulation.
+///
+/// The input events of the process are received as [`BagMux::Mux`] values of its input bag, and
+/// its output events are sent to other processes after being mapped by a coupling function.
+pub struct LogicalProcess<'a, S>
+where
+    S: AbstractSimulator + Rollback,
+    S::Input: BagMux,
+    S::Output: BagMux,
+{
+    simulator: S,
+    sender: mpsc::Sender<Message<InputMux<S>>>,
+    receiver: mpsc::Receiver<Message<InputMux<S>>>,
+    outputs: Vec<Box<dyn OutputLink<OutputMux<S>> + Send + 'a>>,
+    /// Next internal step.
+    next: Stamp,
+    /// Local virtual time (i.e., last executed step).
+    lvt: Stamp,
+    /// Received events, sorted by stamp and output link.
+    inputs: Vec<Input<InputMux<S>>>,
+    history: VecDeque<Step<S>>,
+    /// Messages sent by the rolled-back step at the given stamp, which remain valid when it is executed again.
+    kept: Option<(Stamp, Vec<(usize, u64)>)>,
+    stats: ProcessStats,
+}
+
+impl<'a, S> LogicalProcess<'a, S>
+where
+    S: AbstractSimulator + Rollback,
+    S::Input: BagMux,
+    S::Output: BagMux,
+    InputMux<S>: Clone,
+{
+    /// Creates a new logical process without output links.
+    pub fn new(simulator: S) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            simulator,
+            sender,
+            receiver,
+            outputs: Vec::new(),
+            next: Stamp::MAX,
+            lvt: Stamp::MIN,
+            inputs: Vec::new(),
+            history: VecDeque::new(),
+            kept: None,
+            stats: ProcessStats::default(),
+        }
+    }
+
+    /// Adds an output link to another logical process.
+    ///
+    /// Every output event of this process is mapped by `coupling`. If it returns `Some`,
+    /// the resulting event is injected to the input bag of `to`.
+    pub fn with_output<R>(
+        self,
+        to: &LogicalProcess<'_, R>,
+        mut coupling: impl FnMut(&OutputMux<S>) -> Option<InputMux<R>> + Send + 'a,
+    ) -> Self
+    where
+        R: AbstractSimulator + Rollback,
+        R::Input: BagMux,
+        R::Output: BagMux,
+        InputMux<R>: Send + 'a,
+    {
+        self.with_coupling(Inbox(to.sender.clone()), move |event, send| {
+            if let Some(event) = coupling(event) {
+                send(event);
+            }
+        })
+    }
+
+    /// Adds an output link to the process that owns the given inbox (which may be this process).
+    ///
+    /// Every output event of this process is passed to `coupling`, which may send any number
+    /// of events to the input bag of the destination with its second argument.
+    pub fn with_coupling<T: Send + 'a>(
+        mut self,
+        to: Inbox<T>,
+        coupling: impl FnMut(&OutputMux<S>, &mut dyn FnMut(T)) + Send + 'a,
+    ) -> Self {
+        self.outputs.push(Box::new(Coupling {
+            id: NEXT_LINK_ID.fetch_add(1, Ordering::Relaxed),
+            sender: to.0,
+            coupling,
+        }));
+        self
+    }
+
+    /// Returns the inbox of the process, to which output links of other processes send their events.
+    pub fn inbox(&self) -> Inbox<InputMux<S>> {
+        Inbox(self.sender.clone())
+    }
+
+    /// Returns a reference to the simulator of the process.
+    pub fn simulator(&self) -> &S {
+        &self.simulator
+    }
+
+    /// Consumes the process and returns its simulator.
+    pub fn into_inner(self) -> S {
+        self.simulator
+    }
+
+    /// Returns the statistics of the last simulation.
+    pub fn stats(&self) -> ProcessStats {
+        self.stats
+    }
+
+    /// Initializes the process for a new simulation.
+    fn start(&mut self, t_start: f64) {
+        self.next = Stamp::new(self.simulator.start(t_start), 0);
+        self.lvt = Stamp::MIN;
+        self.inputs.clear();
+        self.history.clear();
+        self.kept = None;
+        self.stats = ProcessStats::default();
+    }
+
+    /// Index of the first received event that has not been processed yet.
+    fn first_unprocessed(&self) -> usize {
+        self.inputs.partition_point(|input| input.stamp <= self.lvt)
+    }
+
+    /// Stamp of the next step to execute.
+    fn next_stamp(&self) -> Stamp {
+        match self.inputs.get(self.first_unprocessed()) {
+            Some(input) => self.next.min(input.stamp),
+            None => self.next,
+        }
+    }
+
+    /// Handles all the messages received so far.
+    fn drain(&mut self, control: &Control) {
+        while let Ok(message) = self.receiver.try_recv() {
+            control.in_transit.fetch_sub(1, Ordering::SeqCst);
+            match message {
+                Message::Event {
+                    id,
+                    link,
+                    stamp,
+                    event,
+                } => {
+                    if stamp <= self.lvt {
+                        self.rollback(stamp, control);
+                    }
+                    let i = self
+                        .inputs
+                        .partition_point(|input| (input.stamp, input.link) <= (stamp, link));
+                    let input = Input {
+                        stamp,
+                        id,
+                        link,
+                        event,
+                    };
+                    self.inputs.insert(i, input);
+                }
+                Message::Anti { id } => {
+                    // Anti-messages always arrive after their event, as channels are FIFO
+                    if let Some(i) = self.inputs.iter().position(|input| input.id == id) {
+                        let stamp = self.inputs[i].stamp;
+                        if stamp <= self.lvt {
+                            self.rollback(stamp, control);
+                        }
+                        self.inputs.remove(i);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Executes the step at the given stamp.
+    fn step(
+        &mut self,
+        stamp: Stamp,
+        input: &mut S::Input,
+        output: &mut S::Output,
+        control: &Control,
+    ) {
+        let snapshot = self.simulator.save();
+        let mut sent = None;
+        if let Some((kept_stamp, kept)) = self.kept.take() {
+            if kept_stamp == stamp {
+                sent = Some(kept);
+            } else {
+                self.cancel(kept, control);
+            }
+        }
+        if stamp.t >= self.next.t {
+            // The output function is computed again, as inner couplings depend on it
+            self.simulator.lambda(output, stamp.t);
+        }
+        let sent = match sent {
+            Some(sent) => sent,
+            None => self.send_output(output, stamp, control),
+        };
+        let first = self.first_unprocessed();
+        for event in self.inputs[first..]
+            .iter()
+            .take_while(|event| event.stamp == stamp)
+        {
+            if input.inject_event(event.event.clone()).is_err() {
+                panic!("input port of logical process is full");
+            }
+        }
+        let t_next = self.simulator.delta(input, output, stamp.t);
+        // Transitions at the same time happen in the next micro-step
+        let micro = if t_next == stamp.t {
+            stamp.micro + 1
+        } else {
+            0
+        };
+        self.history.push_back(Step {
+            stamp,
+            snapshot,
+            next: self.next,
+            sent,
+        });
+        self.next = Stamp::new(t_next, micro);
+        self.lvt = stamp;
+        self.stats.steps += 1;
+    }
+
+    /// Sends the output events of a step through the output links. It returns the sent messages.
+    fn send_output(
+        &mut self,
+        output: &S::Output,
+        stamp: Stamp,
+        control: &Control,
+    ) -> Vec<(usize, u64)> {
+        let mut sent = Vec::new();
+        output.eject_events(|event| {
+            for (i, link) in self.outputs.iter_mut().enumerate() {
+                link.send_event(stamp, &event, &mut |id| sent.push((i, id)));
+            }
+        });
+        control
+            .in_transit
+            .fetch_add(sent.len() as isize, Ordering::SeqCst);
+        sent
+    }
+
+    /// Undoes all the steps at or after the given stamp.
+    ///
+    /// The output of a step does not depend on its input events, so the messages sent by the step
+    /// at the given stamp (if any) are kept. Otherwise, zero-delay loops between processes would
+    /// cancel each other forever.
+    fn rollback(&mut self, stamp: Stamp, control: &Control) {
+        if let Some((_, kept)) = self.kept.take() {
+            self.cancel(kept, control);
+        }
+        let mut first = None;
+        while self.history.back().is_some_and(|step| step.stamp >= stamp) {
+            let mut step = self.history.pop_back().unwrap();
+            let sent = core::mem::take(&mut step.sent);
+            if step.stamp == stamp {
+                self.kept = Some((stamp, sent));
+            } else {
+                self.cancel(sent, control);
+            }
+            self.stats.rolled_back_steps += 1;
+            first = Some(step);
+        }
+        if let Some(step) = first {
+            self.simulator.restore(step.snapshot);
+            self.next = step.next;
+            self.stats.rollbacks += 1;
+        }
+        // Older steps may have been fossil-collected, but no event older than the GVT remains
+        self.lvt = self.history.back().map_or(Stamp::MIN, |step| step.stamp);
+    }
+
+    /// Sends anti-messages for the given messages.
+    fn cancel(&mut self, sent: Vec<(usize, u64)>, control: &Control) {
+        for &(i, id) in sent.iter() {
+            self.outputs[i].send_anti(id);
+        }
+        control
+            .in_transit
+            .fetch_add(sent.len() as isize, Ordering::SeqCst);
+        self.stats.anti_messages += sent.len() as u64;
+    }
+
+    /// Computes the GVT together with the rest of processes and discards everything older.
+    fn gvt(&mut self, control: &Control) -> Stamp {
+        // Wait until no message is in transit, so the GVT only depends on local information
+        loop {
+            self.drain(control);
+            control.barrier.wait();
+            let quiet = control.in_transit.load(Ordering::SeqCst) == 0;
+            control.barrier.wait();
+            if quiet {
+                break;
+            }
+        }
+        {
+            let mut gvt = control.gvt.lock().unwrap_or_else(PoisonError::into_inner);
+            *gvt = gvt.min(self.next_stamp());
+        }
+        control.barrier.wait();
+        let gvt = *control.gvt.lock().unwrap_or_else(PoisonError::into_inner);
+        if control.barrier.wait() {
+            *control.gvt.lock().unwrap_or_else(PoisonError::into_inner) = Stamp::MAX;
+        }
+
+        // Fossil collection
+        while self.history.front().is_some_and(|step| step.stamp < gvt) {
+            self.history.pop_front();
+        }
+        let n = self.inputs.partition_point(|input| input.stamp < gvt);
+        self.inputs.drain(..n);
+        gvt
+    }
+}
+
+impl<S> Process for LogicalProcess<'_, S>
+where
+    S: AbstractSimulator + Rollback + Send,
+    S::Snapshot: Send,
+    S::Input: BagMux,
+    S::Output: BagMux,
+    InputMux<S>: Clone + Send,
+{
+    fn run(&mut self, config: &Config, gvt_interval: usize, control: &Control) {
+        let _guard = AbortOnPanic(&control.barrier);
+        // Only the first step at t_stop is executed, as in the sequential simulation loop
+        let limit = if config.t_start < config.t_stop {
+            Stamp::new(config.t_stop, 0)
+        } else {
+            Stamp::MIN
+        };
+        self.start(config.t_start);
+        let mut input = self.simulator.build_input();
+        let mut output = self.simulator.build_output();
+        loop {
+            for _ in 0..gvt_interval {
+                self.drain(control);
+                let stamp = self.next_stamp();
+                if stamp > limit {
+                    break;
+                }
+                self.step(stamp, &mut input, &mut output, control);
+            }
+            if self.gvt(control) > limit {
+                break;
+            }
+        }
+        self.simulator.stop();
+        self.history.clear();
+        self.inputs.clear();
+    }
+}
+
+/// Executes an optimistic simulation from `t_start` to `t_stop` with a virtual clock.
+///
+/// Every logical process runs in its own thread. The GVT is computed every `gvt_interval` steps
+/// (or earlier, if a process has nothing to do).
+///
+/// # Panics
+///
+/// It panics if `gvt_interval` is zero or if any of the processes panics.
+pub fn simulate(config: &Config, gvt_interval: usize, processes: &mut [&mut dyn Process]) {
+    assert!(gvt_interval > 0, "GVT interval must be greater than zero");
+    let control = Control {
+        barrier: Barrier::new(processes.len()),
+        in_transit: AtomicIsize::new(0),
+        gvt: Mutex::new(Stamp::MAX),
+    };
+    thread::scope(|s| {
+        for process in processes.iter_mut() {
+            let control = &control;
+            s.spawn(move || process.run(config, gvt_interval, control));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpt::{Generator, Processor, Transducer};
+    use crate::port::Bag;
+    use crate::simulation::Simulable;
+    use crate::{Component, ComponentsInput, ComponentsOutput, Coupled, CoupledKind, Port};
+
+    #[derive(crate::Bag, crate::BagMux)]
+    pub struct Jobs {
+        job: Port<usize, 1>,
+    }
+
+    /// Experimental frame: generator and transducer.
+    #[crate::coupled]
+    #[derive(Clone)]
+    pub struct Front {
+        generator: Generator,
+        transducer: Transducer,
+    }
+
+    impl Component for Front {
+        type Kind = CoupledKind;
+        type Input = Jobs;
+        type Output = Jobs;
+    }
+
+    impl Coupled for Front {
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            from.generator
+                .couple(&mut to.transducer.in_generator)
+                .unwrap();
+            from.transducer.couple(&mut to.generator).unwrap();
+        }
+        fn eic(from: &Self::Input, to: &mut ComponentsInput<Self>) {
+            from.job.couple(&mut to.transducer.in_processor).unwrap();
+        }
+        fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
+            from.generator.couple(&mut to.job).unwrap();
+        }
+    }
+
+    /// Pipeline of two processors.
+    #[crate::coupled]
+    #[derive(Clone)]
+    pub struct Back {
+        first: Processor,
+        second: Processor,
+    }
+
+    impl Component for Back {
+        type Kind = CoupledKind;
+        type Input = Jobs;
+        type Output = Jobs;
+    }
+
+    impl Coupled for Back {
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            from.first.couple(&mut to.second).unwrap();
+        }
+        fn eic(from: &Self::Input, to: &mut ComponentsInput<Self>) {
+            from.job.couple(&mut to.first).unwrap();
+        }
+        fn eoc(from: &ComponentsOutput<Self>, to: &mut Self::Output) {
+            from.second.couple(&mut to.job).unwrap();
+        }
+    }
+
+    #[crate::coupled(optimistic)]
+    pub struct Whole {
+        front: Front,
+        back: Back,
+    }
+
+    impl Component for Whole {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for Whole {
+        fn ic(from: &ComponentsOutput<Self>, to: &mut ComponentsInput<Self>) {
+            from.front.job.couple(&mut to.back.job).unwrap();
+            from.back.job.couple(&mut to.front.job).unwrap();
+        }
+    }
+
+    fn front(period: f64) -> Front {
+        Front::build(Generator::new(period), Transducer::new(10.0))
+    }
+
+    fn back(first: f64, second: f64) -> Back {
+        Back::build(Processor::new(first), Processor::new(second))
+    }
+
+    /// Acceptance and throughput of the model simulated in a single process.
+    fn sequential(period: f64, first: f64, second: f64, config: &Config) -> (f64, f64) {
+        let mut sim = Whole::build(front(period), back(first, second)).to_simulator();
+        sim.simulate_vt(config);
+        let trans = &*sim.components.front.components.transducer;
+        (trans.acceptance(), trans.throughput())
+    }
+
+    #[test]
+    fn clone_snapshots_restore_state() {
+        let mut sim = front(1.0).to_simulator();
+        sim.start(0.0);
+        let snapshot = sim.save();
+        for _ in 0..2 {
+            let mut output = Jobs::build();
+            sim.lambda(&mut output, 0.0);
+            assert_eq!(output.job.get_values(), &[0], "first job should be 0");
+            assert_eq!(sim.delta(&mut Jobs::build(), &mut output, 0.0), 1.0);
+            sim.restore(snapshot.clone());
+        }
+    }
+
+    #[test]
+    fn stragglers_roll_back_and_cancel_events() {
+        let config = Config::new(0.0, 20.0, 1.0, None);
+        let control = Control {
+            barrier: Barrier::new(1),
+            in_transit: AtomicIsize::new(0),
+            gvt: Mutex::new(Stamp::MAX),
+        };
+        let sink = LogicalProcess::new(back(1.0, 1.0).to_simulator());
+        let mut lp =
+            LogicalProcess::new(front(1.0).to_simulator()).with_output(&sink, |e| Some(e.clone()));
+        let mut input = Jobs::build();
+        let mut output = Jobs::build();
+
+        // The generator advances speculatively up to t = 5
+        lp.start(config.t_start);
+        while lp.next_stamp() <= Stamp::new(5.0, 0) {
+            lp.step(lp.next_stamp(), &mut input, &mut output, &control);
+        }
+        assert_eq!(lp.stats().steps, 6);
+
+        // A processed job arrives at t = 2.5, so steps at t = 3, 4, 5 are undone
+        let event = <Jobs as BagMux>::Mux::Job(0);
+        let _ = lp.sender.send(Message::Event {
+            id: u64::MAX,
+            link: 0,
+            stamp: Stamp::new(2.5, 0),
+            event,
+        });
+        control.in_transit.fetch_add(1, Ordering::SeqCst);
+        lp.drain(&control);
+        let stats = lp.stats();
+        assert_eq!(stats.rollbacks, 1);
+        assert_eq!(stats.rolled_back_steps, 3);
+        assert_eq!(stats.anti_messages, 3);
+        assert_eq!(lp.next_stamp(), Stamp::new(2.5, 0));
+
+        // Sink received 6 events and 3 anti-messages
+        assert_eq!(sink.receiver.try_iter().count(), 9);
+
+        // The result matches a process that got the event on time
+        let mut expected = LogicalProcess::new(front(1.0).to_simulator());
+        expected.start(config.t_start);
+        let _ = expected.sender.send(Message::Event {
+            id: 0,
+            link: 0,
+            stamp: Stamp::new(2.5, 0),
+            event: <Jobs as BagMux>::Mux::Job(0),
+        });
+        expected.drain(&control);
+        for lp in [&mut lp, &mut expected] {
+            while lp.next_stamp() <= Stamp::new(20.0, 0) {
+                lp.step(lp.next_stamp(), &mut input, &mut output, &control);
+            }
+        }
+        let (got, expected) = (
+            &*lp.simulator().components.transducer,
+            &*expected.simulator().components.transducer,
+        );
+        assert_eq!(got.acceptance(), expected.acceptance());
+        assert_eq!(got.throughput(), expected.throughput());
+    }
+
+    #[test]
+    fn optimistic_matches_sequential() {
+        // Simultaneous events across processes, zero-delay pipelines, and events at t_stop
+        let cases = [
+            (1.0, 2.5, 0.5),
+            (1.0, 1.0, 0.0),
+            (2.0, 0.5, 1.5),
+            (0.5, 1.0, 1.0),
+        ];
+        let mut rollbacks = 0;
+        for (period, first, second) in cases {
+            for t_stop in [20.0, 10.0, 5.0, 0.0] {
+                for gvt_interval in [1, 4, 64] {
+                    let config = Config::new(0.0, t_stop, 1.0, None);
+                    let expected = sequential(period, first, second, &config);
+
+                    let mut back = LogicalProcess::new(back(first, second).to_simulator());
+                    let mut front = LogicalProcess::new(front(period).to_simulator())
+                        .with_output(&back, |e| Some(e.clone()));
+                    back = back.with_output(&front, |e| Some(e.clone()));
+                    simulate(&config, gvt_interval, &mut [&mut front, &mut back]);
+
+                    let trans = &*front.simulator().components.transducer;
+                    assert_eq!(
+                        (trans.acceptance(), trans.throughput()),
+                        expected,
+                        "period {period}, processing times {first} and {second}, t_stop {t_stop}, GVT interval {gvt_interval}"
+                    );
+                    rollbacks += front.stats().rollbacks + back.stats().rollbacks;
+                }
+            }
+        }
+        assert!(rollbacks > 0, "processes should advance speculatively");
+    }
+
+    #[test]
+    fn processes_of_coupled_model_match_sequential() {
+        for t_stop in [20.0, 5.0, 0.0] {
+            let config = Config::new(0.0, t_stop, 1.0, None);
+            let expected = sequential(1.0, 2.5, 0.5, &config);
+
+            let mut processes = Whole::build(front(1.0), back(2.5, 0.5)).into_processes();
+            processes.simulate(&config, 4);
+            let trans = &*processes.front.simulator().components.transducer;
+            assert_eq!((trans.acceptance(), trans.throughput()), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_are_propagated() {
+        let config = Config::new(0.0, 20.0, 1.0, None);
+        let mut back = LogicalProcess::new(back(1.0, 1.0).to_simulator());
+        // The front process sends every job twice, which overflows the input port of the back process
+        let mut front = LogicalProcess::new(front(1.0).to_simulator())
+            .with_output(&back, |e| Some(e.clone()))
+            .with_output(&back, |e| Some(e.clone()));
+        back = back.with_output(&front, |e| Some(e.clone()));
+        simulate(&config, 4, &mut [&mut front, &mut back]);
+    }
+}
@@ -0,0 +1,596 @@
+//! Execution time profiling and deadline monitoring of atomic models.
+//!
+//! Wrap an atomic model in [`Profiled`] to measure how long its transition, output, and time advance
+//! functions take. Measurements use a pluggable [`CycleCounter`]: [`EmbassyCounter`], [`StdCounter`]
+//! (with the `std` feature), or any `Fn() -> u64` closure (e.g., reading the DWT cycle counter).
+//! Budgets can be set per transition kind, with a callback on overrun. The callback can be any
+//! `Fn(&Overrun)` closure or trait object reference (e.g., `&'static dyn Fn(&Overrun)`).
+//!
+//! [`Profiled`] wraps both [`Atomic`] and [`TryAtomic`] models, and forwards [`Reset`] to the inner model.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[coupled]
+//! struct Model {
+//!     sensor: Profiled<Sensor, fn() -> u64>,
+//! }
+//!
+//! let sensor = Profiled::new(Sensor::new(), (|| DWT::cycle_count() as u64) as fn() -> u64)
+//!     .with_budget(Transition::DeltaExt, 2_000)
+//!     .on_overrun(|overrun| defmt::warn!("overrun: {}", overrun.elapsed));
+//! ```
+
+use crate::{
+    component::{
+        atomic::{Atomic, TryAtomic},
+        Component,
+    },
+    simulation::reset::Reset,
+};
+use core::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+};
+
+/// Interface for monotonic cycle or time counters.
+pub trait CycleCounter {
+    /// Returns the current value of the counter.
+    ///
+    /// Elapsed values are computed with wrapping subtraction, so the counter must cover the whole `u64` range.
+    fn now(&self) -> u64;
+}
+
+impl<F: Fn() -> u64> CycleCounter for F {
+    #[inline(always)]
+    fn now(&self) -> u64 {
+        self()
+    }
+}
+
+/// Counter based on [`embassy_time::Instant`]. It counts ticks of the embassy time driver.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EmbassyCounter;
+
+impl CycleCounter for EmbassyCounter {
+    #[inline(always)]
+    fn now(&self) -> u64 {
+        embassy_time::Instant::now().as_ticks()
+    }
+}
+
+/// Counter based on [`std::time::Instant`]. It counts nanoseconds.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct StdCounter {
+    origin: std::time::Instant,
+}
+
+#[cfg(feature = "std")]
+impl Default for StdCounter {
+    fn default() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl CycleCounter for StdCounter {
+    #[inline(always)]
+    fn now(&self) -> u64 {
+        self.origin.elapsed().as_nanos() as u64
+    }
+}
+
+/// Kinds of profiled functions of an atomic model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transition {
+    /// Internal transition function ([`Atomic::delta_int`] or [`TryAtomic::try_delta_int`]).
+    DeltaInt,
+    /// External transition function ([`Atomic::delta_ext`] or [`TryAtomic::try_delta_ext`]).
+    DeltaExt,
+    /// Confluent transition function ([`Atomic::delta_conf`] or [`TryAtomic::try_delta_conf`]).
+    /// With the default implementation, it includes the internal and external transitions,
+    /// which are not accounted to [`Transition::DeltaInt`] and [`Transition::DeltaExt`].
+    DeltaConf,
+    /// Output function ([`Atomic::lambda`] or [`TryAtomic::try_lambda`]).
+    Lambda,
+    /// Time advance function ([`Atomic::ta`] or [`TryAtomic::ta`]).
+    Ta,
+}
+
+impl Transition {
+    /// All the transition kinds.
+    pub const ALL: [Transition; 5] = [
+        Self::DeltaInt,
+        Self::DeltaExt,
+        Self::DeltaConf,
+        Self::Lambda,
+        Self::Ta,
+    ];
+}
+
+/// Execution statistics of a transition kind, in counter units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionStats {
+    /// Number of executions.
+    pub count: u64,
+    /// Minimum execution time.
+    pub min: u64,
+    /// Maximum execution time (i.e., the observed worst-case execution time).
+    pub max: u64,
+    /// Accumulated execution time.
+    pub total: u64,
+    /// Number of executions that exceeded the budget.
+    pub overruns: u64,
+}
+
+impl TransitionStats {
+    const EMPTY: Self = Self {
+        count: 0,
+        min: u64::MAX,
+        max: 0,
+        total: 0,
+        overruns: 0,
+    };
+
+    /// Returns the mean execution time, or `None` if there are no executions.
+    #[inline]
+    pub fn mean(&self) -> Option<u64> {
+        self.total.checked_div(self.count)
+    }
+}
+
+impl Default for TransitionStats {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// Budget overrun of a transition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overrun {
+    /// Kind of the transition that exceeded its budget.
+    pub transition: Transition,
+    /// Execution time of the transition.
+    pub elapsed: u64,
+    /// Budget of the transition.
+    pub budget: u64,
+}
+
+/// Execution statistics and budgets of a profiled model.
+///
+/// `F` is the type of the overrun callback. By default, it is a function pointer.
+pub struct Profiler<C, F = fn(&Overrun)> {
+    counter: C,
+    // Cells are required, as lambda and ta only receive a shared reference to the model
+    stats: [Cell<TransitionStats>; 5],
+    budgets: [Option<u64>; 5],
+    on_overrun: Option<F>,
+}
+
+impl<C: CycleCounter> Profiler<C> {
+    /// Creates a new profiler with the given counter, without budgets nor overrun callback.
+    pub const fn new(counter: C) -> Self {
+        Self {
+            counter,
+            stats: [
+                Cell::new(TransitionStats::EMPTY),
+                Cell::new(TransitionStats::EMPTY),
+                Cell::new(TransitionStats::EMPTY),
+                Cell::new(TransitionStats::EMPTY),
+                Cell::new(TransitionStats::EMPTY),
+            ],
+            budgets: [None; 5],
+            on_overrun: None,
+        }
+    }
+}
+
+impl<C: CycleCounter, F: Fn(&Overrun)> Profiler<C, F> {
+    /// Returns the execution statistics of a transition kind.
+    #[inline]
+    pub fn stats(&self, transition: Transition) -> TransitionStats {
+        self.stats[transition as usize].get()
+    }
+
+    /// Returns the execution budget of a transition kind (if any).
+    #[inline]
+    pub fn budget(&self, transition: Transition) -> Option<u64> {
+        self.budgets[transition as usize]
+    }
+
+    /// Clears all the execution statistics.
+    pub fn reset(&mut self) {
+        self.stats
+            .iter()
+            .for_each(|stats| stats.set(TransitionStats::EMPTY));
+    }
+
+    /// Measures the execution of `f` and accounts it to `transition`.
+    fn measure<R>(&self, transition: Transition, f: impl FnOnce() -> R) -> R {
+        let start = self.counter.now();
+        let result = f();
+        let elapsed = self.counter.now().wrapping_sub(start);
+
+        let cell = &self.stats[transition as usize];
+        let mut stats = cell.get();
+        stats.count += 1;
+        stats.min = stats.min.min(elapsed);
+        stats.max = stats.max.max(elapsed);
+        stats.total = stats.total.saturating_add(elapsed);
+        if let Some(budget) = self.budgets[transition as usize] {
+            if elapsed > budget {
+                stats.overruns += 1;
+                if let Some(callback) = &self.on_overrun {
+                    callback(&Overrun {
+                        transition,
+                        elapsed,
+                        budget,
+                    });
+                }
+            }
+        }
+        cell.set(stats);
+        result
+    }
+}
+
+/// Atomic model wrapper that profiles the execution time of the inner model.
+///
+/// Profiling is opt-in: only wrapped models pay for it. As [`Profiled`] dereferences to the
+/// inner model, a `Simulator<Profiled<T, C>>` still gives direct access to the state of `T`.
+/// It has the same kind as `T`, so it wraps both [`Atomic`] and [`TryAtomic`] models.
+pub struct Profiled<T, C, F = fn(&Overrun)> {
+    model: T,
+    profiler: Profiler<C, F>,
+}
+
+impl<T: Component, C: CycleCounter> Profiled<T, C> {
+    /// Wraps `model` to profile it with the given counter.
+    #[inline]
+    pub const fn new(model: T, counter: C) -> Self {
+        Self {
+            model,
+            profiler: Profiler::new(counter),
+        }
+    }
+}
+
+impl<T: Component, C: CycleCounter, F: Fn(&Overrun)> Profiled<T, C, F> {
+    /// Sets the execution budget of a transition kind, in counter units.
+    #[inline]
+    pub fn with_budget(mut self, transition: Transition, budget: u64) -> Self {
+        self.profiler.budgets[transition as usize] = Some(budget);
+        self
+    }
+
+    /// Sets the callback to call when a transition exceeds its budget.
+    ///
+    /// It replaces the previous callback (if any). The statistics and budgets are kept.
+    #[inline]
+    pub fn on_overrun<G: Fn(&Overrun)>(self, callback: G) -> Profiled<T, C, G> {
+        let Profiler {
+            counter,
+            stats,
+            budgets,
+            ..
+        } = self.profiler;
+        Profiled {
+            model: self.model,
+            profiler: Profiler {
+                counter,
+                stats,
+                budgets,
+                on_overrun: Some(callback),
+            },
+        }
+    }
+
+    /// Returns the profiler of the model.
+    #[inline]
+    pub fn profiler(&self) -> &Profiler<C, F> {
+        &self.profiler
+    }
+
+    /// Returns a mutable reference to the profiler of the model.
+    #[inline]
+    pub fn profiler_mut(&mut self) -> &mut Profiler<C, F> {
+        &mut self.profiler
+    }
+
+    /// Returns the inner model.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.model
+    }
+}
+
+impl<T, C, F> Deref for Profiled<T, C, F> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.model
+    }
+}
+
+impl<T, C, F> DerefMut for Profiled<T, C, F> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.model
+    }
+}
+
+impl<T: Component, C: CycleCounter, F: Fn(&Overrun)> Component for Profiled<T, C, F> {
+    type Kind = T::Kind;
+    type Input = T::Input;
+    type Output = T::Output;
+}
+
+impl<T: Atomic, C: CycleCounter, F: Fn(&Overrun)> Atomic for Profiled<T, C, F> {
+    #[inline]
+    fn start(&mut self) {
+        self.model.start()
+    }
+
+    #[inline]
+    fn stop(&mut self) {
+        self.model.stop()
+    }
+
+    #[inline]
+    fn delta_int(&mut self) {
+        let model = &mut self.model;
+        self.profiler
+            .measure(Transition::DeltaInt, || model.delta_int())
+    }
+
+    #[inline]
+    fn delta_ext(&mut self, e: f64, x: &Self::Input) {
+        let model = &mut self.model;
+        self.profiler
+            .measure(Transition::DeltaExt, || model.delta_ext(e, x))
+    }
+
+    #[inline]
+    fn delta_conf(&mut self, x: &Self::Input) {
+        let model = &mut self.model;
+        self.profiler
+            .measure(Transition::DeltaConf, || model.delta_conf(x))
+    }
+
+    #[inline]
+    fn lambda(&self, output: &mut Self::Output) {
+        self.profiler
+            .measure(Transition::Lambda, || self.model.lambda(output))
+    }
+
+    #[inline]
+    fn ta(&self) -> f64 {
+        self.profiler.measure(Transition::Ta, || self.model.ta())
+    }
+}
+
+impl<T: TryAtomic, C: CycleCounter, F: Fn(&Overrun)> TryAtomic for Profiled<T, C, F> {
+    type Error = T::Error;
+
+    #[inline]
+    fn start(&mut self) {
+        self.model.start()
+    }
+
+    #[inline]
+    fn stop(&mut self) {
+        self.model.stop()
+    }
+
+    #[inline]
+    fn try_delta_int(&mut self) -> Result<(), Self::Error> {
+        let model = &mut self.model;
+        self.profiler
+            .measure(Transition::DeltaInt, || model.try_delta_int())
+    }
+
+    #[inline]
+    fn try_delta_ext(&mut self, e: f64, x: &Self::Input) -> Result<(), Self::Error> {
+        let model = &mut self.model;
+        self.profiler
+            .measure(Transition::DeltaExt, || model.try_delta_ext(e, x))
+    }
+
+    #[inline]
+    fn try_delta_conf(&mut self, x: &Self::Input) -> Result<(), Self::Error> {
+        let model = &mut self.model;
+        self.profiler
+            .measure(Transition::DeltaConf, || model.try_delta_conf(x))
+    }
+
+    #[inline]
+    fn try_lambda(&self, output: &mut Self::Output) -> Result<(), Self::Error> {
+        self.profiler
+            .measure(Transition::Lambda, || self.model.try_lambda(output))
+    }
+
+    #[inline]
+    fn ta(&self) -> f64 {
+        self.profiler.measure(Transition::Ta, || self.model.ta())
+    }
+}
+
+/// Resets the inner model. The execution statistics are kept (see [`Profiler::reset`]).
+impl<T: Reset, C, F> Reset for Profiled<T, C, F> {
+    #[inline]
+    fn reset(&mut self) {
+        self.model.reset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::AtomicKind,
+        port::Port,
+        simulation::{test_utils::TestTryAtomic, AbstractSimulator, Config, Simulable},
+    };
+    use core::{
+        cell::RefCell,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    /// Periodic model whose internal transition gets more expensive over time.
+    /// It advances a fake clock to emulate execution costs. Each test uses its own clock.
+    struct Costly {
+        clock: &'static AtomicU64,
+        calls: u64,
+    }
+
+    impl Costly {
+        fn new(clock: &'static AtomicU64) -> Self {
+            Self { clock, calls: 0 }
+        }
+
+        fn spend(&self, cycles: u64) {
+            self.clock.fetch_add(cycles, Ordering::Relaxed);
+        }
+    }
+
+    impl Component for Costly {
+        type Kind = AtomicKind;
+        type Input = Port<usize, 1>;
+        type Output = Port<usize, 1>;
+    }
+
+    impl Atomic for Costly {
+        fn delta_int(&mut self) {
+            self.calls += 1;
+            self.spend(10 * self.calls);
+        }
+        fn delta_ext(&mut self, _e: f64, _x: &Self::Input) {
+            self.spend(7);
+        }
+        fn lambda(&self, output: &mut Self::Output) {
+            self.spend(3);
+            output.add_value(self.calls as usize).unwrap();
+        }
+        fn ta(&self) -> f64 {
+            1.0
+        }
+    }
+
+    impl Reset for Costly {
+        fn reset(&mut self) {
+            self.calls = 0;
+        }
+    }
+
+    #[test]
+    fn profiled_stats() {
+        static CLOCK: AtomicU64 = AtomicU64::new(0);
+        let model = Profiled::new(Costly::new(&CLOCK), || CLOCK.load(Ordering::Relaxed));
+        let mut sim = model.to_simulator();
+        sim.simulate_vt(&Config::new(0.0, 4.0, 1.0, None));
+
+        assert_eq!(sim.calls, 4, "direct access to the inner model");
+        let profiler = sim.profiler();
+        let delta_int = profiler.stats(Transition::DeltaInt);
+        assert_eq!(delta_int.count, 4);
+        assert_eq!(delta_int.min, 10);
+        assert_eq!(delta_int.max, 40);
+        assert_eq!(delta_int.total, 100);
+        assert_eq!(delta_int.mean(), Some(25));
+        assert_eq!(delta_int.overruns, 0, "no budget");
+
+        let lambda = profiler.stats(Transition::Lambda);
+        assert_eq!((lambda.count, lambda.min, lambda.max), (4, 3, 3));
+        let delta_ext = profiler.stats(Transition::DeltaExt);
+        assert_eq!(delta_ext, TransitionStats::default());
+        assert_eq!(delta_ext.mean(), None);
+        let ta = profiler.stats(Transition::Ta);
+        assert!(ta.count >= 4, "ta is called after every transition");
+        assert_eq!(ta.max, 0);
+
+        sim.profiler_mut().reset();
+        for transition in Transition::ALL {
+            assert_eq!(sim.profiler().stats(transition), TransitionStats::default());
+        }
+    }
+
+    #[test]
+    fn profiled_budget_overruns() {
+        static CLOCK: AtomicU64 = AtomicU64::new(0);
+        let overruns = RefCell::new(heapless::Vec::<Overrun, 8>::new());
+        let model = Profiled::new(Costly::new(&CLOCK), || CLOCK.load(Ordering::Relaxed))
+            .with_budget(Transition::DeltaInt, 25)
+            .with_budget(Transition::Lambda, 3)
+            .on_overrun(|overrun: &Overrun| overruns.borrow_mut().push(*overrun).unwrap());
+        assert_eq!(model.profiler().budget(Transition::DeltaInt), Some(25));
+        assert_eq!(model.profiler().budget(Transition::DeltaExt), None);
+        let mut sim = model.to_simulator();
+        sim.simulate_vt(&Config::new(0.0, 4.0, 1.0, None));
+
+        let expected = [30, 40].map(|elapsed| Overrun {
+            transition: Transition::DeltaInt,
+            elapsed,
+            budget: 25,
+        });
+        assert_eq!(overruns.borrow().as_slice(), &expected);
+        assert_eq!(sim.profiler().stats(Transition::DeltaInt).overruns, 2);
+        assert_eq!(
+            sim.profiler().stats(Transition::Lambda).overruns,
+            0,
+            "executions that take the whole budget are not overruns"
+        );
+    }
+
+    #[test]
+    fn profiled_dyn_overrun_callback() {
+        static CLOCK: AtomicU64 = AtomicU64::new(0);
+        static OVERRUNS: AtomicU64 = AtomicU64::new(0);
+        static CALLBACK: &(dyn Fn(&Overrun) + Sync) = &|_: &Overrun| {
+            OVERRUNS.fetch_add(1, Ordering::Relaxed);
+        };
+        let model = Profiled::new(Costly::new(&CLOCK), || CLOCK.load(Ordering::Relaxed))
+            .with_budget(Transition::DeltaInt, 0)
+            .on_overrun(CALLBACK);
+        let mut sim = model.to_simulator();
+        sim.simulate_vt(&Config::new(0.0, 2.0, 1.0, None));
+
+        assert_eq!(OVERRUNS.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn profiled_try_atomic() {
+        static CLOCK: AtomicU64 = AtomicU64::new(0);
+        let model = Profiled::new(TestTryAtomic::new(1.0, 3), || {
+            CLOCK.fetch_add(1, Ordering::Relaxed)
+        });
+        let mut sim = model.to_simulator();
+        let result = sim.try_simulate_vt(&Config::new(0.0, 10.0, 1.0, None));
+
+        assert!(result.is_err(), "errors are forwarded");
+        assert_eq!(sim.int_calls, 3);
+        let profiler = sim.profiler();
+        assert_eq!(profiler.stats(Transition::DeltaInt).count, 3);
+        assert_eq!(profiler.stats(Transition::Lambda).count, 3);
+        assert_eq!(profiler.stats(Transition::DeltaInt).max, 1);
+    }
+
+    #[test]
+    fn profiled_reset_keeps_stats() {
+        static CLOCK: AtomicU64 = AtomicU64::new(0);
+        let model = Profiled::new(Costly::new(&CLOCK), || CLOCK.load(Ordering::Relaxed));
+        let mut sim = model.to_simulator();
+        let config = Config::new(0.0, 2.0, 1.0, None);
+        sim.simulate_vt(&config);
+        sim.reset();
+        assert_eq!(sim.calls, 0, "inner model is reset");
+        sim.simulate_vt(&config);
+
+        assert_eq!(sim.calls, 2);
+        let delta_int = sim.profiler().stats(Transition::DeltaInt);
+        assert_eq!((delta_int.count, delta_int.max), (4, 20), "stats are kept");
+    }
+}
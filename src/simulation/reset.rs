@@ -0,0 +1,288 @@
+//! Resetting models and simulators to their initial state.
+//!
+//! Atomic models implement [`Reset`] to restore their initial state. Simulators implement it
+//! when all their models do: they reset their models, clear their bags, and forget their timing.
+//! Thus, the same simulator can run the same scenario several times without building it again.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use xdevs::{simulation::reset::Reset, AbstractSimulator, Simulable};
+//!
+//! let mut simulator = TopModel::build(/* ... */).to_simulator();
+//! for _ in 0..10 {
+//!     simulator.reset();
+//!     simulator.simulate_vt(&config);
+//! }
+//! ```
+
+use crate::component::{atomic::Atomic, AtomicKind, Component};
+use core::ops::{Deref, DerefMut};
+
+/// Interface for restoring the initial state of models and simulators.
+///
+/// Atomic models usually restore their initial state from their parameters
+/// (e.g., `*self = Self::default()`), or from a copy of it (see [`Initial`]).
+/// `#[coupled(reset)]` and `#[to_component(reset)]` models implement it by resetting all their
+/// components. Enum models keep their current variant.
+pub trait Reset {
+    /// Restores the initial state.
+    fn reset(&mut self);
+}
+
+/// Atomic model that keeps a copy of the initial state of the inner model to restore it.
+#[derive(Clone)]
+pub struct Initial<T> {
+    model: T,
+    initial: T,
+}
+
+impl<T: Clone> Initial<T> {
+    /// Creates a new model whose initial state is the current state of `model`.
+    pub fn new(model: T) -> Self {
+        Self {
+            initial: model.clone(),
+            model,
+        }
+    }
+}
+
+impl<T> Deref for Initial<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.model
+    }
+}
+
+impl<T> DerefMut for Initial<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.model
+    }
+}
+
+impl<T: Atomic> Component for Initial<T> {
+    type Kind = AtomicKind;
+    type Input = T::Input;
+    type Output = T::Output;
+}
+
+impl<T: Atomic> Atomic for Initial<T> {
+    #[inline(always)]
+    fn start(&mut self) {
+        self.model.start();
+    }
+
+    #[inline(always)]
+    fn stop(&mut self) {
+        self.model.stop();
+    }
+
+    #[inline(always)]
+    fn delta_int(&mut self) {
+        self.model.delta_int();
+    }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, elapsed: f64, input: &Self::Input) {
+        self.model.delta_ext(elapsed, input);
+    }
+
+    #[inline(always)]
+    fn delta_conf(&mut self, input: &Self::Input) {
+        self.model.delta_conf(input);
+    }
+
+    #[inline(always)]
+    fn lambda(&self, output: &mut Self::Output) {
+        self.model.lambda(output);
+    }
+
+    #[inline(always)]
+    fn ta(&self) -> f64 {
+        self.model.ta()
+    }
+}
+
+impl<T: Clone> Reset for Initial<T> {
+    #[inline]
+    fn reset(&mut self) {
+        self.model.clone_from(&self.initial);
+    }
+}
+
+impl<T: Reset> Reset for &mut T {
+    #[inline(always)]
+    fn reset(&mut self) {
+        T::reset(self);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Reset> Reset for alloc::boxed::Box<T> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        T::reset(self);
+    }
+}
+
+impl<T: Reset, const N: usize> Reset for [T; N] {
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.iter_mut().for_each(T::reset);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Reset> Reset for alloc::vec::Vec<T> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        self.iter_mut().for_each(T::reset);
+    }
+}
+
+impl<T: Reset> Reset for Option<T> {
+    #[inline(always)]
+    fn reset(&mut self) {
+        if let Some(inner) = self {
+            inner.reset();
+        }
+    }
+}
+
+macro_rules! impl_reset_for_tuple {
+    ($($idx:tt => $T:ident),+) => {
+        impl<$($T: Reset),+> Reset for ($($T,)+) {
+            #[inline(always)]
+            fn reset(&mut self) {
+                $(self.$idx.reset();)+
+            }
+        }
+    }
+}
+
+impl_reset_for_tuple!(0 => T0);
+impl_reset_for_tuple!(0 => T0, 1 => T1);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7, 8 => T8);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7, 8 => T8, 9 => T9);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7, 8 => T8, 9 => T9, 10 => T10);
+impl_reset_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7, 8 => T8, 9 => T9, 10 => T10, 11 => T11);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        component::{coupled::PartialCoupled, CoupledKind},
+        simulation::{
+            coordinator::Coordinator, simulator::TrySimulator, test_utils::TestTryAtomic,
+            AbstractSimulator, Config, Simulable,
+        },
+        Coupled,
+    };
+
+    /// Periodic atomic model that counts its internal transitions.
+    #[derive(Clone)]
+    struct Counter {
+        period: f64,
+        count: usize,
+    }
+
+    impl Component for Counter {
+        type Kind = AtomicKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Atomic for Counter {
+        fn delta_int(&mut self) {
+            self.count += 1;
+        }
+        fn delta_ext(&mut self, _elapsed: f64, _input: &Self::Input) {}
+        fn lambda(&self, _output: &mut Self::Output) {}
+        fn ta(&self) -> f64 {
+            self.period
+        }
+    }
+
+    impl Reset for Counter {
+        fn reset(&mut self) {
+            self.count = 0;
+        }
+    }
+
+    impl Reset for TestTryAtomic {
+        fn reset(&mut self) {
+            self.int_calls = 0;
+            self.ext_calls = 0;
+        }
+    }
+
+    #[crate::coupled(reset)]
+    struct Counters {
+        counter: Counter,
+        initial: Initial<Counter>,
+        optional: Option<Counter>,
+    }
+
+    impl Component for Counters {
+        type Kind = CoupledKind;
+        type Input = ();
+        type Output = ();
+    }
+
+    impl Coupled for Counters {}
+
+    fn counts(coord: &Coordinator<Counters>) -> [usize; 3] {
+        let components = <Counters as PartialCoupled>::get_components(coord);
+        [
+            components.counter.count,
+            components.initial.count,
+            components.optional.as_ref().unwrap().count,
+        ]
+    }
+
+    #[test]
+    fn reset_repeats_simulation() {
+        let counter = |period| Counter { period, count: 0 };
+        let initial = Initial::new(Counter {
+            period: 2.0,
+            count: 1,
+        });
+        let model = Counters::build(counter(1.0), initial, Some(counter(5.0)));
+        let mut coord = model.to_simulator();
+        let config = Config::new(0.0, 10.0, 1.0, None);
+
+        coord.simulate_vt(&config);
+        assert_eq!(counts(&coord), [10, 6, 2]);
+        coord.simulate_vt(&config);
+        assert_eq!(counts(&coord), [20, 11, 4], "state is kept between runs");
+
+        coord.reset();
+        assert_eq!(counts(&coord), [0, 1, 0], "initial state is restored");
+        assert_eq!(coord.t_next(), f64::INFINITY);
+        coord.simulate_vt(&config);
+        assert_eq!(counts(&coord), [10, 6, 2]);
+    }
+
+    #[test]
+    fn reset_recovers_failed_simulator() {
+        let mut simulator = TrySimulator::new(TestTryAtomic::new(1.0, 2));
+        let config = Config::new(0.0, 10.0, 1.0, None);
+        assert!(simulator.try_simulate_vt(&config).is_err());
+        assert!(simulator.is_failed());
+
+        simulator.reset();
+        assert!(!simulator.is_failed());
+        assert!(simulator.take_error().is_none());
+        let error = simulator.try_simulate_vt(&config).unwrap_err();
+        assert_eq!(error.message(), "Overflow(2)", "same failure after reset");
+    }
+}
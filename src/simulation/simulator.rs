@@ -1,11 +1,15 @@
 use crate::{
-    component::{atomic::Atomic, AtomicKind},
+    component::{
+        atomic::{Atomic, TryAtomic},
+        AtomicKind, TryAtomicKind,
+    },
     port::Bag,
-    simulation::{AbstractSimulator, Simulable},
+    simulation::{error::ModelError, reset::Reset, AbstractSimulator, Simulable},
 };
 use core::ops::{Deref, DerefMut};
 
 /// Processor that wraps a DEVS component and implements the logic for simulating it.
+#[derive(Clone)]
 pub struct Simulator<T: Atomic> {
     component: T,
     t_last: f64,
@@ -98,12 +102,216 @@ unsafe impl<T: Atomic> AbstractSimulator for Simulator<T> {
         self.t_next = t_next;
         t_next
     }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        if !input.is_empty() {
+            let e = t - self.t_last;
+            self.component.delta_ext(e, input);
+            input.clear();
+            self.t_last = t;
+            self.t_next = t + self.component.ta();
+        }
+        self.t_next
+    }
+
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        self.t_next
+    }
+}
+
+/// Processor that wraps a fallible DEVS component and implements the logic for simulating it.
+///
+/// When a transition or output function fails, the component becomes passive for the rest of the
+/// simulation, and the error is kept until the parent model takes it.
+pub struct TrySimulator<T: TryAtomic> {
+    component: T,
+    t_last: f64,
+    t_next: f64,
+    failed: bool,
+    error: Option<T::Error>,
+}
+
+impl<T: TryAtomic> TrySimulator<T> {
+    /// Creates a new processor for the given component.
+    #[inline(always)]
+    pub const fn new(component: T) -> Self {
+        Self {
+            component,
+            t_last: f64::INFINITY,
+            t_next: f64::INFINITY,
+            failed: false,
+            error: None,
+        }
+    }
+
+    /// Returns `true` if the component failed during the simulation.
+    #[inline(always)]
+    pub fn is_failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Passivates the component and stores the error.
+    #[inline]
+    fn fail(&mut self, error: T::Error) -> f64 {
+        self.failed = true;
+        self.error = Some(error);
+        self.t_next = f64::INFINITY;
+        f64::INFINITY
+    }
+}
+
+impl<T: TryAtomic> Deref for TrySimulator<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.component
+    }
+}
+
+impl<T: TryAtomic> DerefMut for TrySimulator<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.component
+    }
+}
+
+impl<T: Atomic + Reset> Reset for Simulator<T> {
+    #[inline]
+    fn reset(&mut self) {
+        self.component.reset();
+        self.t_last = f64::INFINITY;
+        self.t_next = f64::INFINITY;
+    }
+}
+
+// Fallible atomic models can be simulated using a `TrySimulator` struct
+impl<T: TryAtomic> Simulable<TryAtomicKind> for T {
+    type Simulator = TrySimulator<T>;
+
+    fn to_simulator(self) -> Self::Simulator {
+        TrySimulator::new(self)
+    }
+}
+
+/// Resetting a failed simulator also discards its error.
+impl<T: TryAtomic + Reset> Reset for TrySimulator<T> {
+    #[inline]
+    fn reset(&mut self) {
+        self.component.reset();
+        self.t_last = f64::INFINITY;
+        self.t_next = f64::INFINITY;
+        self.failed = false;
+        self.error = None;
+    }
+}
+
+unsafe impl<T: TryAtomic> AbstractSimulator for TrySimulator<T> {
+    type Input = T::Input;
+
+    type Output = T::Output;
+
+    #[inline(always)]
+    fn start(&mut self, t_start: f64) -> f64 {
+        self.t_last = t_start;
+        self.component.start();
+        let t_next = t_start + self.component.ta();
+        self.t_next = t_next;
+        t_next
+    }
+
+    #[inline(always)]
+    fn stop(&mut self) {
+        self.component.stop();
+    }
+
+    #[inline(always)]
+    fn lambda(&mut self, output: &mut Self::Output, t: f64) {
+        if t >= self.t_next {
+            if let Err(error) = self.component.try_lambda(output) {
+                output.clear();
+                self.fail(error);
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn delta(&mut self, input: &mut Self::Input, output: &mut Self::Output, t: f64) -> f64 {
+        if self.failed {
+            input.clear();
+            output.clear();
+            return f64::INFINITY;
+        }
+        let t_next = self.t_next;
+        let result = if !input.is_empty() {
+            let result = if t >= t_next {
+                output.clear();
+                self.component.try_delta_conf(input)
+            } else {
+                let e = t - self.t_last;
+                self.component.try_delta_ext(e, input)
+            };
+            input.clear();
+            result
+        } else if t >= t_next {
+            output.clear();
+            self.component.try_delta_int()
+        } else {
+            return t_next;
+        };
+        if let Err(error) = result {
+            return self.fail(error);
+        }
+        let t_next = t + self.component.ta();
+        self.t_last = t;
+        self.t_next = t_next;
+        t_next
+    }
+
+    #[inline(always)]
+    fn delta_ext(&mut self, input: &mut Self::Input, t: f64) -> f64 {
+        if self.failed {
+            input.clear();
+            return f64::INFINITY;
+        }
+        if !input.is_empty() {
+            let e = t - self.t_last;
+            let result = self.component.try_delta_ext(e, input);
+            input.clear();
+            if let Err(error) = result {
+                return self.fail(error);
+            }
+            self.t_last = t;
+            self.t_next = t + self.component.ta();
+        }
+        self.t_next
+    }
+
+    #[inline(always)]
+    fn t_next(&self) -> f64 {
+        self.t_next
+    }
+
+    #[inline(always)]
+    fn has_error(&self) -> bool {
+        self.error.is_some()
+    }
+
+    #[inline(always)]
+    fn take_error(&mut self) -> Option<ModelError> {
+        self.error.take().map(|error| ModelError::new(&error))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{port::Port, simulation::test_utils::TestAtomic};
+    use crate::{
+        port::Port,
+        simulation::test_utils::{TestAtomic, TestTryAtomic},
+    };
 
     #[test]
     fn start_sets_timing() {
@@ -203,4 +411,31 @@ mod tests {
         assert_eq!(sim.t_last, 0.0, "t_last = t");
         assert_eq!(sim.t_next, 3.0, "t_next = t + ta() (= period)");
     }
+
+    #[test]
+    fn try_delta_failure_passivates() {
+        let mut sim = TrySimulator::new(TestTryAtomic::new(1.0, 2));
+        assert_eq!(sim.start(0.0), 1.0);
+        assert_eq!(sim.delta(&mut Port::new(), &mut Port::new(), 1.0), 2.0);
+        assert!(!sim.has_error());
+
+        let mut output = Port::<usize, 1>::new();
+        sim.lambda(&mut output, 2.0);
+        assert_eq!(output.get_values(), &[2]);
+        let t_next = sim.delta(&mut Port::new(), &mut output, 2.0);
+        assert_eq!(t_next, f64::INFINITY, "failed component becomes passive");
+        assert!(sim.is_failed() && sim.has_error());
+
+        let error = sim.take_error().unwrap();
+        assert_eq!(error.message(), "Overflow(2)");
+        assert!(error.path().is_empty());
+        assert!(sim.is_failed() && !sim.has_error(), "error taken only once");
+
+        // Failed components ignore further inputs
+        let mut input = Port::<usize, 1>::new();
+        input.add_value(1).unwrap();
+        assert_eq!(sim.delta(&mut input, &mut output, 3.0), f64::INFINITY);
+        assert!(input.is_empty());
+        assert_eq!(sim.component.ext_calls, 0);
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     port::Bag,
-    simulation::{AsyncInput, Config},
+    simulation::{AsyncInput, Config, Pacer, Pacing},
 };
 use std::{
     thread,
@@ -40,12 +40,15 @@ pub fn wait_event<T: Bag>(
     config: &Config,
     mut input_handler: impl FnMut(Duration, &mut T),
 ) -> impl FnMut(f64, f64, &mut T) -> f64 {
-    let (time_scale, max_jitter) = (config.time_scale, config.max_jitter);
+    let time_scale = config.time_scale;
+    let mut pacer = Pacer::new(config);
     let mut last_rt = SystemTime::now();
-    let start_rt = last_rt;
 
     move |t_from, t_until, binput: &mut T| -> f64 {
-        let next_rt = last_rt + Duration::from_secs_f64((t_until - t_from) * time_scale);
+        let Some((t_from, last)) = pacer.schedule(t_from, t_until, last_rt, SystemTime::now) else {
+            return t_until;
+        };
+        let next_rt = last + Duration::from_secs_f64((t_until - t_from) * time_scale);
 
         if let Ok(duration) = next_rt.duration_since(SystemTime::now()) {
             input_handler(duration, binput);
@@ -56,7 +59,7 @@ pub fn wait_event<T: Bag>(
         match t.duration_since(next_rt) {
             Ok(duration) => {
                 // t >= next_rt, check for the jitter
-                if let Some(max_jitter) = max_jitter {
+                if let Some(max_jitter) = pacer.max_jitter() {
                     if duration > max_jitter {
                         panic!("[WE]>> Jitter too high: {:?}", duration);
                     }
@@ -66,19 +69,187 @@ pub fn wait_event<T: Bag>(
             }
             Err(_) => {
                 // t < next_rt
+                let duration = t.duration_since(last).unwrap_or_default();
                 last_rt = t;
-                let duration = last_rt.duration_since(start_rt).unwrap();
-                duration.as_secs_f64() / time_scale
+                t_from + duration.as_secs_f64() / time_scale
             }
         }
     }
 }
 
+/// Returns a [`Pacing::FastForward`] mode for a simulation that started at the wall-clock `anchor`.
+///
+/// If `anchor` is in the future, the simulation runs live from the beginning.
+pub fn fast_forward_since(anchor: SystemTime) -> Pacing {
+    Pacing::FastForward(SystemTime::now().duration_since(anchor).unwrap_or_default())
+}
+
+/// Wall-clock accuracy achieved by a [`SpinSleep`] strategy.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Accuracy {
+    /// Number of waits.
+    pub samples: u64,
+    /// Accumulated delay with respect to the deadlines.
+    pub total_delay: Duration,
+    /// Maximum delay with respect to a deadline.
+    pub max_delay: Duration,
+}
+
+impl Accuracy {
+    /// Returns the mean delay with respect to the deadlines.
+    pub fn mean_delay(&self) -> Duration {
+        match self.samples {
+            0 => Duration::ZERO,
+            n => self.total_delay.div_f64(n as f64),
+        }
+    }
+
+    fn record(&mut self, delay: Duration) {
+        self.samples += 1;
+        self.total_delay += delay;
+        self.max_delay = self.max_delay.max(delay);
+    }
+}
+
+/// Hybrid wait strategy for sub-millisecond accuracy.
+///
+/// [`thread::sleep`] usually overshoots by tens to hundreds of microseconds. This strategy sleeps
+/// until `threshold` before the deadline, and then spins (or yields) until the deadline.
+#[derive(Debug, Clone)]
+pub struct SpinSleep {
+    threshold: Duration,
+    yielding: bool,
+    accuracy: Accuracy,
+}
+
+impl SpinSleep {
+    /// Default threshold for switching from sleeping to spinning.
+    pub const DEFAULT_THRESHOLD: Duration = Duration::from_millis(1);
+
+    /// Creates a new strategy that spins during the last `threshold` before each deadline.
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            yielding: false,
+            accuracy: Accuracy::default(),
+        }
+    }
+
+    /// Yields the thread instead of spinning during the final stretch.
+    /// It is less accurate, but friendlier to other threads.
+    pub fn yielding(mut self) -> Self {
+        self.yielding = true;
+        self
+    }
+
+    /// Returns the accuracy achieved so far.
+    pub fn accuracy(&self) -> &Accuracy {
+        &self.accuracy
+    }
+
+    /// Blocks the current thread until `deadline`.
+    pub fn sleep_until(&mut self, deadline: Instant) {
+        if let Some(coarse) = deadline
+            .checked_duration_since(Instant::now())
+            .and_then(|remaining| remaining.checked_sub(self.threshold))
+        {
+            thread::sleep(coarse);
+        }
+        self.spin_until(deadline);
+    }
+
+    fn spin_until(&mut self, deadline: Instant) {
+        let mut now = Instant::now();
+        while now < deadline {
+            match self.yielding {
+                true => thread::yield_now(),
+                false => std::hint::spin_loop(),
+            }
+            now = Instant::now();
+        }
+        self.accuracy.record(now - deadline);
+    }
+}
+
+impl Default for SpinSleep {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_THRESHOLD)
+    }
+}
+
+/// Closure for RT simulation on targets with `std`.
+/// It waits until the next state transition using a [`SpinSleep`] strategy.
+///
+/// See [`wait_event`] for more details.
+pub fn spin_sleep<'a, T: Bag + 'a>(
+    config: &Config,
+    strategy: &'a mut SpinSleep,
+) -> impl FnMut(f64, f64, &mut T) -> f64 + 'a {
+    wait_event(config, move |waiting_period, _| {
+        strategy.sleep_until(Instant::now() + waiting_period)
+    })
+}
+
+/// Asynchronous input handler that waits until the next state transition of the model
+/// using a [`SpinSleep`] strategy. The coarse sleep does not block the executor.
+#[derive(Debug, Default)]
+pub struct SpinSleepAsync<T: Bag> {
+    strategy: SpinSleep,
+    last_rt: Option<Instant>,
+    /// The pacing state, created on the first call.
+    pacer: Option<Pacer>,
+    input: core::marker::PhantomData<T>,
+}
+
+impl<T: Bag> SpinSleepAsync<T> {
+    /// Creates a new `SpinSleepAsync` instance with the given strategy.
+    pub fn new(strategy: SpinSleep) -> Self {
+        Self {
+            strategy,
+            last_rt: None,
+            pacer: None,
+            input: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns the accuracy achieved so far.
+    pub fn accuracy(&self) -> &Accuracy {
+        self.strategy.accuracy()
+    }
+}
+
+impl<T: Bag> AsyncInput for SpinSleepAsync<T> {
+    type Input = T;
+
+    async fn handle(
+        &mut self,
+        config: &Config,
+        t_from: f64,
+        t_until: f64,
+        _input: &mut Self::Input,
+    ) -> f64 {
+        let pacer = self.pacer.get_or_insert_with(|| Pacer::new(config));
+        let last_rt = self.last_rt.unwrap_or_else(Instant::now);
+        let Some((t_from, last_rt)) = pacer.schedule(t_from, t_until, last_rt, Instant::now) else {
+            return t_until;
+        };
+        let next_rt = last_rt + Duration::from_secs_f64((t_until - t_from) * config.time_scale);
+        if let Some(coarse) = next_rt.checked_sub(self.strategy.threshold) {
+            tokio::time::sleep_until(coarse.into()).await;
+        }
+        self.strategy.spin_until(next_rt);
+        self.last_rt = Some(next_rt);
+        t_until
+    }
+}
+
 /// A simple asynchronous input handler that sleeps until the next state transition of the model.
 #[derive(Default)]
 pub struct SleepAsync<T: Bag> {
     /// The last recorded real time instant.
     last_rt: Option<Instant>,
+    /// The pacing state, created on the first call.
+    pacer: Option<Pacer>,
     /// Phantom data to associate with the input bag type.
     input: core::marker::PhantomData<T>,
 }
@@ -88,6 +259,7 @@ impl<T: Bag> SleepAsync<T> {
     pub fn new() -> Self {
         Self {
             last_rt: None,
+            pacer: None,
             input: core::marker::PhantomData,
         }
     }
@@ -103,10 +275,158 @@ impl<T: Bag> AsyncInput for SleepAsync<T> {
         t_until: f64,
         _input: &mut Self::Input,
     ) -> f64 {
+        let pacer = self.pacer.get_or_insert_with(|| Pacer::new(config));
         let last_rt = self.last_rt.unwrap_or_else(Instant::now);
+        let Some((t_from, last_rt)) = pacer.schedule(t_from, t_until, last_rt, Instant::now) else {
+            return t_until;
+        };
         let next_rt = last_rt + Duration::from_secs_f64((t_until - t_from) * config.time_scale);
         tokio::time::sleep_until(next_rt.into()).await;
         self.last_rt = Some(next_rt);
         t_until
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{test_utils::TestAtomic, AbstractSimulator, Simulable};
+
+    #[test]
+    fn spin_sleep_accuracy() {
+        let mut strategy = SpinSleep::default();
+        for _ in 0..5 {
+            let deadline = Instant::now() + Duration::from_millis(3);
+            strategy.sleep_until(deadline);
+            assert!(Instant::now() >= deadline);
+        }
+        // Delays depend on the load of the host, so only their consistency is checked
+        let accuracy = strategy.accuracy();
+        assert_eq!(accuracy.samples, 5);
+        assert!(accuracy.mean_delay() <= accuracy.max_delay);
+
+        // Past deadlines return immediately
+        let mut strategy = SpinSleep::new(Duration::ZERO).yielding();
+        strategy.sleep_until(Instant::now() - Duration::from_millis(1));
+        assert_eq!(strategy.accuracy().samples, 1);
+    }
+
+    #[test]
+    fn spin_sleep_simulate_rt() {
+        let mut sim = TestAtomic::periodic(1.0, 1.0).to_simulator();
+        let config = Config::new(0.0, 10.0, 0.002, None);
+        let mut strategy = SpinSleep::default();
+        sim.simulate_rt(&config, spin_sleep(&config, &mut strategy), |_| {});
+        assert_eq!(sim.int_calls, 10);
+        // Deadlines that already passed (e.g., on a loaded host) are not waited for
+        assert!((1..=10).contains(&strategy.accuracy().samples));
+    }
+
+    #[tokio::test]
+    async fn spin_sleep_async() {
+        let mut sim = TestAtomic::periodic(1.0, 1.0).to_simulator();
+        let config = Config::new(0.0, 10.0, 0.002, None);
+        let mut input = SpinSleepAsync::new(SpinSleep::default());
+        sim.simulate_rt_async(&config, &mut input, |_| {}).await;
+        assert_eq!(sim.int_calls, 10);
+        assert_eq!(input.accuracy().samples, 10);
+    }
+
+    #[tokio::test]
+    async fn spin_sleep_async_fast_forward() {
+        let mut sim = TestAtomic::periodic(1.0, 1.0).to_simulator();
+        // History of 5 time units: the first 5 transitions are replayed without waiting
+        let config = Config::new(0.0, 10.0, 0.002, None)
+            .with_pacing(Pacing::FastForward(Duration::from_millis(10)));
+        let mut input = SpinSleepAsync::new(SpinSleep::default());
+        sim.simulate_rt_async(&config, &mut input, |_| {}).await;
+        assert_eq!(sim.int_calls, 10);
+        assert_eq!(input.accuracy().samples, 5, "only the live part is paced");
+    }
+
+    #[test]
+    fn wait_event_fast_forward() {
+        let mut sim = TestAtomic::periodic(1.0, 1.0).to_simulator();
+        // History of 5 time units: the first 5 transitions are replayed without waiting
+        let config = Config::new(0.0, 10.0, 0.01, None)
+            .with_pacing(Pacing::FastForward(Duration::from_millis(50)));
+        let mut waits = 0;
+        sim.simulate_rt(
+            &config,
+            wait_event(&config, |duration, _| {
+                waits += 1;
+                thread::sleep(duration)
+            }),
+            |_| {},
+        );
+        assert_eq!(sim.int_calls, 10);
+        // Deadlines that already passed (e.g., on a loaded host) are not waited for
+        assert!((1..=5).contains(&waits), "only the live part is paced");
+    }
+
+    #[test]
+    #[should_panic(expected = "Jitter too high")]
+    fn wait_event_strict_panics_when_late() {
+        let mut sim = TestAtomic::periodic(1.0, 1.0).to_simulator();
+        let config = Config::new(0.0, 5.0, 0.01, Some(Duration::from_millis(5)));
+        sim.simulate_rt(&config, sleep(&config), |_| {
+            thread::sleep(Duration::from_millis(30))
+        });
+    }
+
+    #[test]
+    fn wait_event_catch_up() {
+        let mut sim = TestAtomic::periodic(1.0, 1.0).to_simulator();
+        let config = Config::new(0.0, 10.0, 0.01, Some(Duration::from_millis(5)))
+            .with_pacing(Pacing::CatchUp);
+        let mut late = true;
+        let mut waits = 0;
+        let start = Instant::now();
+        sim.simulate_rt(
+            &config,
+            wait_event(&config, |duration, _| {
+                waits += 1;
+                thread::sleep(duration)
+            }),
+            |_| {
+                if std::mem::take(&mut late) {
+                    thread::sleep(Duration::from_millis(30))
+                }
+            },
+        );
+        assert_eq!(sim.int_calls, 10);
+        // Late transitions do not shift the schedule: transitions at t = 2 and 3 do not wait
+        assert!(waits <= 8);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn fast_forward_since_anchor() {
+        let anchor = SystemTime::now() - Duration::from_secs(2);
+        match fast_forward_since(anchor) {
+            Pacing::FastForward(behind) => assert!(behind >= Duration::from_secs(2)),
+            pacing => panic!("unexpected pacing {pacing:?}"),
+        }
+        let future = SystemTime::now() + Duration::from_secs(2);
+        assert_eq!(
+            fast_forward_since(future),
+            Pacing::FastForward(Duration::ZERO)
+        );
+    }
+
+    #[tokio::test]
+    async fn sleep_async_fast_forward() {
+        let mut sim = TestAtomic::periodic(1.0, 1.0).to_simulator();
+        // History of 90 time units: only the last 10 time units (100 ms) are paced
+        let config = Config::new(0.0, 100.0, 0.01, None)
+            .with_pacing(Pacing::FastForward(Duration::from_millis(900)));
+        let start = Instant::now();
+        sim.simulate_rt_async(&config, SleepAsync::new(), |_| {})
+            .await;
+        assert_eq!(sim.int_calls, 100);
+        assert!(
+            start.elapsed() >= Duration::from_millis(100),
+            "live part is paced"
+        );
+    }
+}
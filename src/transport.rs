@@ -0,0 +1,73 @@
+//! Transports that connect the ports of an [`RtEngine`](crate::rt_engine::RtEngine) to the outside world.
+//!
+//! Events are exchanged as [`BagMux::Mux`](crate::port::BagMux::Mux) values.
+//! A [`Codec`] translates them to bytes and back. By default, the [`wire`](crate::wire) format is used.
+
+use crate::wire::{WireCodec, WireError};
+use core::future::Future;
+
+#[cfg(feature = "std")]
+pub mod socket;
+pub mod stream;
+
+/// Sending half of an [`RtEngine`](crate::rt_engine::RtEngine) input channel.
+///
+/// It is implemented by the [`Sender`](crate::export::Sender) of every backend.
+pub trait EventSink<I> {
+    /// Sends an input event to the engine. It returns `false` if the engine is gone.
+    fn send_event(&self, event: I) -> impl Future<Output = bool>;
+}
+
+/// Receiving half of an [`RtEngine`](crate::rt_engine::RtEngine) output channel.
+///
+/// It is implemented by the [`Receiver`](crate::export::Receiver) of every backend.
+pub trait EventSource<O> {
+    /// Waits for the next output event of the engine. It returns `None` if the engine is gone.
+    ///
+    /// Events lost due to lagging are silently skipped.
+    fn recv_event(&mut self) -> impl Future<Output = Option<O>>;
+}
+
+/// Interface for translating events to bytes and back.
+pub trait Codec<T> {
+    /// Encodes `value` into `buf`. It returns the number of bytes written.
+    fn encode(&self, value: &T, buf: &mut [u8]) -> Result<usize, WireError>;
+
+    /// Decodes a value from the whole `buf`.
+    fn decode(&self, buf: &[u8]) -> Result<T, WireError>;
+}
+
+/// Default codec. It uses the [`wire`](crate::wire) format of the events.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WireFormat;
+
+impl<T: WireCodec> Codec<T> for WireFormat {
+    #[inline]
+    fn encode(&self, value: &T, buf: &mut [u8]) -> Result<usize, WireError> {
+        value.encode(buf)
+    }
+
+    #[inline]
+    fn decode(&self, buf: &[u8]) -> Result<T, WireError> {
+        let (value, n) = T::decode(buf)?;
+        if n != buf.len() {
+            return Err(WireError::InvalidValue);
+        }
+        Ok(value)
+    }
+}
+
+/// Polls two futures concurrently and returns the output of the first one that completes.
+pub(crate) async fn select<T>(a: impl Future<Output = T>, b: impl Future<Output = T>) -> T {
+    use core::{pin::pin, task::Poll};
+
+    let mut a = pin!(a);
+    let mut b = pin!(b);
+    core::future::poll_fn(|cx| {
+        if let Poll::Ready(res) = a.as_mut().poll(cx) {
+            return Poll::Ready(res);
+        }
+        b.as_mut().poll(cx)
+    })
+    .await
+}
@@ -0,0 +1,736 @@
+//! TCP and UDP transports for [`RtEngine`](crate::rt_engine::RtEngine) on targets with `std`.
+//!
+//! Both transports receive input events from remote peers and forward them to the engine's
+//! [`Sender`], and publish every output event received from the engine's [`Receiver`] to all
+//! the connected peers. Frames that cannot be decoded are discarded.
+
+use super::{Codec, WireFormat};
+use crate::export::{Receiver, RecvError, Sender};
+use std::{
+    io,
+    net::SocketAddr,
+    time::{Duration, Instant},
+    vec,
+    vec::Vec,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream, ToSocketAddrs, UdpSocket,
+    },
+    task::{AbortHandle, JoinHandle},
+};
+
+/// Default maximum length (in bytes) of an encoded event.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 512;
+
+/// Default maximum number of peers that a [`UdpTransport`] learns from input events.
+pub const DEFAULT_MAX_PEERS: usize = 16;
+
+/// Policy for reconnecting a [`TcpTransport`] client when the connection fails.
+///
+/// The waiting period starts at `initial_delay` and doubles after every failed attempt,
+/// up to `max_delay`. If `max_retries` is `Some(n)`, the transport gives up after `n` failed attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Waiting period before the first reconnection attempt.
+    pub initial_delay: Duration,
+    /// Maximum waiting period between reconnection attempts.
+    pub max_delay: Duration,
+    /// Maximum number of consecutive failed attempts. If `None`, it retries forever.
+    pub max_retries: Option<usize>,
+}
+
+impl ReconnectPolicy {
+    /// The transport does not try to reconnect.
+    pub const fn never() -> Self {
+        Self {
+            initial_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+            max_retries: Some(0),
+        }
+    }
+
+    /// The transport retries forever, waiting `delay` between attempts.
+    pub const fn fixed(delay: Duration) -> Self {
+        Self {
+            initial_delay: delay,
+            max_delay: delay,
+            max_retries: None,
+        }
+    }
+
+    /// The transport retries forever with an exponential backoff from `initial_delay` to `max_delay`.
+    pub const fn backoff(initial_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            initial_delay,
+            max_delay,
+            max_retries: None,
+        }
+    }
+
+    /// Limits the number of consecutive failed attempts.
+    pub const fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    /// Returns the waiting period before the given attempt (starting at 0), or `None` if the transport must give up.
+    pub fn delay(&self, attempt: usize) -> Option<Duration> {
+        if let Some(max_retries) = self.max_retries {
+            if attempt >= max_retries {
+                return None;
+            }
+        }
+        let factor = 1_u32.checked_shl(attempt as u32).unwrap_or(u32::MAX);
+        let delay = self.initial_delay.saturating_mul(factor);
+        Some(delay.min(self.max_delay))
+    }
+}
+
+impl Default for ReconnectPolicy {
+    /// Default policy retries forever with an exponential backoff from 100 ms to 5 s.
+    fn default() -> Self {
+        Self::backoff(Duration::from_millis(100), Duration::from_secs(5))
+    }
+}
+
+/// UDP transport. Each datagram contains exactly one event.
+///
+/// Output events are sent to the peers registered with [`UdpTransport::with_peer`]
+/// and to the peers that have sent an input event to the transport.
+/// The transport keeps up to [`DEFAULT_MAX_PEERS`] of the latter (see [`UdpTransport::with_max_peers`]):
+/// when it is full, the peer that has been idle for the longest time is forgotten.
+/// Optionally, peers are also forgotten when they have been idle for a given time (see [`UdpTransport::with_peer_timeout`]).
+pub struct UdpTransport<C = WireFormat> {
+    socket: UdpSocket,
+    peers: Peers,
+    codec: C,
+    max_frame_len: usize,
+}
+
+impl UdpTransport<WireFormat> {
+    /// Creates a new UDP transport bound to the given address.
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            socket: UdpSocket::bind(addr).await?,
+            peers: Peers::new(),
+            codec: WireFormat,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        })
+    }
+}
+
+impl<C> UdpTransport<C> {
+    /// Replaces the codec of the transport.
+    pub fn with_codec<D>(self, codec: D) -> UdpTransport<D> {
+        UdpTransport {
+            socket: self.socket,
+            peers: self.peers,
+            codec,
+            max_frame_len: self.max_frame_len,
+        }
+    }
+
+    /// Registers a peer that receives output events.
+    /// Registered peers are never forgotten.
+    pub fn with_peer(mut self, peer: SocketAddr) -> Self {
+        self.peers.register(peer);
+        self
+    }
+
+    /// Sets the maximum number of peers learned from input events.
+    /// If it is zero, output events are only sent to registered peers.
+    pub fn with_max_peers(mut self, max_peers: usize) -> Self {
+        self.peers.max_learned = max_peers;
+        self.peers.learned.truncate(max_peers);
+        self
+    }
+
+    /// Forgets the peers learned from input events after they have been idle for `timeout`.
+    pub fn with_peer_timeout(mut self, timeout: Duration) -> Self {
+        self.peers.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum length (in bytes) of an encoded event.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Returns the local address of the transport.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Runs the transport until the engine drops its channels.
+    pub async fn run<I, O>(mut self, sender: Sender<I>, mut receiver: Receiver<O>) -> io::Result<()>
+    where
+        C: Codec<I> + Codec<O>,
+        O: Clone,
+    {
+        let mut rx_buf = vec![0; self.max_frame_len];
+        let mut tx_buf = vec![0; self.max_frame_len];
+        loop {
+            tokio::select! {
+                res = self.socket.recv_from(&mut rx_buf) => {
+                    let (n, peer) = match res {
+                        Ok(res) => res,
+                        // Some platforms report unreachable peers when receiving
+                        Err(err) if is_peer_error(&err) => continue,
+                        Err(err) => return Err(err),
+                    };
+                    self.peers.heard(peer, Instant::now());
+                    if let Ok(event) = Codec::<I>::decode(&self.codec, &rx_buf[..n]) {
+                        if sender.send(event).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+                res = receiver.recv() => match res {
+                    Ok(event) => {
+                        let Ok(n) = self.codec.encode(&event, &mut tx_buf) else {
+                            continue;
+                        };
+                        self.peers.expire(Instant::now());
+                        for peer in self.peers.iter() {
+                            match self.socket.send_to(&tx_buf[..n], peer).await {
+                                Ok(_) => {}
+                                Err(err) if is_peer_error(&err) => {}
+                                Err(err) => return Err(err),
+                            }
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return Ok(()),
+                },
+            }
+        }
+    }
+}
+
+/// Peers of a [`UdpTransport`].
+struct Peers {
+    /// Peers registered by the user.
+    registered: Vec<SocketAddr>,
+    /// Peers learned from input events, with the last time they sent one.
+    learned: Vec<(SocketAddr, Instant)>,
+    /// Maximum number of learned peers.
+    max_learned: usize,
+    /// Idle time after which learned peers are forgotten.
+    timeout: Option<Duration>,
+}
+
+impl Peers {
+    fn new() -> Self {
+        Self {
+            registered: Vec::new(),
+            learned: Vec::new(),
+            max_learned: DEFAULT_MAX_PEERS,
+            timeout: None,
+        }
+    }
+
+    fn register(&mut self, peer: SocketAddr) {
+        if !self.registered.contains(&peer) {
+            self.registered.push(peer);
+        }
+        self.learned.retain(|(learned, _)| *learned != peer);
+    }
+
+    /// Records that `peer` sent an input event at `now`.
+    /// If there is no room for a new peer, the peer that has been idle for the longest time is replaced.
+    fn heard(&mut self, peer: SocketAddr, now: Instant) {
+        if self.registered.contains(&peer) {
+            return;
+        }
+        if let Some((_, last)) = self.learned.iter_mut().find(|(p, _)| *p == peer) {
+            *last = now;
+        } else if self.learned.len() < self.max_learned {
+            self.learned.push((peer, now));
+        } else if let Some(oldest) = self.learned.iter_mut().min_by_key(|(_, last)| *last) {
+            *oldest = (peer, now);
+        }
+    }
+
+    /// Forgets the learned peers that have been idle for longer than the timeout.
+    fn expire(&mut self, now: Instant) {
+        if let Some(timeout) = self.timeout {
+            self.learned
+                .retain(|(_, last)| now.saturating_duration_since(*last) <= timeout);
+        }
+    }
+
+    /// Iterates over the peers that receive output events.
+    fn iter(&self) -> impl Iterator<Item = &SocketAddr> {
+        let learned = self.learned.iter().map(|(peer, _)| peer);
+        self.registered.iter().chain(learned)
+    }
+}
+
+/// Returns `true` if the error is caused by a remote peer and the transport can keep running.
+fn is_peer_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused | io::ErrorKind::ConnectionReset
+    )
+}
+
+/// Role of a [`TcpTransport`].
+enum TcpRole {
+    /// Accepts any number of incoming connections.
+    Listen(TcpListener),
+    /// Connects to a remote peer, reconnecting according to the policy.
+    Connect(SocketAddr, ReconnectPolicy),
+}
+
+/// Reason why a reader task finished.
+enum ReaderEnd {
+    /// The peer closed the connection or sent an invalid frame.
+    Disconnected,
+    /// The engine dropped its input channel.
+    EngineClosed,
+}
+
+/// TCP transport. Events are framed with a little-endian `u16` length prefix.
+pub struct TcpTransport<C = WireFormat> {
+    role: TcpRole,
+    codec: C,
+    max_frame_len: usize,
+}
+
+impl TcpTransport<WireFormat> {
+    /// Creates a new TCP transport that accepts connections on the given address.
+    pub async fn listen(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            role: TcpRole::Listen(TcpListener::bind(addr).await?),
+            codec: WireFormat,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        })
+    }
+
+    /// Creates a new TCP transport that connects to the given address.
+    pub fn connect(addr: SocketAddr, policy: ReconnectPolicy) -> Self {
+        Self {
+            role: TcpRole::Connect(addr, policy),
+            codec: WireFormat,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+}
+
+impl<C> TcpTransport<C> {
+    /// Replaces the codec of the transport.
+    pub fn with_codec<D>(self, codec: D) -> TcpTransport<D> {
+        TcpTransport {
+            role: self.role,
+            codec,
+            max_frame_len: self.max_frame_len,
+        }
+    }
+
+    /// Sets the maximum length (in bytes) of an encoded event. It cannot exceed [`u16::MAX`].
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len.min(u16::MAX as usize);
+        self
+    }
+
+    /// Returns the local address of a listening transport.
+    pub fn local_addr(&self) -> Option<io::Result<SocketAddr>> {
+        match &self.role {
+            TcpRole::Listen(listener) => Some(listener.local_addr()),
+            TcpRole::Connect(..) => None,
+        }
+    }
+
+    /// Runs the transport until the engine drops its channels.
+    ///
+    /// A connecting transport returns an error when its [`ReconnectPolicy`] gives up.
+    pub async fn run<I, O>(self, sender: Sender<I>, mut receiver: Receiver<O>) -> io::Result<()>
+    where
+        C: Codec<I> + Codec<O> + Clone + Send + 'static,
+        I: Send + 'static,
+        O: Clone,
+    {
+        let Self {
+            role,
+            codec,
+            max_frame_len,
+        } = self;
+        match role {
+            TcpRole::Listen(listener) => {
+                serve(listener, codec, max_frame_len, sender, &mut receiver).await
+            }
+            TcpRole::Connect(addr, policy) => {
+                let mut attempt = 0;
+                loop {
+                    let err = match TcpStream::connect(addr).await {
+                        Ok(stream) => {
+                            attempt = 0;
+                            let (reader, writer) = stream.into_split();
+                            let reader =
+                                spawn_reader(reader, codec.clone(), max_frame_len, sender.clone());
+                            let closed = forward_outputs(
+                                writer,
+                                reader,
+                                &codec,
+                                max_frame_len,
+                                &mut receiver,
+                            )
+                            .await;
+                            if closed {
+                                return Ok(());
+                            }
+                            io::Error::from(io::ErrorKind::ConnectionAborted)
+                        }
+                        Err(err) => err,
+                    };
+                    match policy.delay(attempt) {
+                        Some(delay) => {
+                            attempt += 1;
+                            tokio::time::sleep(delay).await;
+                        }
+                        None => return Err(err),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Accepts connections and publishes output events to all of them.
+async fn serve<C, I, O>(
+    listener: TcpListener,
+    codec: C,
+    max_frame_len: usize,
+    sender: Sender<I>,
+    receiver: &mut Receiver<O>,
+) -> io::Result<()>
+where
+    C: Codec<I> + Codec<O> + Clone + Send + 'static,
+    I: Send + 'static,
+    O: Clone,
+{
+    let mut tx_buf = vec![0; max_frame_len + 2];
+    let mut writers: Vec<(OwnedWriteHalf, AbortHandle)> = Vec::new();
+    let res = loop {
+        tokio::select! {
+            res = listener.accept() => {
+                let (stream, _) = match res {
+                    Ok(res) => res,
+                    Err(err) if is_peer_error(&err) => continue,
+                    Err(err) => break Err(err),
+                };
+                let (reader, writer) = stream.into_split();
+                let reader = spawn_reader(reader, codec.clone(), max_frame_len, sender.clone());
+                writers.push((writer, reader.abort_handle()));
+            }
+            res = receiver.recv() => match res {
+                Ok(event) => {
+                    let Some(frame) = encode_frame(&codec, &event, &mut tx_buf) else {
+                        continue;
+                    };
+                    let mut i = 0;
+                    while i < writers.len() {
+                        if writers[i].0.write_all(frame).await.is_ok() {
+                            i += 1;
+                        } else {
+                            writers.swap_remove(i).1.abort();
+                        }
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break Ok(()),
+            },
+        }
+    };
+    for (_, reader) in writers {
+        reader.abort();
+    }
+    res
+}
+
+/// Publishes output events to a single connection.
+///
+/// It returns `true` if the engine dropped its output channel, or `false` if the connection was lost.
+async fn forward_outputs<C: Codec<O>, O: Clone>(
+    mut writer: OwnedWriteHalf,
+    mut reader: JoinHandle<ReaderEnd>,
+    codec: &C,
+    max_frame_len: usize,
+    receiver: &mut Receiver<O>,
+) -> bool {
+    let mut tx_buf = vec![0; max_frame_len + 2];
+    let closed = loop {
+        tokio::select! {
+            end = &mut reader => break matches!(end, Ok(ReaderEnd::EngineClosed)),
+            res = receiver.recv() => match res {
+                Ok(event) => {
+                    let Some(frame) = encode_frame(codec, &event, &mut tx_buf) else {
+                        continue;
+                    };
+                    if writer.write_all(frame).await.is_err() {
+                        break false;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => break true,
+            },
+        }
+    };
+    reader.abort();
+    closed
+}
+
+/// Encodes a length-prefixed frame. It returns `None` if the event cannot be encoded.
+fn encode_frame<'a, C: Codec<O>, O>(codec: &C, event: &O, buf: &'a mut [u8]) -> Option<&'a [u8]> {
+    let n = codec.encode(event, &mut buf[2..]).ok()?;
+    let len = u16::try_from(n).ok()?;
+    buf[..2].copy_from_slice(&len.to_le_bytes());
+    Some(&buf[..n + 2])
+}
+
+/// Spawns a task that reads frames from a connection and forwards them to the engine.
+fn spawn_reader<C, I>(
+    mut reader: OwnedReadHalf,
+    codec: C,
+    max_frame_len: usize,
+    sender: Sender<I>,
+) -> JoinHandle<ReaderEnd>
+where
+    C: Codec<I> + Send + 'static,
+    I: Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut rx_buf = vec![0; max_frame_len];
+        loop {
+            let mut len = [0; 2];
+            if reader.read_exact(&mut len).await.is_err() {
+                return ReaderEnd::Disconnected;
+            }
+            let len = u16::from_le_bytes(len) as usize;
+            if len > max_frame_len {
+                return ReaderEnd::Disconnected;
+            }
+            if reader.read_exact(&mut rx_buf[..len]).await.is_err() {
+                return ReaderEnd::Disconnected;
+            }
+            if let Ok(event) = codec.decode(&rx_buf[..len]) {
+                if sender.send(event).await.is_err() {
+                    return ReaderEnd::EngineClosed;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        export::{InputChannel, OutputChannel},
+        rt_engine::{RtEngineInputChannel, RtEngineOutputChannel},
+        wire::WireCodec,
+    };
+
+    fn frame(value: u32) -> Vec<u8> {
+        let mut buf = vec![0; 6];
+        buf[..2].copy_from_slice(&4_u16.to_le_bytes());
+        value.encode(&mut buf[2..]).unwrap();
+        buf
+    }
+
+    #[test]
+    fn reconnect_policy_delays() {
+        let policy = ReconnectPolicy::backoff(Duration::from_millis(10), Duration::from_millis(50))
+            .with_max_retries(4);
+        assert_eq!(policy.delay(0), Some(Duration::from_millis(10)));
+        assert_eq!(policy.delay(1), Some(Duration::from_millis(20)));
+        assert_eq!(policy.delay(2), Some(Duration::from_millis(40)));
+        assert_eq!(policy.delay(3), Some(Duration::from_millis(50)), "capped");
+        assert_eq!(policy.delay(4), None, "gives up after max_retries");
+
+        assert_eq!(ReconnectPolicy::never().delay(0), None);
+        let fixed = ReconnectPolicy::fixed(Duration::from_millis(5));
+        assert_eq!(fixed.delay(100), Some(Duration::from_millis(5)));
+    }
+
+    #[tokio::test]
+    async fn udp_roundtrip() {
+        let mut input = InputChannel::<u32, 4>::new();
+        let output = OutputChannel::<u32, 4>::new();
+
+        let transport = UdpTransport::bind("127.0.0.1:0").await.unwrap();
+        let addr = transport.local_addr().unwrap();
+        let task = tokio::spawn(transport.run(input.sender(), output.receiver().unwrap()));
+
+        let peer = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        peer.send_to(&7_u32.to_le_bytes(), addr).await.unwrap();
+        assert_eq!(input.recv().await, 7, "datagram forwarded to the engine");
+
+        output.publish(42);
+        let mut buf = [0; 16];
+        let (n, from) = peer.recv_from(&mut buf).await.unwrap();
+        assert_eq!(from, addr);
+        assert_eq!(
+            u32::decode(&buf[..n]).unwrap(),
+            (42, 4),
+            "output sent to peer"
+        );
+
+        task.abort();
+    }
+
+    #[test]
+    fn udp_peers_are_capped() {
+        let addr = |port| SocketAddr::from(([127, 0, 0, 1], port));
+        let t0 = Instant::now();
+        let mut peers = Peers::new();
+        peers.max_learned = 2;
+        peers.register(addr(1));
+
+        peers.heard(addr(1), t0);
+        peers.heard(addr(2), t0);
+        peers.heard(addr(3), t0 + Duration::from_secs(1));
+        peers.heard(addr(2), t0 + Duration::from_secs(2));
+        peers.heard(addr(4), t0 + Duration::from_secs(3));
+        assert_eq!(
+            peers.iter().copied().collect::<Vec<_>>(),
+            [addr(1), addr(2), addr(4)],
+            "registered peer is kept, and the longest idle peer is replaced"
+        );
+    }
+
+    #[test]
+    fn udp_idle_peers_expire() {
+        let addr = |port| SocketAddr::from(([127, 0, 0, 1], port));
+        let t0 = Instant::now();
+        let mut peers = Peers::new();
+        peers.timeout = Some(Duration::from_secs(5));
+        peers.register(addr(1));
+
+        peers.heard(addr(2), t0);
+        peers.heard(addr(3), t0 + Duration::from_secs(4));
+        peers.expire(t0 + Duration::from_secs(6));
+        assert_eq!(
+            peers.iter().copied().collect::<Vec<_>>(),
+            [addr(1), addr(3)],
+            "only the idle learned peer is forgotten"
+        );
+    }
+
+    #[tokio::test]
+    async fn udp_sends_to_latest_peers() {
+        let mut input = InputChannel::<u32, 4>::new();
+        let output = OutputChannel::<u32, 4>::new();
+
+        let transport = UdpTransport::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .with_max_peers(1);
+        let addr = transport.local_addr().unwrap();
+        let task = tokio::spawn(transport.run(input.sender(), output.receiver().unwrap()));
+
+        let old_peer = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        old_peer.send_to(&1_u32.to_le_bytes(), addr).await.unwrap();
+        assert_eq!(input.recv().await, 1);
+        let new_peer = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        new_peer.send_to(&2_u32.to_le_bytes(), addr).await.unwrap();
+        assert_eq!(input.recv().await, 2);
+
+        output.publish(42);
+        let mut buf = [0; 16];
+        let (n, _) = new_peer.recv_from(&mut buf).await.unwrap();
+        assert_eq!(u32::decode(&buf[..n]).unwrap(), (42, 4));
+        assert!(
+            old_peer.try_recv_from(&mut buf).is_err(),
+            "forgotten peer does not receive output events"
+        );
+
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn udp_discards_invalid_datagrams() {
+        let mut input = InputChannel::<u32, 4>::new();
+        let output = OutputChannel::<u32, 4>::new();
+
+        let transport = UdpTransport::bind("127.0.0.1:0").await.unwrap();
+        let addr = transport.local_addr().unwrap();
+        let task = tokio::spawn(transport.run(input.sender(), output.receiver().unwrap()));
+
+        let peer = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        peer.send_to(&[1, 2, 3], addr).await.unwrap();
+        peer.send_to(&9_u32.to_le_bytes(), addr).await.unwrap();
+        assert_eq!(input.recv().await, 9, "short datagram is discarded");
+
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn tcp_listen_roundtrip() {
+        let mut input = InputChannel::<u32, 4>::new();
+        let output = OutputChannel::<u32, 4>::new();
+
+        let transport = TcpTransport::listen("127.0.0.1:0").await.unwrap();
+        let addr = transport.local_addr().unwrap().unwrap();
+        let task = tokio::spawn(transport.run(input.sender(), output.receiver().unwrap()));
+
+        let mut peer = TcpStream::connect(addr).await.unwrap();
+        peer.write_all(&frame(7)).await.unwrap();
+        assert_eq!(input.recv().await, 7, "frame forwarded to the engine");
+
+        output.publish(42);
+        let mut buf = [0; 6];
+        peer.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), frame(42), "output sent to peer");
+
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn tcp_connect_reconnects() {
+        let mut input = InputChannel::<u32, 4>::new();
+        let output = OutputChannel::<u32, 4>::new();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let policy = ReconnectPolicy::fixed(Duration::from_millis(10));
+        let transport = TcpTransport::connect(addr, policy);
+        let task = tokio::spawn(transport.run(input.sender(), output.receiver().unwrap()));
+
+        let (mut peer, _) = listener.accept().await.unwrap();
+        peer.write_all(&frame(1)).await.unwrap();
+        assert_eq!(input.recv().await, 1);
+        drop(peer);
+
+        let (mut peer, _) = listener.accept().await.unwrap();
+        peer.write_all(&frame(2)).await.unwrap();
+        assert_eq!(input.recv().await, 2, "transport reconnected");
+
+        output.publish(3);
+        let mut buf = [0; 6];
+        peer.read_exact(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), frame(3));
+
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn tcp_connect_gives_up() {
+        let input = InputChannel::<u32, 4>::new();
+        let output = OutputChannel::<u32, 4>::new();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let transport = TcpTransport::connect(addr, ReconnectPolicy::never());
+        let res = transport
+            .run(input.sender(), output.receiver().unwrap())
+            .await;
+        assert!(res.is_err(), "never policy returns the connection error");
+    }
+}
@@ -0,0 +1,658 @@
+//! Byte-stream transport for [`RtEngine`](crate::rt_engine::RtEngine) over [`embedded_io_async`] streams.
+//!
+//! It works with any [`Read`]/[`Write`] pair, such as the halves of a UART or a USB CDC-ACM class.
+//! Every event is sent as a frame: the encoded event followed by its CRC-16/CCITT-FALSE (little-endian),
+//! [COBS]-encoded and terminated by a `0x00` delimiter. Corrupted frames are discarded,
+//! and the receiver resynchronizes at the next delimiter.
+//!
+//! [COBS]: https://en.wikipedia.org/wiki/Consistent_Overhead_Byte_Stuffing
+
+use super::{select, Codec, EventSink, EventSource, WireFormat};
+use crate::wire::WireError;
+use embedded_io_async::{Error, ErrorKind, Read, Write};
+
+/// Default maximum length (in bytes) of a frame on the wire, including its delimiter.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 64;
+
+/// Byte that terminates every frame.
+const DELIMITER: u8 = 0;
+/// Length of the CRC that follows the payload of every frame.
+const CRC_LEN: usize = 2;
+/// Maximum number of bytes in a COBS block.
+const COBS_BLOCK_LEN: usize = 254;
+
+/// Errors that may occur when exchanging frames over a byte stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamError {
+    /// The underlying stream failed.
+    Io(ErrorKind),
+    /// The underlying stream reached its end.
+    Eof,
+    /// The frame does not fit in the buffer.
+    Overflow,
+    /// The frame is not properly COBS-encoded.
+    Framing,
+    /// The CRC of the frame does not match its content.
+    Crc,
+    /// The event could not be encoded or decoded.
+    Wire(WireError),
+}
+
+impl StreamError {
+    /// Returns `true` if the stream cannot be used anymore.
+    /// Otherwise, only the current frame is lost.
+    pub fn is_fatal(&self) -> bool {
+        matches!(self, Self::Io(_) | Self::Eof)
+    }
+}
+
+impl From<WireError> for StreamError {
+    fn from(err: WireError) -> Self {
+        Self::Wire(err)
+    }
+}
+
+/// Frame counters of a [`StreamTransport`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StreamStats {
+    /// Number of events received successfully.
+    pub rx_frames: usize,
+    /// Number of events sent successfully.
+    pub tx_frames: usize,
+    /// Number of received frames discarded due to a CRC mismatch.
+    pub crc_errors: usize,
+    /// Number of received frames discarded due to a bad encoding or an overflow.
+    pub framing_errors: usize,
+    /// Number of events that could not be encoded or decoded.
+    pub wire_errors: usize,
+}
+
+impl StreamStats {
+    fn record_rx<T>(&mut self, res: &Result<T, StreamError>) {
+        match res {
+            Ok(_) => self.rx_frames += 1,
+            Err(StreamError::Crc) => self.crc_errors += 1,
+            Err(StreamError::Framing | StreamError::Overflow) => self.framing_errors += 1,
+            Err(StreamError::Wire(_)) => self.wire_errors += 1,
+            Err(_) => {}
+        }
+    }
+
+    fn record_tx<T>(&mut self, res: &Result<T, StreamError>) {
+        match res {
+            Ok(_) => self.tx_frames += 1,
+            Err(StreamError::Wire(_) | StreamError::Overflow) => self.wire_errors += 1,
+            Err(_) => {}
+        }
+    }
+}
+
+/// Reads COBS-encoded frames from a byte stream.
+///
+/// `N` is the maximum length of a frame on the wire, including its delimiter.
+pub struct FrameReader<R, const N: usize = DEFAULT_MAX_FRAME_LEN> {
+    reader: R,
+    buf: [u8; N],
+    /// Number of valid bytes in `buf`.
+    len: usize,
+    /// Number of bytes of `buf` that belong to the last frame.
+    consumed: usize,
+    /// Number of bytes of `buf` already scanned for a delimiter.
+    scanned: usize,
+    /// Set after an overflow, until the next delimiter arrives.
+    discarding: bool,
+}
+
+impl<R: Read, const N: usize> FrameReader<R, N> {
+    /// Creates a new frame reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: [0; N],
+            len: 0,
+            consumed: 0,
+            scanned: 0,
+            discarding: false,
+        }
+    }
+
+    /// Returns the underlying reader. Any buffered data is lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Waits for the next frame and returns its payload (without the CRC).
+    ///
+    /// Empty frames are skipped, so senders may emit extra delimiters to help receivers resynchronize.
+    pub async fn read_frame(&mut self) -> Result<&[u8], StreamError> {
+        loop {
+            if self.consumed > 0 {
+                self.buf.copy_within(self.consumed..self.len, 0);
+                self.len -= self.consumed;
+                self.consumed = 0;
+                self.scanned = 0;
+            }
+            let pending = &self.buf[self.scanned..self.len];
+            if let Some(pos) = pending.iter().position(|&b| b == DELIMITER) {
+                let end = self.scanned + pos;
+                self.consumed = end + 1;
+                if core::mem::take(&mut self.discarding) {
+                    return Err(StreamError::Overflow);
+                }
+                if end == 0 {
+                    continue;
+                }
+                let n = cobs_decode(&mut self.buf[..end]).ok_or(StreamError::Framing)?;
+                if n < CRC_LEN {
+                    return Err(StreamError::Framing);
+                }
+                let (payload, crc) = self.buf[..n].split_at(n - CRC_LEN);
+                if crc16(payload).to_le_bytes() != crc {
+                    return Err(StreamError::Crc);
+                }
+                return Ok(&self.buf[..n - CRC_LEN]);
+            }
+            self.scanned = self.len;
+            if self.len == N {
+                // The frame does not fit: drop it and wait for the next delimiter
+                self.discarding = true;
+                self.len = 0;
+                self.scanned = 0;
+            }
+            let n = self
+                .reader
+                .read(&mut self.buf[self.len..])
+                .await
+                .map_err(|err| StreamError::Io(err.kind()))?;
+            if n == 0 {
+                return Err(StreamError::Eof);
+            }
+            self.len += n;
+        }
+    }
+}
+
+/// Writes COBS-encoded frames to a byte stream.
+///
+/// `N` is the maximum length of a frame on the wire, including its delimiter.
+pub struct FrameWriter<W, const N: usize = DEFAULT_MAX_FRAME_LEN> {
+    writer: W,
+    buf: [u8; N],
+}
+
+impl<W: Write, const N: usize> FrameWriter<W, N> {
+    /// Creates a new frame writer.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            buf: [0; N],
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes `payload` as a single frame.
+    pub async fn write_frame(&mut self, payload: &[u8]) -> Result<(), StreamError> {
+        self.write_with(|buf| {
+            let dst = buf.get_mut(..payload.len()).ok_or(StreamError::Overflow)?;
+            dst.copy_from_slice(payload);
+            Ok(payload.len())
+        })
+        .await
+    }
+
+    /// Writes a frame with the payload produced by `f`, which returns the length of the payload.
+    async fn write_with(
+        &mut self,
+        f: impl FnOnce(&mut [u8]) -> Result<usize, StreamError>,
+    ) -> Result<(), StreamError> {
+        let n = f(&mut self.buf[..N.saturating_sub(CRC_LEN)])?;
+        let crc = crc16(&self.buf[..n]);
+        self.buf[n..n + CRC_LEN].copy_from_slice(&crc.to_le_bytes());
+        let data = &self.buf[..n + CRC_LEN];
+        // COBS adds one byte every 254 bytes in the worst case
+        if data.len() + data.len() / COBS_BLOCK_LEN + 2 > N {
+            return Err(StreamError::Overflow);
+        }
+        write_cobs(&mut self.writer, data).await?;
+        write_all(&mut self.writer, &[DELIMITER]).await?;
+        self.writer
+            .flush()
+            .await
+            .map_err(|err| StreamError::Io(err.kind()))
+    }
+}
+
+/// Connects an [`RtEngine`](crate::rt_engine::RtEngine) to a byte stream.
+///
+/// Input events read from the stream are forwarded to the engine, and output events of the
+/// engine are written to the stream. By default, events are encoded with the [`wire`](crate::wire) format.
+/// `N` is the maximum length of a frame on the wire, including its delimiter.
+pub struct StreamTransport<R, W, C = WireFormat, const N: usize = DEFAULT_MAX_FRAME_LEN> {
+    reader: FrameReader<R, N>,
+    writer: FrameWriter<W, N>,
+    codec: C,
+    rx_stats: StreamStats,
+    tx_stats: StreamStats,
+}
+
+impl<R: Read, W: Write> StreamTransport<R, W> {
+    /// Creates a new transport from the reading and writing halves of a stream.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader: FrameReader::new(reader),
+            writer: FrameWriter::new(writer),
+            codec: WireFormat,
+            rx_stats: StreamStats::default(),
+            tx_stats: StreamStats::default(),
+        }
+    }
+}
+
+impl<R: Read, W: Write, C, const N: usize> StreamTransport<R, W, C, N> {
+    /// Replaces the codec used for encoding and decoding events.
+    pub fn with_codec<D>(self, codec: D) -> StreamTransport<R, W, D, N> {
+        StreamTransport {
+            reader: self.reader,
+            writer: self.writer,
+            codec,
+            rx_stats: self.rx_stats,
+            tx_stats: self.tx_stats,
+        }
+    }
+
+    /// Sets the maximum length of a frame on the wire, including its delimiter.
+    /// Any buffered data is lost.
+    pub fn with_max_frame_len<const M: usize>(self) -> StreamTransport<R, W, C, M> {
+        StreamTransport {
+            reader: FrameReader::new(self.reader.into_inner()),
+            writer: FrameWriter::new(self.writer.into_inner()),
+            codec: self.codec,
+            rx_stats: self.rx_stats,
+            tx_stats: self.tx_stats,
+        }
+    }
+
+    /// Returns the frame counters of the transport.
+    pub fn stats(&self) -> StreamStats {
+        StreamStats {
+            tx_frames: self.tx_stats.tx_frames,
+            wire_errors: self.rx_stats.wire_errors + self.tx_stats.wire_errors,
+            ..self.rx_stats
+        }
+    }
+
+    /// Returns the reading and writing halves of the stream.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader.into_inner(), self.writer.into_inner())
+    }
+
+    /// Waits for the next event from the stream.
+    pub async fn recv<I>(&mut self) -> Result<I, StreamError>
+    where
+        C: Codec<I>,
+    {
+        recv_event(&mut self.reader, &self.codec, &mut self.rx_stats).await
+    }
+
+    /// Writes an event to the stream.
+    pub async fn send<O>(&mut self, event: &O) -> Result<(), StreamError>
+    where
+        C: Codec<O>,
+    {
+        send_event(&mut self.writer, &self.codec, event, &mut self.tx_stats).await
+    }
+
+    /// Runs the transport until the engine drops its channels or the stream fails.
+    ///
+    /// Frames that cannot be received or sent are discarded and accounted in [`stats`](Self::stats).
+    pub async fn run<I, O>(
+        &mut self,
+        sender: impl EventSink<I>,
+        mut receiver: impl EventSource<O>,
+    ) -> Result<(), StreamError>
+    where
+        C: Codec<I> + Codec<O>,
+    {
+        let Self {
+            reader,
+            writer,
+            codec,
+            rx_stats,
+            tx_stats,
+        } = self;
+        let codec = &*codec;
+
+        let rx = async {
+            loop {
+                match recv_event(reader, codec, rx_stats).await {
+                    Ok(event) => {
+                        if !sender.send_event(event).await {
+                            return Ok(());
+                        }
+                    }
+                    Err(err) if err.is_fatal() => return Err(err),
+                    Err(_) => continue,
+                }
+            }
+        };
+        let tx = async {
+            while let Some(event) = receiver.recv_event().await {
+                match send_event(writer, codec, &event, tx_stats).await {
+                    Err(err) if err.is_fatal() => return Err(err),
+                    _ => continue,
+                }
+            }
+            Ok(())
+        };
+        select(rx, tx).await
+    }
+}
+
+async fn recv_event<R: Read, T, const N: usize>(
+    reader: &mut FrameReader<R, N>,
+    codec: &impl Codec<T>,
+    stats: &mut StreamStats,
+) -> Result<T, StreamError> {
+    let res = match reader.read_frame().await {
+        Ok(frame) => codec.decode(frame).map_err(StreamError::Wire),
+        Err(err) => Err(err),
+    };
+    stats.record_rx(&res);
+    res
+}
+
+async fn send_event<W: Write, T, const N: usize>(
+    writer: &mut FrameWriter<W, N>,
+    codec: &impl Codec<T>,
+    event: &T,
+    stats: &mut StreamStats,
+) -> Result<(), StreamError> {
+    let res = writer.write_with(|buf| Ok(codec.encode(event, buf)?)).await;
+    stats.record_tx(&res);
+    res
+}
+
+async fn write_all<W: Write>(writer: &mut W, data: &[u8]) -> Result<(), StreamError> {
+    writer
+        .write_all(data)
+        .await
+        .map_err(|err| StreamError::Io(err.kind()))
+}
+
+/// Writes the COBS encoding of `data` (without the delimiter).
+async fn write_cobs<W: Write>(writer: &mut W, mut data: &[u8]) -> Result<(), StreamError> {
+    loop {
+        let len = data
+            .iter()
+            .take(COBS_BLOCK_LEN)
+            .position(|&b| b == 0)
+            .unwrap_or(data.len().min(COBS_BLOCK_LEN));
+        write_all(writer, &[len as u8 + 1]).await?;
+        write_all(writer, &data[..len]).await?;
+        match data.get(len) {
+            // The zero is implied by the block, unless it is full
+            Some(0) if len < COBS_BLOCK_LEN => data = &data[len + 1..],
+            // Full block without zero
+            Some(_) => data = &data[len..],
+            None if len == COBS_BLOCK_LEN => data = &[],
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Decodes a COBS-encoded frame (without the delimiter) in place.
+/// It returns the length of the decoded data, or `None` if the frame is not valid.
+fn cobs_decode(buf: &mut [u8]) -> Option<usize> {
+    let (mut read, mut write) = (0, 0);
+    while read < buf.len() {
+        let code = buf[read] as usize;
+        if code == 0 || read + code > buf.len() {
+            return None;
+        }
+        buf.copy_within(read + 1..read + code, write);
+        read += code;
+        write += code - 1;
+        if code <= COBS_BLOCK_LEN && read < buf.len() {
+            buf[write] = 0;
+            write += 1;
+        }
+    }
+    Some(write)
+}
+
+/// Computes the CRC-16/CCITT-FALSE of `data`.
+fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0xFFFF_u16;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encodes `data` with COBS into a fixed buffer.
+    async fn cobs(data: &[u8]) -> ([u8; 1024], usize) {
+        let mut out = [0; 1024];
+        let mut writer = &mut out[..];
+        write_cobs(&mut writer, data).await.unwrap();
+        let n = 1024 - writer.len();
+        (out, n)
+    }
+
+    #[test]
+    fn crc16_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+        assert_eq!(crc16(&[]), 0xFFFF);
+    }
+
+    #[tokio::test]
+    async fn cobs_vectors() {
+        let vectors: [(&[u8], &[u8]); 6] = [
+            (&[], &[0x01]),
+            (&[0x00], &[0x01, 0x01]),
+            (&[0x00, 0x00], &[0x01, 0x01, 0x01]),
+            (&[0x11, 0x22, 0x00, 0x33], &[0x03, 0x11, 0x22, 0x02, 0x33]),
+            (&[0x11, 0x00, 0x00, 0x00], &[0x02, 0x11, 0x01, 0x01, 0x01]),
+            (&[0x11, 0x22, 0x33], &[0x04, 0x11, 0x22, 0x33]),
+        ];
+        for (data, expected) in vectors {
+            let (mut out, n) = cobs(data).await;
+            assert_eq!(&out[..n], expected, "encoding {data:?}");
+            let m = cobs_decode(&mut out[..n]).unwrap();
+            assert_eq!(&out[..m], data, "decoding {expected:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn cobs_long_blocks() {
+        for len in [253, 254, 255, 508, 600] {
+            let data: [u8; 600] = core::array::from_fn(|i| (i % 255) as u8 + 1);
+            let (mut out, n) = cobs(&data[..len]).await;
+            assert!(!out[..n].contains(&0), "no zeros in the encoding");
+            assert!(n <= len + len / COBS_BLOCK_LEN + 1);
+            let m = cobs_decode(&mut out[..n]).unwrap();
+            assert_eq!(&out[..m], &data[..len], "roundtrip of {len} bytes");
+        }
+    }
+
+    #[tokio::test]
+    async fn cobs_full_blocks_followed_by_zero() {
+        let mut data = [0x01; 509];
+        data[254] = 0x00;
+        // 254 non-zero bytes, a zero, and (optionally) more data
+        for (len, tail) in [(255, &[0x01, 0x01][..]), (256, &[0x01, 0x02, 0x01][..])] {
+            let (mut out, n) = cobs(&data[..len]).await;
+            assert_eq!(out[0], 0xFF, "full block");
+            assert_eq!(&out[1..255], &data[..254]);
+            assert_eq!(&out[255..n], tail, "the zero is encoded");
+            let m = cobs_decode(&mut out[..n]).unwrap();
+            assert_eq!(&out[..m], &data[..len], "roundtrip of {len} bytes");
+        }
+
+        // 508 non-zero bytes fill two blocks
+        let data = [0x01; 508];
+        let (mut out, n) = cobs(&data).await;
+        assert_eq!((out[0], out[255]), (0xFF, 0xFF), "two full blocks");
+        assert!(!out[..n].contains(&0), "no zeros in the encoding");
+        let m = cobs_decode(&mut out[..n]).unwrap();
+        assert_eq!(&out[..m], &data, "roundtrip of 508 bytes");
+    }
+
+    #[test]
+    fn cobs_invalid() {
+        assert_eq!(cobs_decode(&mut [0x03, 0x11]), None, "truncated block");
+        assert_eq!(cobs_decode(&mut [0x02, 0x11, 0x00]), None, "zero code");
+    }
+
+    #[tokio::test]
+    async fn frames_roundtrip() {
+        let mut wire = [0; 64];
+        let mut out = &mut wire[..];
+        let mut writer = FrameWriter::<_, 16>::new(&mut out);
+        writer.write_frame(&[1, 0, 2]).await.unwrap();
+        writer.write_frame(&[]).await.unwrap();
+        writer.write_frame(&[0; 8]).await.unwrap();
+        assert_eq!(
+            writer.write_frame(&[0; 14]).await,
+            Err(StreamError::Overflow),
+            "frame does not fit"
+        );
+        let n = 64 - out.len();
+
+        let mut reader = FrameReader::<_, 16>::new(&wire[..n]);
+        assert_eq!(reader.read_frame().await, Ok(&[1, 0, 2][..]));
+        assert_eq!(reader.read_frame().await, Ok(&[][..]));
+        assert_eq!(reader.read_frame().await, Ok(&[0; 8][..]));
+        assert_eq!(reader.read_frame().await, Err(StreamError::Eof));
+    }
+
+    #[tokio::test]
+    async fn frames_resynchronize() {
+        let mut wire = [0; 128];
+        let mut out = &mut wire[..];
+        let mut writer = FrameWriter::<_, 16>::new(&mut out);
+        writer.write_frame(&[1, 2, 3]).await.unwrap();
+        writer.write_frame(&[4, 5, 6]).await.unwrap();
+        writer.write_frame(&[7, 8, 9]).await.unwrap();
+        let n = 128 - out.len();
+
+        // Corrupt the payload of the first frame and prepend garbage that overflows the buffer
+        let mut stream = [0xAA; 128];
+        stream[19] = 0; // end of garbage
+        stream[20..20 + n].copy_from_slice(&wire[..n]);
+        stream[21] ^= 0x40;
+        let mut reader = FrameReader::<_, 16>::new(&stream[..20 + n]);
+        assert_eq!(reader.read_frame().await, Err(StreamError::Overflow));
+        assert_eq!(reader.read_frame().await, Err(StreamError::Crc));
+        assert_eq!(reader.read_frame().await, Ok(&[4, 5, 6][..]));
+        assert_eq!(reader.read_frame().await, Ok(&[7, 8, 9][..]));
+    }
+
+    #[tokio::test]
+    async fn transport_stats() {
+        let mut wire = [0; 128];
+        let mut out = &mut wire[..];
+        let mut writer = FrameWriter::<_, DEFAULT_MAX_FRAME_LEN>::new(&mut out);
+        writer.write_frame(&7_u32.to_le_bytes()).await.unwrap();
+        writer.write_frame(&[1, 2, 3]).await.unwrap();
+        let m = 128 - writer.into_inner().len();
+        let mut writer = FrameWriter::<_, DEFAULT_MAX_FRAME_LEN>::new(&mut out);
+        writer.write_frame(&[0x01, 0x01]).await.unwrap();
+        let mut n = 128 - out.len();
+        wire[m + 1] ^= 0x02; // corrupt the payload of the last frame
+        wire[n..n + 6].copy_from_slice(&[0x04, 0x09, 0x00, 0x00, 0x00, 0x00]);
+        n += 6;
+
+        let mut sink = [0; 64];
+        let mut transport = StreamTransport::new(&wire[..n], &mut sink[..]);
+        assert_eq!(transport.recv::<u32>().await, Ok(7));
+        assert!(matches!(
+            transport.recv::<u32>().await,
+            Err(StreamError::Wire(_))
+        ));
+        assert_eq!(transport.recv::<u32>().await, Err(StreamError::Crc));
+        assert_eq!(transport.recv::<u32>().await, Err(StreamError::Framing));
+        assert_eq!(transport.recv::<u32>().await, Err(StreamError::Eof));
+        transport.send(&42_u32).await.unwrap();
+
+        let stats = transport.stats();
+        assert_eq!(stats.rx_frames, 1);
+        assert_eq!(stats.tx_frames, 1);
+        assert_eq!(stats.crc_errors, 1);
+        assert_eq!(stats.framing_errors, 1);
+        assert_eq!(stats.wire_errors, 1);
+    }
+
+    #[cfg(feature = "std")]
+    mod pipe {
+        use super::*;
+        use crate::{
+            export::{InputChannel, OutputChannel},
+            rt_engine::{RtEngineInputChannel, RtEngineOutputChannel},
+        };
+        use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+        /// In-memory stream that implements the `embedded-io-async` traits.
+        struct Pipe<T>(T);
+
+        impl<T> embedded_io_async::ErrorType for Pipe<T> {
+            type Error = ErrorKind;
+        }
+
+        impl<T: AsyncRead + Unpin> Read for Pipe<T> {
+            async fn read(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+                self.0.read(buf).await.map_err(|_| ErrorKind::Other)
+            }
+        }
+
+        impl<T: AsyncWrite + Unpin> Write for Pipe<T> {
+            async fn write(&mut self, buf: &[u8]) -> Result<usize, ErrorKind> {
+                self.0.write(buf).await.map_err(|_| ErrorKind::Other)
+            }
+
+            async fn flush(&mut self) -> Result<(), ErrorKind> {
+                self.0.flush().await.map_err(|_| ErrorKind::Other)
+            }
+        }
+
+        #[tokio::test]
+        async fn run_over_duplex_pipe() {
+            let mut input = InputChannel::<u32, 4>::new();
+            let output = OutputChannel::<u32, 4>::new();
+
+            let (local, remote) = tokio::io::duplex(64);
+            let (local_rx, local_tx) = tokio::io::split(local);
+            let (remote_rx, remote_tx) = tokio::io::split(remote);
+            let sender = input.sender();
+            let receiver = output.receiver().unwrap();
+            let task = tokio::spawn(async move {
+                let mut transport = StreamTransport::new(Pipe(local_rx), Pipe(local_tx));
+                transport.run(sender, receiver).await
+            });
+
+            let mut remote = StreamTransport::new(Pipe(remote_rx), Pipe(remote_tx));
+            remote.send(&7_u32).await.unwrap();
+            assert_eq!(input.recv().await, 7, "event forwarded to the engine");
+
+            output.publish(42);
+            assert_eq!(remote.recv::<u32>().await, Ok(42), "output sent to peer");
+
+            drop(remote);
+            assert_eq!(task.await.unwrap(), Err(StreamError::Eof));
+        }
+    }
+}
@@ -0,0 +1,372 @@
+//! Compact binary wire format for [`BagMux`](crate::port::BagMux) events.
+//!
+//! Every event is encoded as a little-endian `u16` port tag followed by the payload of the port.
+//! Port tags are derived from the port name (see [`port_tag`]), so they do not change when
+//! fields are reordered or new ports are added. Tags can also be pinned manually with the
+//! `#[wire(tag = N)]` field attribute of the [`WireMux`](crate::WireMux) derive macro.
+//!
+//! Primitive types, arrays, tuples and [`Option`] implement [`WireCodec`] out of the box.
+//! User-defined payloads only need to implement [`WireCodec`] manually.
+
+/// Errors that may occur when encoding or decoding events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireError {
+    /// The buffer is too small to encode or decode the value.
+    BufferTooSmall,
+    /// The port tag does not correspond to any port of the bag.
+    UnknownTag(u16),
+    /// The buffer contains a value that is not valid for the target type.
+    InvalidValue,
+}
+
+/// Interface for values that can be sent through the wire.
+pub trait WireCodec: Sized {
+    /// Encodes the value into `buf`. It returns the number of bytes written.
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError>;
+
+    /// Decodes a value from `buf`. It returns the value and the number of bytes read.
+    fn decode(buf: &[u8]) -> Result<(Self, usize), WireError>;
+}
+
+/// Interface for [`BagMux::Mux`](crate::port::BagMux::Mux) enums that can be sent through the wire.
+///
+/// # Safety
+///
+/// This trait must be implemented via the [`WireMux`](crate::WireMux) macro. Do not implement it manually.
+pub unsafe trait WireMux: WireCodec {
+    /// Port names and their tags, in declaration order.
+    const PORT_TAGS: &'static [(&'static str, u16)];
+
+    /// Returns the tag of the port this event belongs to.
+    fn port_tag(&self) -> u16;
+}
+
+/// Computes the stable tag of a port from its name (16-bit folded FNV-1a hash).
+pub const fn port_tag(name: &str) -> u16 {
+    let bytes = name.as_bytes();
+    let mut hash: u32 = 0x811c_9dc5;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+        i += 1;
+    }
+    ((hash >> 16) ^ (hash & 0xffff)) as u16
+}
+
+/// Writes `bytes` at the beginning of `buf`.
+#[inline]
+fn write_bytes(buf: &mut [u8], bytes: &[u8]) -> Result<usize, WireError> {
+    let dst = buf
+        .get_mut(..bytes.len())
+        .ok_or(WireError::BufferTooSmall)?;
+    dst.copy_from_slice(bytes);
+    Ok(bytes.len())
+}
+
+/// Reads `N` bytes from the beginning of `buf`.
+#[inline]
+fn read_bytes<const N: usize>(buf: &[u8]) -> Result<[u8; N], WireError> {
+    let src = buf.get(..N).ok_or(WireError::BufferTooSmall)?;
+    let mut bytes = [0; N];
+    bytes.copy_from_slice(src);
+    Ok(bytes)
+}
+
+macro_rules! impl_wire_codec_for_num {
+    ($($T:ty),+) => {
+        $(
+            impl WireCodec for $T {
+                #[inline]
+                fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+                    write_bytes(buf, &self.to_le_bytes())
+                }
+
+                #[inline]
+                fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+                    let bytes = read_bytes::<{ core::mem::size_of::<$T>() }>(buf)?;
+                    Ok((<$T>::from_le_bytes(bytes), bytes.len()))
+                }
+            }
+        )+
+    }
+}
+
+impl_wire_codec_for_num!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+// Pointer-sized integers are sent as 32-bit values to keep the format target-independent.
+impl WireCodec for usize {
+    #[inline]
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        u32::try_from(*self)
+            .map_err(|_| WireError::InvalidValue)?
+            .encode(buf)
+    }
+
+    #[inline]
+    fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+        let (value, n) = u32::decode(buf)?;
+        let value = usize::try_from(value).map_err(|_| WireError::InvalidValue)?;
+        Ok((value, n))
+    }
+}
+
+impl WireCodec for isize {
+    #[inline]
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        i32::try_from(*self)
+            .map_err(|_| WireError::InvalidValue)?
+            .encode(buf)
+    }
+
+    #[inline]
+    fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+        let (value, n) = i32::decode(buf)?;
+        let value = isize::try_from(value).map_err(|_| WireError::InvalidValue)?;
+        Ok((value, n))
+    }
+}
+
+impl WireCodec for bool {
+    #[inline]
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        (*self as u8).encode(buf)
+    }
+
+    #[inline]
+    fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+        match u8::decode(buf)? {
+            (0, n) => Ok((false, n)),
+            (1, n) => Ok((true, n)),
+            _ => Err(WireError::InvalidValue),
+        }
+    }
+}
+
+impl WireCodec for char {
+    #[inline]
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        (*self as u32).encode(buf)
+    }
+
+    #[inline]
+    fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+        let (value, n) = u32::decode(buf)?;
+        let value = char::from_u32(value).ok_or(WireError::InvalidValue)?;
+        Ok((value, n))
+    }
+}
+
+impl WireCodec for () {
+    #[inline]
+    fn encode(&self, _buf: &mut [u8]) -> Result<usize, WireError> {
+        Ok(0)
+    }
+
+    #[inline]
+    fn decode(_buf: &[u8]) -> Result<(Self, usize), WireError> {
+        Ok(((), 0))
+    }
+}
+
+impl<T: WireCodec> WireCodec for Option<T> {
+    #[inline]
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        match self {
+            None => false.encode(buf),
+            Some(value) => {
+                let n = true.encode(buf)?;
+                Ok(n + value.encode(&mut buf[n..])?)
+            }
+        }
+    }
+
+    #[inline]
+    fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+        match bool::decode(buf)? {
+            (false, n) => Ok((None, n)),
+            (true, n) => {
+                let (value, m) = T::decode(&buf[n..])?;
+                Ok((Some(value), n + m))
+            }
+        }
+    }
+}
+
+impl<T: WireCodec, const N: usize> WireCodec for [T; N] {
+    #[inline]
+    fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+        let mut n = 0;
+        for value in self {
+            n += value.encode(&mut buf[n..])?;
+        }
+        Ok(n)
+    }
+
+    fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+        let mut n = 0;
+        let mut values = heapless::Vec::<T, N>::new();
+        for _ in 0..N {
+            let (value, m) = T::decode(&buf[n..])?;
+            n += m;
+            // We never push more than N values
+            let _ = values.push(value);
+        }
+        match values.into_array() {
+            Ok(values) => Ok((values, n)),
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+macro_rules! impl_wire_codec_for_tuple {
+    ($($idx:tt => $T:ident),+) => {
+        impl<$($T: WireCodec),+> WireCodec for ($($T,)+) {
+            #[inline]
+            fn encode(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+                let mut n = 0;
+                $(n += self.$idx.encode(&mut buf[n..])?;)+
+                Ok(n)
+            }
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn decode(buf: &[u8]) -> Result<(Self, usize), WireError> {
+                let mut n = 0;
+                $(
+                    let ($T, m) = $T::decode(&buf[n..])?;
+                    n += m;
+                )+
+                Ok((($($T,)+), n))
+            }
+        }
+    }
+}
+
+impl_wire_codec_for_tuple!(0 => T0);
+impl_wire_codec_for_tuple!(0 => T0, 1 => T1);
+impl_wire_codec_for_tuple!(0 => T0, 1 => T1, 2 => T2);
+impl_wire_codec_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3);
+impl_wire_codec_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4);
+impl_wire_codec_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::port::BagMux;
+
+    #[derive(crate::Bag, crate::BagMux, crate::WireMux)]
+    struct TestBag {
+        in_job: crate::Port<usize, 1>,
+        in_stop: crate::Port<bool, 1>,
+        #[wire(tag = 7)]
+        in_array: [crate::Port<(u8, f32), 1>; 2],
+    }
+
+    type TestMux = <TestBag as BagMux>::Mux;
+
+    fn roundtrip<T: WireCodec>(value: &T) -> T {
+        let mut buf = [0; 64];
+        let n = value.encode(&mut buf).unwrap();
+        let (decoded, m) = T::decode(&buf[..n]).unwrap();
+        assert_eq!(n, m, "decode consumes all the encoded bytes");
+        decoded
+    }
+
+    #[test]
+    fn port_tag_is_stable() {
+        assert_eq!(port_tag("in_job"), port_tag("in_job"));
+        assert_ne!(port_tag("in_job"), port_tag("in_stop"));
+    }
+
+    #[test]
+    fn primitives_roundtrip() {
+        assert_eq!(roundtrip(&0xdead_beef_u32), 0xdead_beef);
+        assert_eq!(roundtrip(&-42_i16), -42);
+        assert_eq!(roundtrip(&1.5_f64), 1.5);
+        assert_eq!(roundtrip(&123_usize), 123);
+        assert!(roundtrip(&true));
+        assert_eq!(roundtrip(&'x'), 'x');
+        assert_eq!(roundtrip(&Some(3_u8)), Some(3));
+        assert_eq!(roundtrip(&None::<u8>), None);
+        assert_eq!(roundtrip(&[1_u16, 2, 3]), [1, 2, 3]);
+        assert_eq!(roundtrip(&(4_usize, -1_i8)), (4, -1));
+    }
+
+    #[test]
+    fn primitives_are_little_endian() {
+        let mut buf = [0; 4];
+        assert_eq!(0x0403_0201_u32.encode(&mut buf), Ok(4));
+        assert_eq!(buf, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn encode_rejects_small_buffer() {
+        let mut buf = [0; 3];
+        assert_eq!(1_u32.encode(&mut buf), Err(WireError::BufferTooSmall));
+        assert_eq!(u32::decode(&buf), Err(WireError::BufferTooSmall));
+    }
+
+    #[test]
+    fn decode_rejects_invalid_values() {
+        assert_eq!(bool::decode(&[2]), Err(WireError::InvalidValue));
+        assert_eq!(
+            char::decode(&0xd800_u32.to_le_bytes()),
+            Err(WireError::InvalidValue)
+        );
+    }
+
+    #[test]
+    fn mux_roundtrip() {
+        let events = [
+            TestMux::InJob(5),
+            TestMux::InStop(true),
+            TestMux::InArray((1, (2, 0.5))),
+        ];
+        for event in events {
+            let tag = event.port_tag();
+            let decoded = roundtrip(&event);
+            assert_eq!(decoded.port_tag(), tag, "port tag is preserved");
+            match (event, decoded) {
+                (TestMux::InJob(a), TestMux::InJob(b)) => assert_eq!(a, b),
+                (TestMux::InStop(a), TestMux::InStop(b)) => assert_eq!(a, b),
+                (TestMux::InArray(a), TestMux::InArray(b)) => assert_eq!(a, b),
+                _ => panic!("decoded event belongs to another port"),
+            }
+        }
+    }
+
+    #[test]
+    fn mux_layout() {
+        let mut buf = [0; 16];
+        let n = TestMux::InJob(0x0102).encode(&mut buf).unwrap();
+        assert_eq!(n, 6, "2 bytes of tag + 4 bytes of usize");
+        assert_eq!(buf[..2], port_tag("in_job").to_le_bytes());
+        assert_eq!(buf[2..6], [2, 1, 0, 0]);
+
+        let n = TestMux::InArray((1, (9, 1.0))).encode(&mut buf).unwrap();
+        assert_eq!(n, 2 + 4 + 1 + 4);
+        assert_eq!(buf[..2], 7_u16.to_le_bytes(), "pinned tag is used");
+    }
+
+    #[test]
+    fn mux_port_tags() {
+        assert_eq!(
+            TestMux::PORT_TAGS,
+            &[
+                ("in_job", port_tag("in_job")),
+                ("in_stop", port_tag("in_stop")),
+                ("in_array", 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn mux_rejects_unknown_tag() {
+        let buf = [0xff, 0xff, 0, 0, 0, 0];
+        assert!(matches!(
+            TestMux::decode(&buf),
+            Err(WireError::UnknownTag(0xffff))
+        ));
+    }
+}